@@ -86,4 +86,72 @@ pub enum ErrorCode {
     RebalanceNotNeeded,
     #[msg("WSOL must be swept before rebalance.")]
     WsolNotCleared,
+    #[msg("Invalid rebalance leg.")]
+    InvalidRebalanceLeg,
+    #[msg("Rebalance session expired.")]
+    RebalanceSessionExpired,
+    #[msg("Allocation outside rebalance threshold.")]
+    RebalanceOutOfBand,
+    #[msg("Strategy change must respect the withdraw timelock.")]
+    StrategyTimelock,
+    #[msg("Rebalance session is in the wrong phase.")]
+    InvalidSessionPhase,
+    #[msg("Invalid fill amount.")]
+    InvalidFillAmount,
+    #[msg("Invalid order type.")]
+    InvalidOrderType,
+    #[msg("Invalid slippage tolerance.")]
+    InvalidSlippage,
+    #[msg("Invalid DCA price band.")]
+    InvalidDcaBand,
+    #[msg("Invalid DCA mode.")]
+    InvalidDcaMode,
+    #[msg("Invalid order amount.")]
+    InvalidOrderAmount,
+    #[msg("Order has not expired.")]
+    OrderNotExpired,
+    #[msg("NAV snapshot is stale.")]
+    StaleNavSnapshot,
+    #[msg("Invalid NAV history interval.")]
+    InvalidNavHistoryInterval,
+    #[msg("Whitelist entry is not a tradable token.")]
+    InvalidAssetKind,
+    #[msg("Fund nesting is limited to one level.")]
+    FundNestingNotAllowed,
+    #[msg("Fund redemption already pending.")]
+    FundRedemptionPending,
+    #[msg("No fund redemption pending.")]
+    NoFundRedemptionPending,
+    #[msg("Invalid fund role.")]
+    InvalidRole,
+    #[msg("Fund role table is full.")]
+    TooManyRoleMembers,
+    #[msg("Fund is paused.")]
+    FundPaused,
+    #[msg("Invalid manager.")]
+    InvalidManager,
+    #[msg("Manager transfer must respect the withdraw timelock.")]
+    ManagerTransferTimelock,
+    #[msg("No manager transfer pending.")]
+    NoPendingManager,
+    #[msg("Invalid admin.")]
+    InvalidAdmin,
+    #[msg("No admin transfer pending.")]
+    NoPendingAdmin,
+    #[msg("No config update pending.")]
+    NoPendingConfigUpdate,
+    #[msg("Config update timelock has not passed.")]
+    ConfigUpdateTimelock,
+    #[msg("Keeper tip exceeds the configured cap.")]
+    InvalidKeeperTip,
+    #[msg("Swap program registry is full.")]
+    TooManySwapPrograms,
+    #[msg("A rebalance session is open.")]
+    RebalanceSessionActive,
+    #[msg("Fund token limit reached.")]
+    TooManyFundTokens,
+    #[msg("Invalid NAV snapshot max age.")]
+    InvalidNavSnapshotAge,
+    #[msg("Fund proxy balance must be zero.")]
+    ProxyBalanceNotZero,
 }
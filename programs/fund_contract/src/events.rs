@@ -0,0 +1,109 @@
+use anchor_lang::prelude::*;
+
+use crate::state::dca_order::DcaOrder;
+use crate::state::limit_order::LimitOrder;
+
+#[event]
+pub struct LimitOrderClosed {
+    pub fund: Pubkey,
+    pub order: Pubkey,
+    pub side: u8,
+    pub order_type: u8,
+    pub mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub amount_in: u64,
+    pub filled_in: u64,
+    pub filled_out: u64,
+    pub created_ts: i64,
+    pub closed_ts: i64,
+    pub status: u8,
+}
+
+impl LimitOrderClosed {
+    pub fn new(order_key: Pubkey, order: &LimitOrder, closed_ts: i64) -> Self {
+        Self {
+            fund: order.fund,
+            order: order_key,
+            side: order.side,
+            order_type: order.order_type,
+            mint: order.mint,
+            quote_mint: order.quote_mint,
+            amount_in: order.amount_in,
+            filled_in: order.filled_in,
+            filled_out: order.filled_out,
+            created_ts: order.created_ts,
+            closed_ts,
+            status: order.status,
+        }
+    }
+}
+
+#[event]
+pub struct DcaOrderClosed {
+    pub fund: Pubkey,
+    pub order: Pubkey,
+    pub side: u8,
+    pub mode: u8,
+    pub mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub total_amount: u64,
+    pub remaining_amount: u64,
+    pub acquired_amount: u64,
+    pub period_count: u32,
+    pub closed_ts: i64,
+    pub status: u8,
+}
+
+impl DcaOrderClosed {
+    pub fn new(order_key: Pubkey, order: &DcaOrder, closed_ts: i64) -> Self {
+        Self {
+            fund: order.fund,
+            order: order_key,
+            side: order.side,
+            mode: order.mode,
+            mint: order.mint,
+            quote_mint: order.quote_mint,
+            total_amount: order.total_amount,
+            remaining_amount: order.remaining_amount,
+            acquired_amount: order.acquired_amount,
+            period_count: order.period_count,
+            closed_ts,
+            status: order.status,
+        }
+    }
+}
+
+#[event]
+pub struct ManagerTransferProposed {
+    pub fund: Pubkey,
+    pub manager: Pubkey,
+    pub pending_manager: Pubkey,
+    pub effective_ts: i64,
+    pub proposed_ts: i64,
+}
+
+#[event]
+pub struct ManagerTransferred {
+    pub fund: Pubkey,
+    pub previous_manager: Pubkey,
+    pub manager: Pubkey,
+    pub accepted_ts: i64,
+}
+
+#[event]
+pub struct KeeperTipPaid {
+    pub fund: Pubkey,
+    pub keeper: Pubkey,
+    pub notional_lamports: u64,
+    pub tip_lamports: u64,
+}
+
+#[event]
+pub struct PriceImprovementShared {
+    pub fund: Pubkey,
+    pub executor: Pubkey,
+    pub expected_out: u64,
+    pub filled_out: u64,
+    pub surplus_lamports: u64,
+    pub executor_lamports: u64,
+}
@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::state::global_config::GlobalConfig;
+
+pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    require!(
+        config.pending_admin != Pubkey::default(),
+        ErrorCode::NoPendingAdmin
+    );
+    require!(
+        config.pending_admin == ctx.accounts.new_admin.key(),
+        ErrorCode::Unauthorized
+    );
+    config.admin = config.pending_admin;
+    config.pending_admin = Pubkey::default();
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(config_id: u64)]
+pub struct AcceptAdmin<'info> {
+    #[account(
+        mut,
+        seeds = [b"config", config_id.to_le_bytes().as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, GlobalConfig>,
+    pub new_admin: Signer<'info>,
+}
@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::events::ManagerTransferred;
+use crate::state::fund::FundState;
+use crate::state::fund_roles::FundRoles;
+
+pub fn accept_manager(ctx: Context<AcceptManager>) -> Result<()> {
+    let new_manager = ctx.accounts.new_manager.key();
+    let fund_key = ctx.accounts.fund_state.key();
+    let fund_state = &mut ctx.accounts.fund_state;
+    require!(
+        fund_state.pending_manager != Pubkey::default(),
+        ErrorCode::NoPendingManager
+    );
+    require!(
+        fund_state.pending_manager == new_manager,
+        ErrorCode::Unauthorized
+    );
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        now >= fund_state.pending_manager_effective_ts,
+        ErrorCode::ManagerTransferTimelock
+    );
+
+    let previous_manager = fund_state.manager;
+    fund_state.manager = new_manager;
+    fund_state.pending_manager = Pubkey::default();
+    fund_state.pending_manager_effective_ts = 0;
+
+    if let Some(fund_roles) = ctx.accounts.fund_roles.as_mut() {
+        fund_roles.members.retain(|entry| entry.key != new_manager);
+    }
+
+    emit!(ManagerTransferred {
+        fund: fund_key,
+        previous_manager,
+        manager: new_manager,
+        accepted_ts: now,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AcceptManager<'info> {
+    pub new_manager: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"fund", fund_state.config.as_ref(), fund_state.creator.as_ref(), fund_state.fund_id.to_le_bytes().as_ref()],
+        bump = fund_state.bump,
+    )]
+    pub fund_state: Account<'info, FundState>,
+    #[account(
+        mut,
+        seeds = [b"fund_roles", fund_state.key().as_ref()],
+        bump = fund_roles.bump
+    )]
+    pub fund_roles: Option<Account<'info, FundRoles>>,
+}
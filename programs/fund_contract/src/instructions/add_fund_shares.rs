@@ -0,0 +1,123 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::errors::ErrorCode;
+use crate::instructions::set_fund_role::require_fund_role;
+use crate::state::fund::{FundState, FUND_TYPE_TRADING};
+use crate::state::fund_roles::{FundRoles, ROLE_OPS};
+use crate::state::global_config::GlobalConfig;
+use crate::state::nav_snapshot::MAX_NAV_ASSETS;
+use crate::state::whitelist::{FundWhitelist, ASSET_KIND_FUND_SHARES};
+
+pub fn add_fund_shares(ctx: Context<AddFundShares>) -> Result<()> {
+    require_fund_role(
+        ctx.accounts.fund_state.key(),
+        &ctx.accounts.fund_state,
+        ctx.accounts.fund_roles.as_ref(),
+        &ctx.accounts.manager.key(),
+        ROLE_OPS,
+    )?;
+    require!(
+        ctx.accounts.fund_state.fund_type == FUND_TYPE_TRADING,
+        ErrorCode::InvalidFundType
+    );
+    require!(
+        ctx.accounts.underlying_fund.key() != ctx.accounts.fund_state.key(),
+        ErrorCode::FundNestingNotAllowed
+    );
+    require!(
+        ctx.accounts.fund_state.holder_count == 0,
+        ErrorCode::FundNestingNotAllowed
+    );
+    require!(
+        ctx.accounts.underlying_fund.held_fund_count == 0,
+        ErrorCode::FundNestingNotAllowed
+    );
+    require!(
+        (ctx.accounts.fund_state.enabled_token_count as usize) < MAX_NAV_ASSETS,
+        ErrorCode::TooManyFundTokens
+    );
+
+    let whitelist = &mut ctx.accounts.fund_whitelist;
+    whitelist.fund = ctx.accounts.fund_state.key();
+    whitelist.mint = ctx.accounts.share_mint.key();
+    whitelist.decimals = ctx.accounts.share_mint.decimals;
+    whitelist.pyth_feed = Pubkey::default();
+    whitelist.enabled = true;
+    whitelist.escrowed_amount = 0;
+    whitelist.kind = ASSET_KIND_FUND_SHARES;
+    whitelist.underlying_fund = ctx.accounts.underlying_fund.key();
+    whitelist.bump = ctx.bumps.fund_whitelist;
+
+    let fund_state = &mut ctx.accounts.fund_state;
+    fund_state.enabled_token_count = fund_state
+        .enabled_token_count
+        .checked_add(1)
+        .ok_or(ErrorCode::MathOverflow)?;
+    fund_state.held_fund_count = fund_state
+        .held_fund_count
+        .checked_add(1)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    let underlying_fund = &mut ctx.accounts.underlying_fund;
+    underlying_fund.holder_count = underlying_fund
+        .holder_count
+        .checked_add(1)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AddFundShares<'info> {
+    #[account(mut)]
+    pub manager: Signer<'info>,
+    #[account(
+        seeds = [b"config", config.config_id.to_le_bytes().as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, GlobalConfig>,
+    #[account(
+        mut,
+        seeds = [b"fund", config.key().as_ref(), fund_state.creator.as_ref(), fund_state.fund_id.to_le_bytes().as_ref()],
+        bump = fund_state.bump,
+        has_one = config
+    )]
+    pub fund_state: Account<'info, FundState>,
+    #[account(
+        mut,
+        seeds = [b"fund", config.key().as_ref(), underlying_fund.creator.as_ref(), underlying_fund.fund_id.to_le_bytes().as_ref()],
+        bump = underlying_fund.bump,
+        has_one = config
+    )]
+    pub underlying_fund: Account<'info, FundState>,
+    #[account(
+        seeds = [b"shares", underlying_fund.key().as_ref()],
+        bump = underlying_fund.share_mint_bump
+    )]
+    pub share_mint: Account<'info, Mint>,
+    #[account(
+        init,
+        payer = manager,
+        space = 8 + FundWhitelist::LEN,
+        seeds = [b"whitelist", fund_state.key().as_ref(), share_mint.key().as_ref()],
+        bump
+    )]
+    pub fund_whitelist: Account<'info, FundWhitelist>,
+    #[account(
+        init_if_needed,
+        payer = manager,
+        associated_token::mint = share_mint,
+        associated_token::authority = fund_state
+    )]
+    pub fund_token_vault: Account<'info, TokenAccount>,
+    #[account(
+        seeds = [b"fund_roles", fund_state.key().as_ref()],
+        bump = fund_roles.bump
+    )]
+    pub fund_roles: Option<Account<'info, FundRoles>>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
@@ -4,9 +4,12 @@ use anchor_spl::associated_token::{create, AssociatedToken};
 use anchor_spl::token::{Mint, Token, TokenAccount};
 
 use crate::errors::ErrorCode;
+use crate::instructions::set_fund_role::require_fund_role;
 use crate::state::fund::FundState;
+use crate::state::fund_roles::{FundRoles, ROLE_OPS};
 use crate::state::global_config::GlobalConfig;
-use crate::state::whitelist::{FundWhitelist, GlobalWhitelist};
+use crate::state::nav_snapshot::MAX_NAV_ASSETS;
+use crate::state::whitelist::{FundWhitelist, GlobalWhitelist, ASSET_KIND_TOKEN};
 
 const SCOPE_GLOBAL: u8 = 0;
 const SCOPE_FUND: u8 = 1;
@@ -105,7 +108,7 @@ fn add_fund<'info>(
         &[
             b"fund",
             ctx.accounts.config.key().as_ref(),
-            fund_state.manager.as_ref(),
+            fund_state.creator.as_ref(),
             fund_state.fund_id.to_le_bytes().as_ref(),
         ],
         ctx.program_id,
@@ -118,11 +121,18 @@ fn add_fund<'info>(
         fund_state.config == ctx.accounts.config.key(),
         ErrorCode::InvalidTokenVault
     );
+    require_fund_role(
+        *fund_state_info.key,
+        &fund_state,
+        ctx.accounts.fund_roles.as_ref(),
+        &ctx.accounts.authority.key(),
+        ROLE_OPS,
+    )?;
+    require!(fund_state.fund_id == fund_id, ErrorCode::InvalidTokenVault);
     require!(
-        fund_state.manager == ctx.accounts.authority.key(),
-        ErrorCode::Unauthorized
+        (fund_state.enabled_token_count as usize) < MAX_NAV_ASSETS,
+        ErrorCode::TooManyFundTokens
     );
-    require!(fund_state.fund_id == fund_id, ErrorCode::InvalidTokenVault);
 
     let global_whitelist =
         load_global_whitelist(&ctx.accounts.global_whitelist, ctx.program_id)?;
@@ -189,6 +199,9 @@ fn add_fund<'info>(
         decimals: global_whitelist.decimals,
         pyth_feed: global_whitelist.pyth_feed,
         enabled: true,
+        escrowed_amount: 0,
+        kind: ASSET_KIND_TOKEN,
+        underlying_fund: Pubkey::default(),
         bump: fund_whitelist_bump,
     };
     whitelist.try_serialize(&mut writer)?;
@@ -260,6 +273,7 @@ pub struct AddToken<'info> {
     /// CHECK: created or validated in handler.
     #[account(mut)]
     pub global_whitelist: UncheckedAccount<'info>,
+    pub fund_roles: Option<Account<'info, FundRoles>>,
     pub system_program: Program<'info, System>,
     pub token_program: Program<'info, Token>,
     pub associated_token_program: Program<'info, AssociatedToken>,
@@ -0,0 +1,148 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+
+use crate::errors::ErrorCode;
+use crate::instructions::set_fund_role::require_fund_role;
+use crate::instructions::amend_limit_order::{adjust_order_escrow, OrderEscrow};
+use crate::instructions::create_dca_order::validate_price_band;
+use crate::instructions::create_limit_order::track_order_escrow;
+use crate::state::dca_order::{DcaOrder, DCA_STATUS_OPEN};
+use crate::state::fund::{FundState, FundVault, FUND_TYPE_TRADING};
+use crate::state::fund_roles::{FundRoles, ROLE_TRADER};
+use crate::state::global_config::GlobalConfig;
+use crate::state::whitelist::FundWhitelist;
+
+pub fn amend_dca_order<'info>(
+    ctx: Context<'_, '_, 'info, 'info, AmendDcaOrder<'info>>,
+    total_amount: u64,
+    slice_amount: u64,
+    interval_secs: i64,
+    min_out: u64,
+    expiry_ts: i64,
+    band_lower_price: i64,
+    band_upper_price: i64,
+) -> Result<()> {
+    require_fund_role(
+        ctx.accounts.fund_state.key(),
+        &ctx.accounts.fund_state,
+        ctx.accounts.fund_roles.as_ref(),
+        &ctx.accounts.manager.key(),
+        ROLE_TRADER,
+    )?;
+    require!(!ctx.accounts.fund_state.paused, ErrorCode::FundPaused);
+    require!(
+        ctx.accounts.fund_state.fund_type == FUND_TYPE_TRADING,
+        ErrorCode::InvalidFundType
+    );
+
+    let order = &mut ctx.accounts.order;
+    require!(order.status == DCA_STATUS_OPEN, ErrorCode::OrderNotOpen);
+    require!(order.fund == ctx.accounts.fund_state.key(), ErrorCode::InvalidTokenVault);
+
+    let executed = order
+        .total_amount
+        .checked_sub(order.remaining_amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+    require!(total_amount > executed, ErrorCode::InvalidOrderAmount);
+    require!(slice_amount > 0, ErrorCode::InvalidDcaSlice);
+    require!(slice_amount <= total_amount, ErrorCode::InvalidDcaSlice);
+    require!(interval_secs > 0, ErrorCode::InvalidDcaInterval);
+    validate_price_band(band_lower_price, band_upper_price)?;
+
+    let remaining_amount = total_amount
+        .checked_sub(executed)
+        .ok_or(ErrorCode::MathOverflow)?;
+    adjust_order_escrow(
+        ctx.program_id,
+        &order.key(),
+        OrderEscrow {
+            sol_vault_seed: b"dca_order_sol_vault",
+            vault_auth_seed: b"dca_order_vault_auth",
+            escrows_sol: order.escrows_sol(),
+            escrow_mint: order.escrow_mint(),
+            fund_state: &ctx.accounts.fund_state,
+            fund_vault: ctx.accounts.fund_vault.to_account_info(),
+            fund_token_vault: &ctx.accounts.fund_token_vault,
+            order_sol_vault: ctx.accounts.order_sol_vault.to_account_info(),
+            order_vault_auth: ctx.accounts.order_vault_auth.to_account_info(),
+            order_token_vault: &ctx.accounts.order_token_vault,
+            token_program: ctx.accounts.token_program.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+        },
+        order.remaining_amount,
+        remaining_amount,
+    )?;
+    track_order_escrow(
+        order.escrows_sol(),
+        &order.escrow_mint(),
+        &mut ctx.accounts.fund_state,
+        &mut ctx.accounts.whitelist,
+        order.remaining_amount,
+        remaining_amount,
+    )?;
+
+    order.next_exec_ts = order
+        .next_exec_ts
+        .checked_sub(order.interval_secs)
+        .and_then(|ts| ts.checked_add(interval_secs))
+        .ok_or(ErrorCode::MathOverflow)?;
+    order.total_amount = total_amount;
+    order.remaining_amount = remaining_amount;
+    order.slice_amount = slice_amount;
+    order.interval_secs = interval_secs;
+    order.min_out = min_out;
+    order.expiry_ts = expiry_ts;
+    order.band_lower_price = band_lower_price;
+    order.band_upper_price = band_upper_price;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(order_id: u64)]
+pub struct AmendDcaOrder<'info> {
+    #[account(mut)]
+    pub manager: Signer<'info>,
+    #[account(
+        seeds = [b"config", config.config_id.to_le_bytes().as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, GlobalConfig>,
+    #[account(
+        mut,
+        seeds = [b"fund", config.key().as_ref(), fund_state.creator.as_ref(), fund_state.fund_id.to_le_bytes().as_ref()],
+        bump = fund_state.bump,
+        has_one = config
+    )]
+    pub fund_state: Account<'info, FundState>,
+    #[account(
+        mut,
+        seeds = [b"vault", fund_state.key().as_ref()],
+        bump = fund_state.vault_bump
+    )]
+    pub fund_vault: Account<'info, FundVault>,
+    #[account(mut)]
+    pub whitelist: Account<'info, FundWhitelist>,
+    #[account(mut)]
+    pub fund_token_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"dca_order", fund_state.key().as_ref(), order_id.to_le_bytes().as_ref()],
+        bump = order.bump
+    )]
+    pub order: Account<'info, DcaOrder>,
+    /// CHECK: validated against derived PDA in handler
+    #[account(mut)]
+    pub order_sol_vault: UncheckedAccount<'info>,
+    /// CHECK: PDA authority for DCA order vault
+    pub order_vault_auth: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub order_token_vault: Account<'info, TokenAccount>,
+    #[account(
+        seeds = [b"fund_roles", fund_state.key().as_ref()],
+        bump = fund_roles.bump
+    )]
+    pub fund_roles: Option<Account<'info, FundRoles>>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,264 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount};
+
+use crate::errors::ErrorCode;
+use crate::instructions::set_fund_role::require_fund_role;
+use crate::instructions::create_limit_order::{track_order_escrow, validate_order_prices};
+use crate::state::fund::{FundState, FundVault, FUND_TYPE_TRADING};
+use crate::state::fund_roles::{FundRoles, ROLE_TRADER};
+use crate::state::global_config::GlobalConfig;
+use crate::state::limit_order::{LimitOrder, ORDER_STATUS_OPEN, ORDER_TYPE_TRAILING_STOP};
+use crate::state::whitelist::FundWhitelist;
+
+pub fn amend_limit_order<'info>(
+    ctx: Context<'_, '_, 'info, 'info, AmendLimitOrder<'info>>,
+    amount_in: u64,
+    min_out: u64,
+    limit_price: i64,
+    stop_price: i64,
+    trail_bps: u16,
+    expiry_ts: i64,
+) -> Result<()> {
+    require_fund_role(
+        ctx.accounts.fund_state.key(),
+        &ctx.accounts.fund_state,
+        ctx.accounts.fund_roles.as_ref(),
+        &ctx.accounts.manager.key(),
+        ROLE_TRADER,
+    )?;
+    require!(!ctx.accounts.fund_state.paused, ErrorCode::FundPaused);
+    require!(
+        ctx.accounts.fund_state.fund_type == FUND_TYPE_TRADING,
+        ErrorCode::InvalidFundType
+    );
+
+    let order = &mut ctx.accounts.order;
+    require!(order.status == ORDER_STATUS_OPEN, ErrorCode::OrderNotOpen);
+    require!(order.fund == ctx.accounts.fund_state.key(), ErrorCode::InvalidTokenVault);
+    require!(min_out > 0, ErrorCode::InvalidMinOut);
+    require!(amount_in > order.filled_in, ErrorCode::InvalidOrderAmount);
+    validate_order_prices(order.side, order.order_type, limit_price, stop_price, trail_bps)?;
+
+    let current = order
+        .amount_in
+        .checked_sub(order.filled_in)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let target = amount_in
+        .checked_sub(order.filled_in)
+        .ok_or(ErrorCode::MathOverflow)?;
+    adjust_order_escrow(
+        ctx.program_id,
+        &order.key(),
+        OrderEscrow {
+            sol_vault_seed: b"limit_order_sol_vault",
+            vault_auth_seed: b"limit_order_vault_auth",
+            escrows_sol: order.escrows_sol(),
+            escrow_mint: order.escrow_mint(),
+            fund_state: &ctx.accounts.fund_state,
+            fund_vault: ctx.accounts.fund_vault.to_account_info(),
+            fund_token_vault: &ctx.accounts.fund_token_vault,
+            order_sol_vault: ctx.accounts.order_sol_vault.to_account_info(),
+            order_vault_auth: ctx.accounts.order_vault_auth.to_account_info(),
+            order_token_vault: &ctx.accounts.order_token_vault,
+            token_program: ctx.accounts.token_program.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+        },
+        current,
+        target,
+    )?;
+    track_order_escrow(
+        order.escrows_sol(),
+        &order.escrow_mint(),
+        &mut ctx.accounts.fund_state,
+        &mut ctx.accounts.whitelist,
+        current,
+        target,
+    )?;
+
+    if order.order_type == ORDER_TYPE_TRAILING_STOP && stop_price != order.stop_price {
+        order.peak_price = stop_price;
+    }
+    order.amount_in = amount_in;
+    order.min_out = min_out;
+    order.limit_price = limit_price;
+    order.stop_price = stop_price;
+    order.trail_bps = trail_bps;
+    order.expiry_ts = expiry_ts;
+
+    Ok(())
+}
+
+pub(crate) struct OrderEscrow<'a, 'info> {
+    pub(crate) sol_vault_seed: &'a [u8],
+    pub(crate) vault_auth_seed: &'a [u8],
+    pub(crate) escrows_sol: bool,
+    pub(crate) escrow_mint: Pubkey,
+    pub(crate) fund_state: &'a Account<'info, FundState>,
+    pub(crate) fund_vault: AccountInfo<'info>,
+    pub(crate) fund_token_vault: &'a Account<'info, TokenAccount>,
+    pub(crate) order_sol_vault: AccountInfo<'info>,
+    pub(crate) order_vault_auth: AccountInfo<'info>,
+    pub(crate) order_token_vault: &'a Account<'info, TokenAccount>,
+    pub(crate) token_program: AccountInfo<'info>,
+    pub(crate) system_program: AccountInfo<'info>,
+}
+
+pub(crate) fn adjust_order_escrow(
+    program_id: &Pubkey,
+    order_key: &Pubkey,
+    escrow: OrderEscrow,
+    current: u64,
+    target: u64,
+) -> Result<()> {
+    let (vault_auth, vault_auth_bump) =
+        Pubkey::find_program_address(&[escrow.vault_auth_seed, order_key.as_ref()], program_id);
+    require!(vault_auth == *escrow.order_vault_auth.key, ErrorCode::InvalidOrderVault);
+
+    if escrow.escrows_sol {
+        let (sol_vault, sol_vault_bump) =
+            Pubkey::find_program_address(&[escrow.sol_vault_seed, order_key.as_ref()], program_id);
+        require!(sol_vault == *escrow.order_sol_vault.key, ErrorCode::InvalidOrderVault);
+        require!(
+            escrow.order_sol_vault.owner == &anchor_lang::solana_program::system_program::ID,
+            ErrorCode::InvalidOrderVault
+        );
+
+        if target > current {
+            let delta = target - current;
+            let mut fund_lamports = escrow.fund_vault.try_borrow_mut_lamports()?;
+            let mut order_lamports = escrow.order_sol_vault.try_borrow_mut_lamports()?;
+            **fund_lamports = (**fund_lamports)
+                .checked_sub(delta)
+                .ok_or(ErrorCode::InsufficientLiquidity)?;
+            **order_lamports = (**order_lamports)
+                .checked_add(delta)
+                .ok_or(ErrorCode::MathOverflow)?;
+        } else if current > target {
+            let delta = current - target;
+            require!(
+                escrow.order_sol_vault.lamports() >= delta,
+                ErrorCode::InsufficientLiquidity
+            );
+            let sol_vault_seeds: &[&[u8]] =
+                &[escrow.sol_vault_seed, order_key.as_ref(), &[sol_vault_bump]];
+            let sol_vault_signer = [sol_vault_seeds];
+            let transfer_ctx = CpiContext::new_with_signer(
+                escrow.system_program.clone(),
+                anchor_lang::system_program::Transfer {
+                    from: escrow.order_sol_vault.clone(),
+                    to: escrow.fund_vault.clone(),
+                },
+                &sol_vault_signer,
+            );
+            anchor_lang::system_program::transfer(transfer_ctx, delta)?;
+        }
+        return Ok(());
+    }
+
+    let fund_key = escrow.fund_state.key();
+    let expected_order_vault =
+        anchor_spl::associated_token::get_associated_token_address(&vault_auth, &escrow.escrow_mint);
+    require!(
+        expected_order_vault == escrow.order_token_vault.key(),
+        ErrorCode::InvalidOrderVault
+    );
+    require!(
+        escrow.order_token_vault.owner == vault_auth,
+        ErrorCode::InvalidOrderVault
+    );
+    require!(escrow.order_token_vault.amount == current, ErrorCode::InvalidOrderVault);
+    let expected_fund_vault =
+        anchor_spl::associated_token::get_associated_token_address(&fund_key, &escrow.escrow_mint);
+    require!(
+        expected_fund_vault == escrow.fund_token_vault.key(),
+        ErrorCode::InvalidTokenVault
+    );
+    require!(escrow.fund_token_vault.owner == fund_key, ErrorCode::InvalidTokenVault);
+
+    if target > current {
+        let fund_id_bytes = escrow.fund_state.fund_id.to_le_bytes();
+        let signer_seeds: &[&[u8]] = &[
+            b"fund",
+            escrow.fund_state.config.as_ref(),
+            escrow.fund_state.creator.as_ref(),
+            fund_id_bytes.as_ref(),
+            &[escrow.fund_state.bump],
+        ];
+        let signer_seeds_set = [signer_seeds];
+        let transfer_ctx = CpiContext::new_with_signer(
+            escrow.token_program.clone(),
+            token::Transfer {
+                from: escrow.fund_token_vault.to_account_info(),
+                to: escrow.order_token_vault.to_account_info(),
+                authority: escrow.fund_state.to_account_info(),
+            },
+            &signer_seeds_set,
+        );
+        token::transfer(transfer_ctx, target - current)?;
+    } else if current > target {
+        let signer_seeds: &[&[u8]] =
+            &[escrow.vault_auth_seed, order_key.as_ref(), &[vault_auth_bump]];
+        let signer_seeds_set = [signer_seeds];
+        let transfer_ctx = CpiContext::new_with_signer(
+            escrow.token_program.clone(),
+            token::Transfer {
+                from: escrow.order_token_vault.to_account_info(),
+                to: escrow.fund_token_vault.to_account_info(),
+                authority: escrow.order_vault_auth.clone(),
+            },
+            &signer_seeds_set,
+        );
+        token::transfer(transfer_ctx, current - target)?;
+    }
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(order_id: u64)]
+pub struct AmendLimitOrder<'info> {
+    #[account(mut)]
+    pub manager: Signer<'info>,
+    #[account(
+        seeds = [b"config", config.config_id.to_le_bytes().as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, GlobalConfig>,
+    #[account(
+        mut,
+        seeds = [b"fund", config.key().as_ref(), fund_state.creator.as_ref(), fund_state.fund_id.to_le_bytes().as_ref()],
+        bump = fund_state.bump,
+        has_one = config
+    )]
+    pub fund_state: Account<'info, FundState>,
+    #[account(
+        mut,
+        seeds = [b"vault", fund_state.key().as_ref()],
+        bump = fund_state.vault_bump
+    )]
+    pub fund_vault: Account<'info, FundVault>,
+    #[account(mut)]
+    pub whitelist: Account<'info, FundWhitelist>,
+    #[account(mut)]
+    pub fund_token_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"limit_order", fund_state.key().as_ref(), order_id.to_le_bytes().as_ref()],
+        bump = order.bump
+    )]
+    pub order: Account<'info, LimitOrder>,
+    /// CHECK: validated against derived PDA in handler
+    #[account(mut)]
+    pub order_sol_vault: UncheckedAccount<'info>,
+    /// CHECK: PDA authority for order token vault
+    pub order_vault_auth: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub order_token_vault: Account<'info, TokenAccount>,
+    #[account(
+        seeds = [b"fund_roles", fund_state.key().as_ref()],
+        bump = fund_roles.bump
+    )]
+    pub fund_roles: Option<Account<'info, FundRoles>>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
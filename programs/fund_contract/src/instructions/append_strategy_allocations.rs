@@ -0,0 +1,82 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::instructions::set_strategy::{resize_strategy, StrategyAllocationInput};
+use crate::state::fund::{FundState, FUND_TYPE_STRATEGY};
+use crate::state::strategy::{Strategy, StrategyAllocation, MAX_STRATEGY_TOKENS};
+use crate::state::whitelist::FundWhitelist;
+
+pub fn append_strategy_allocations<'info>(
+    ctx: Context<'_, '_, 'info, 'info, AppendStrategyAllocations<'info>>,
+    allocations: Vec<StrategyAllocationInput>,
+) -> Result<()> {
+    require!(
+        ctx.accounts.fund_state.manager == ctx.accounts.manager.key(),
+        ErrorCode::Unauthorized
+    );
+    require!(
+        ctx.accounts.fund_state.fund_type == FUND_TYPE_STRATEGY,
+        ErrorCode::InvalidFundType
+    );
+    require!(!allocations.is_empty(), ErrorCode::InvalidStrategy);
+    require!(
+        ctx.remaining_accounts.len() == allocations.len(),
+        ErrorCode::InvalidRemainingAccounts
+    );
+
+    let fund_key = ctx.accounts.fund_state.key();
+    let strategy = &mut ctx.accounts.strategy;
+    require!(
+        strategy.draft_allocations.len() + allocations.len() <= MAX_STRATEGY_TOKENS,
+        ErrorCode::InvalidStrategy
+    );
+
+    for (idx, alloc) in allocations.iter().enumerate() {
+        let whitelist_info = &ctx.remaining_accounts[idx];
+        let whitelist: Account<FundWhitelist> =
+            Account::try_from(whitelist_info).map_err(|_| ErrorCode::InvalidTokenVault)?;
+        require!(whitelist.enabled, ErrorCode::InvalidTokenVault);
+        require!(whitelist.fund == fund_key, ErrorCode::InvalidTokenVault);
+        require!(whitelist.mint == alloc.mint, ErrorCode::InvalidTokenVault);
+        require!(
+            !strategy.draft_allocations.iter().any(|draft| draft.mint == alloc.mint),
+            ErrorCode::InvalidStrategy
+        );
+        strategy.draft_allocations.push(StrategyAllocation {
+            mint: alloc.mint,
+            weight_bps: alloc.weight_bps,
+            start_weight_bps: alloc.weight_bps,
+        });
+    }
+
+    if strategy.fund == Pubkey::default() {
+        strategy.fund = fund_key;
+        strategy.bump = ctx.bumps.strategy;
+    }
+
+    resize_strategy(
+        &ctx.accounts.strategy,
+        &ctx.accounts.manager,
+        &ctx.accounts.system_program,
+    )
+}
+
+#[derive(Accounts)]
+pub struct AppendStrategyAllocations<'info> {
+    #[account(mut)]
+    pub manager: Signer<'info>,
+    #[account(
+        seeds = [b"fund", fund_state.config.as_ref(), fund_state.creator.as_ref(), fund_state.fund_id.to_le_bytes().as_ref()],
+        bump = fund_state.bump,
+    )]
+    pub fund_state: Account<'info, FundState>,
+    #[account(
+        init_if_needed,
+        payer = manager,
+        space = 8 + Strategy::space(0, 0, 0),
+        seeds = [b"strategy", fund_state.key().as_ref()],
+        bump
+    )]
+    pub strategy: Account<'info, Strategy>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::state::global_config::GlobalConfig;
+
+pub fn apply_config_update(ctx: Context<ApplyConfigUpdate>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    require!(config.has_pending_update, ErrorCode::NoPendingConfigUpdate);
+    let now = Clock::get()?.unix_timestamp;
+    require!(config.activate_pending(now), ErrorCode::ConfigUpdateTimelock);
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(config_id: u64)]
+pub struct ApplyConfigUpdate<'info> {
+    #[account(
+        mut,
+        seeds = [b"config", config_id.to_le_bytes().as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, GlobalConfig>,
+}
@@ -6,10 +6,12 @@ use anchor_lang::solana_program::sysvar::instructions::ID as IX_SYSVAR_ID;
 use anchor_spl::token::TokenAccount;
 
 use crate::errors::ErrorCode;
+use crate::instructions::set_fund_role::require_fund_role;
 use crate::state::fund::{FundState, FundVault, FUND_TYPE_TRADING};
+use crate::state::fund_roles::{FundRoles, ROLE_TRADER};
 use crate::state::global_config::GlobalConfig;
 use crate::state::trading::Trading;
-use crate::state::whitelist::FundWhitelist;
+use crate::state::whitelist::{FundWhitelist, ASSET_KIND_TOKEN};
 
 pub fn borrow_for_swap<'info>(
     ctx: Context<'_, '_, 'info, 'info, BorrowForSwap<'info>>,
@@ -24,15 +26,23 @@ pub fn borrow_for_swap<'info>(
         ErrorCode::InvalidFundType
     );
 
-    require!(
-        ctx.accounts.fund_state.manager == ctx.accounts.manager.key(),
-        ErrorCode::Unauthorized
-    );
+    require_fund_role(
+        ctx.accounts.fund_state.key(),
+        &ctx.accounts.fund_state,
+        ctx.accounts.fund_roles.as_ref(),
+        &ctx.accounts.manager.key(),
+        ROLE_TRADER,
+    )?;
+    require!(!ctx.accounts.fund_state.paused, ErrorCode::FundPaused);
     require!(
         ctx.accounts.manager_receive.key() == ctx.accounts.manager.key(),
         ErrorCode::InvalidReceiver
     );
     require!(ctx.accounts.output_whitelist.enabled, ErrorCode::InvalidTokenVault);
+    require!(
+        ctx.accounts.output_whitelist.kind == ASSET_KIND_TOKEN,
+        ErrorCode::InvalidAssetKind
+    );
     require!(
         ctx.accounts.output_whitelist.fund == ctx.accounts.fund_state.key(),
         ErrorCode::InvalidTokenVault
@@ -136,7 +146,7 @@ pub struct BorrowForSwap<'info> {
     pub config: Account<'info, GlobalConfig>,
     #[account(
         mut,
-        seeds = [b"fund", config.key().as_ref(), fund_state.manager.as_ref(), fund_state.fund_id.to_le_bytes().as_ref()],
+        seeds = [b"fund", config.key().as_ref(), fund_state.creator.as_ref(), fund_state.fund_id.to_le_bytes().as_ref()],
         bump = fund_state.bump,
         has_one = config
     )]
@@ -161,5 +171,10 @@ pub struct BorrowForSwap<'info> {
     pub output_token_vault: Account<'info, TokenAccount>,
     /// CHECK: instruction sysvar
     pub instructions_sysvar: AccountInfo<'info>,
+    #[account(
+        seeds = [b"fund_roles", fund_state.key().as_ref()],
+        bump = fund_roles.bump
+    )]
+    pub fund_roles: Option<Account<'info, FundRoles>>,
     pub system_program: Program<'info, System>,
 }
@@ -3,8 +3,13 @@ use anchor_spl::token::{self, CloseAccount, Token, TokenAccount};
 use anchor_spl::token::spl_token::native_mint;
 
 use crate::errors::ErrorCode;
+use crate::instructions::set_fund_role::require_fund_role;
+use crate::events::DcaOrderClosed;
+use crate::instructions::create_limit_order::track_order_escrow;
+use crate::instructions::expire_limit_order::drain_order_sol_vault;
 use crate::state::dca_order::{DcaOrder, DCA_SIDE_BUY, DCA_SIDE_SELL, DCA_STATUS_CANCELLED, DCA_STATUS_OPEN};
 use crate::state::fund::{FundState, FundVault, FUND_TYPE_TRADING};
+use crate::state::fund_roles::{FundRoles, ROLE_TRADER, ROLE_RISK_OFFICER};
 use crate::state::global_config::GlobalConfig;
 use crate::state::whitelist::FundWhitelist;
 
@@ -13,7 +18,13 @@ pub fn cancel_dca_order<'info>(
 ) -> Result<()> {
     let order = &mut ctx.accounts.order;
     require!(order.status == DCA_STATUS_OPEN, ErrorCode::OrderNotOpen);
-    require!(ctx.accounts.fund_state.manager == ctx.accounts.manager.key(), ErrorCode::Unauthorized);
+    require_fund_role(
+        ctx.accounts.fund_state.key(),
+        &ctx.accounts.fund_state,
+        ctx.accounts.fund_roles.as_ref(),
+        &ctx.accounts.manager.key(),
+        ROLE_TRADER | ROLE_RISK_OFFICER,
+    )?;
     require!(
         ctx.accounts.fund_state.fund_type == FUND_TYPE_TRADING,
         ErrorCode::InvalidFundType
@@ -38,7 +49,7 @@ pub fn cancel_dca_order<'info>(
     );
 
     match order.side {
-        DCA_SIDE_BUY => {
+        DCA_SIDE_BUY if order.escrows_sol() => {
             let expected_wsol_vault =
                 anchor_spl::associated_token::get_associated_token_address(
                     &vault_auth,
@@ -57,24 +68,18 @@ pub fn cancel_dca_order<'info>(
                 ErrorCode::InvalidOrderVault
             );
 
-            let sol_balance = ctx.accounts.order_sol_vault.to_account_info().lamports();
-            if sol_balance > 0 {
-                let sol_vault_seeds: &[&[u8]] = &[
-                    b"dca_order_sol_vault",
-                    order_key.as_ref(),
-                    &[sol_vault_bump],
-                ];
-                let sol_vault_signer = [sol_vault_seeds];
-                let transfer_ctx = CpiContext::new_with_signer(
-                    ctx.accounts.system_program.to_account_info(),
-                    anchor_lang::system_program::Transfer {
-                        from: ctx.accounts.order_sol_vault.to_account_info(),
-                        to: ctx.accounts.fund_vault.to_account_info(),
-                    },
-                    &sol_vault_signer,
-                );
-                anchor_lang::system_program::transfer(transfer_ctx, sol_balance)?;
-            }
+            let sol_vault_seeds: &[&[u8]] = &[
+                b"dca_order_sol_vault",
+                order_key.as_ref(),
+                &[sol_vault_bump],
+            ];
+            drain_order_sol_vault(
+                &ctx.accounts.order_sol_vault.to_account_info(),
+                &ctx.accounts.fund_vault.to_account_info(),
+                &ctx.accounts.manager.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
+                sol_vault_seeds,
+            )?;
 
             let signer_seeds: &[&[u8]] = &[
                 b"dca_order_vault_auth",
@@ -87,7 +92,7 @@ pub fn cancel_dca_order<'info>(
                     ctx.accounts.token_program.to_account_info(),
                     CloseAccount {
                         account: ctx.accounts.order_token_vault.to_account_info(),
-                        destination: ctx.accounts.fund_vault.to_account_info(),
+                        destination: ctx.accounts.manager.to_account_info(),
                         authority: ctx.accounts.order_vault_auth.to_account_info(),
                     },
                     &signer_seeds_set,
@@ -97,18 +102,19 @@ pub fn cancel_dca_order<'info>(
                 return err!(ErrorCode::InvalidOrderVault);
             }
         }
-        DCA_SIDE_SELL => {
+        DCA_SIDE_BUY | DCA_SIDE_SELL => {
+            let escrow_mint = order.escrow_mint();
             let expected_order_vault =
                 anchor_spl::associated_token::get_associated_token_address(
                     &vault_auth,
-                    &order.mint,
+                    &escrow_mint,
                 );
             require!(
                 expected_order_vault == ctx.accounts.order_token_vault.key(),
                 ErrorCode::InvalidOrderVault
             );
             require!(
-                ctx.accounts.order_token_vault.mint == order.mint,
+                ctx.accounts.order_token_vault.mint == escrow_mint,
                 ErrorCode::InvalidOrderVault
             );
             require!(
@@ -117,7 +123,7 @@ pub fn cancel_dca_order<'info>(
             );
 
             require!(ctx.accounts.fund_token_vault.owner == ctx.accounts.fund_state.key(), ErrorCode::InvalidTokenVault);
-            require!(ctx.accounts.fund_token_vault.mint == order.mint, ErrorCode::InvalidTokenVault);
+            require!(ctx.accounts.fund_token_vault.mint == escrow_mint, ErrorCode::InvalidTokenVault);
 
             let signer_seeds: &[&[u8]] = &[
                 b"dca_order_vault_auth",
@@ -144,7 +150,7 @@ pub fn cancel_dca_order<'info>(
                 ctx.accounts.token_program.to_account_info(),
                 CloseAccount {
                     account: ctx.accounts.order_token_vault.to_account_info(),
-                    destination: ctx.accounts.fund_vault.to_account_info(),
+                    destination: ctx.accounts.manager.to_account_info(),
                     authority: ctx.accounts.order_vault_auth.to_account_info(),
                 },
                 &signer_seeds_set,
@@ -154,6 +160,15 @@ pub fn cancel_dca_order<'info>(
         _ => return err!(ErrorCode::InvalidOrderSide),
     }
 
+    track_order_escrow(
+        order.escrows_sol(),
+        &order.escrow_mint(),
+        &mut ctx.accounts.fund_state,
+        &mut ctx.accounts.whitelist,
+        order.remaining_amount,
+        0,
+    )?;
+
     order.status = DCA_STATUS_CANCELLED;
     ctx.accounts.fund_state.active_dca_count = ctx
         .accounts
@@ -161,6 +176,7 @@ pub fn cancel_dca_order<'info>(
         .active_dca_count
         .checked_sub(1)
         .ok_or(ErrorCode::MathOverflow)?;
+    emit!(DcaOrderClosed::new(order.key(), order, Clock::get()?.unix_timestamp));
 
     Ok(())
 }
@@ -177,7 +193,7 @@ pub struct CancelDcaOrder<'info> {
     pub config: Account<'info, GlobalConfig>,
     #[account(
         mut,
-        seeds = [b"fund", config.key().as_ref(), fund_state.manager.as_ref(), fund_state.fund_id.to_le_bytes().as_ref()],
+        seeds = [b"fund", config.key().as_ref(), fund_state.creator.as_ref(), fund_state.fund_id.to_le_bytes().as_ref()],
         bump = fund_state.bump,
         has_one = config
     )]
@@ -188,13 +204,15 @@ pub struct CancelDcaOrder<'info> {
         bump = fund_state.vault_bump
     )]
     pub fund_vault: Account<'info, FundVault>,
+    #[account(mut)]
     pub whitelist: Account<'info, FundWhitelist>,
     #[account(mut)]
     pub fund_token_vault: Account<'info, TokenAccount>,
     #[account(
         mut,
         seeds = [b"dca_order", fund_state.key().as_ref(), order_id.to_le_bytes().as_ref()],
-        bump = order.bump
+        bump = order.bump,
+        close = manager
     )]
     pub order: Account<'info, DcaOrder>,
     /// CHECK: validated against derived PDA in handler
@@ -204,6 +222,11 @@ pub struct CancelDcaOrder<'info> {
     pub order_vault_auth: UncheckedAccount<'info>,
     #[account(mut)]
     pub order_token_vault: Account<'info, TokenAccount>,
+    #[account(
+        seeds = [b"fund_roles", fund_state.key().as_ref()],
+        bump = fund_roles.bump
+    )]
+    pub fund_roles: Option<Account<'info, FundRoles>>,
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
 }
@@ -0,0 +1,163 @@
+use anchor_lang::prelude::*;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+
+use crate::errors::ErrorCode;
+use crate::instructions::set_fund_role::require_fund_role;
+use crate::instructions::invest_in_fund::{
+    invoke_fund_program, sweep_proxy, validate_fund_share_whitelist,
+};
+use crate::program::FundContract;
+use crate::state::fund::{FundState, FundVault};
+use crate::state::fund_roles::{FundRoles, ROLE_TRADER};
+use crate::state::global_config::GlobalConfig;
+use crate::state::whitelist::FundWhitelist;
+use crate::state::withdraw_request::WithdrawRequest;
+
+pub fn cancel_fund_redemption<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CancelFundRedemption<'info>>,
+) -> Result<()> {
+    require_fund_role(
+        ctx.accounts.fund_state.key(),
+        &ctx.accounts.fund_state,
+        ctx.accounts.fund_roles.as_ref(),
+        &ctx.accounts.manager.key(),
+        ROLE_TRADER,
+    )?;
+    validate_fund_share_whitelist(
+        &ctx.accounts.fund_whitelist,
+        ctx.accounts.fund_state.key(),
+        ctx.accounts.underlying_fund.key(),
+    )?;
+    let pending_shares = ctx.accounts.fund_whitelist.escrowed_amount;
+    require!(pending_shares > 0, ErrorCode::NoFundRedemptionPending);
+
+    let fund_key = ctx.accounts.fund_state.key();
+    let underlying_key = ctx.accounts.underlying_fund.key();
+    let proxy_seeds: &[&[u8]] = &[
+        b"fund_proxy",
+        fund_key.as_ref(),
+        underlying_key.as_ref(),
+        &[ctx.bumps.proxy],
+    ];
+    let proxy_seeds_set = [proxy_seeds];
+    let cancel_accounts = crate::accounts::CancelWithdraw {
+        investor: ctx.accounts.proxy.key(),
+        config: ctx.accounts.config.key(),
+        fund_state: ctx.accounts.underlying_fund.key(),
+        withdraw_request: ctx.accounts.withdraw_request.key(),
+    };
+    let mut account_infos = vec![
+        ctx.accounts.proxy.to_account_info(),
+        ctx.accounts.config.to_account_info(),
+        ctx.accounts.underlying_fund.to_account_info(),
+        ctx.accounts.withdraw_request.to_account_info(),
+        ctx.accounts.fund_program.to_account_info(),
+    ];
+    invoke_fund_program(
+        cancel_accounts.to_account_metas(None),
+        crate::instruction::CancelWithdraw {}.data(),
+        &mut account_infos,
+        &[],
+        &proxy_seeds_set,
+    )?;
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        token::Transfer {
+            from: ctx.accounts.proxy_share_account.to_account_info(),
+            to: ctx.accounts.fund_token_vault.to_account_info(),
+            authority: ctx.accounts.proxy.to_account_info(),
+        },
+        &proxy_seeds_set,
+    );
+    token::transfer(transfer_ctx, pending_shares)?;
+    ctx.accounts
+        .fund_whitelist
+        .track_escrow(pending_shares, 0)?;
+
+    let request_rent = Rent::get()?.minimum_balance(8 + WithdrawRequest::LEN);
+    ctx.accounts.fund_state.track_escrow(request_rent, 0)?;
+    sweep_proxy(
+        &ctx.accounts.proxy.to_account_info(),
+        &ctx.accounts.fund_vault.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        &proxy_seeds_set,
+    )?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CancelFundRedemption<'info> {
+    #[account(mut)]
+    pub manager: Signer<'info>,
+    #[account(
+        seeds = [b"config", config.config_id.to_le_bytes().as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, GlobalConfig>,
+    #[account(
+        mut,
+        seeds = [b"fund", config.key().as_ref(), fund_state.creator.as_ref(), fund_state.fund_id.to_le_bytes().as_ref()],
+        bump = fund_state.bump,
+        has_one = config
+    )]
+    pub fund_state: Account<'info, FundState>,
+    #[account(
+        mut,
+        seeds = [b"vault", fund_state.key().as_ref()],
+        bump = fund_state.vault_bump
+    )]
+    pub fund_vault: Account<'info, FundVault>,
+    #[account(
+        mut,
+        seeds = [b"whitelist", fund_state.key().as_ref(), share_mint.key().as_ref()],
+        bump = fund_whitelist.bump
+    )]
+    pub fund_whitelist: Account<'info, FundWhitelist>,
+    #[account(
+        mut,
+        associated_token::mint = share_mint,
+        associated_token::authority = fund_state
+    )]
+    pub fund_token_vault: Account<'info, TokenAccount>,
+    #[account(
+        seeds = [b"fund", config.key().as_ref(), underlying_fund.creator.as_ref(), underlying_fund.fund_id.to_le_bytes().as_ref()],
+        bump = underlying_fund.bump,
+        has_one = config
+    )]
+    pub underlying_fund: Account<'info, FundState>,
+    #[account(
+        seeds = [b"shares", underlying_fund.key().as_ref()],
+        bump = underlying_fund.share_mint_bump
+    )]
+    pub share_mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [b"fund_proxy", fund_state.key().as_ref(), underlying_fund.key().as_ref()],
+        bump
+    )]
+    pub proxy: SystemAccount<'info>,
+    #[account(
+        mut,
+        associated_token::mint = share_mint,
+        associated_token::authority = proxy
+    )]
+    pub proxy_share_account: Account<'info, TokenAccount>,
+    /// CHECK: validated and closed by the cancel_withdraw CPI.
+    #[account(
+        mut,
+        seeds = [b"withdraw", underlying_fund.key().as_ref(), proxy.key().as_ref()],
+        bump
+    )]
+    pub withdraw_request: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"fund_roles", fund_state.key().as_ref()],
+        bump = fund_roles.bump
+    )]
+    pub fund_roles: Option<Account<'info, FundRoles>>,
+    pub fund_program: Program<'info, FundContract>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
@@ -29,7 +29,7 @@ pub struct CancelWithdraw<'info> {
     )]
     pub config: Account<'info, GlobalConfig>,
     #[account(
-        seeds = [b"fund", config.key().as_ref(), fund_state.manager.as_ref(), fund_state.fund_id.to_le_bytes().as_ref()],
+        seeds = [b"fund", config.key().as_ref(), fund_state.creator.as_ref(), fund_state.fund_id.to_le_bytes().as_ref()],
         bump = fund_state.bump,
         has_one = config
     )]
@@ -0,0 +1,139 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::spl_token::native_mint;
+use anchor_spl::token::Token;
+
+use crate::errors::ErrorCode;
+use crate::instructions::invest_in_fund::move_lamports;
+use crate::instructions::register_keeper::require_keeper;
+use crate::instructions::set_keeper_tip::pay_keeper_tip;
+use crate::instructions::sweep_wsol::unwrap_wsol;
+use crate::state::fund::{FundState, FundVault};
+use crate::state::global_config::GlobalConfig;
+use crate::state::keeper_record::{KeeperRecord, KEEPER_SCOPE_REBALANCE};
+use crate::state::rebalance_session::{
+    RebalanceSession, REBALANCE_SESSION_MAX_SECS, SESSION_PHASE_COMPLETE, SESSION_PHASE_OPENING,
+};
+
+pub fn close_rebalance_session<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CloseRebalanceSession<'info>>,
+) -> Result<()> {
+    require_keeper(
+        &ctx.accounts.config,
+        ctx.accounts.keeper_record.as_ref(),
+        &ctx.accounts.executor.key(),
+        &ctx.accounts.fund_state.key(),
+        KEEPER_SCOPE_REBALANCE,
+    )?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let session = &ctx.accounts.session;
+    let session_deadline = session
+        .opened_ts
+        .checked_add(REBALANCE_SESSION_MAX_SECS)
+        .ok_or(ErrorCode::MathOverflow)?;
+    require!(
+        now > session_deadline
+            || session.phase == SESSION_PHASE_OPENING
+            || session.phase == SESSION_PHASE_COMPLETE,
+        ErrorCode::InvalidSessionPhase
+    );
+
+    let fund_key = ctx.accounts.fund_state.key();
+    if session.phase == SESSION_PHASE_COMPLETE && session.notional_lamports > 0 {
+        let notional_lamports = session.notional_lamports;
+        let tip_lamports = ctx.accounts.fund_state.keeper_tip(
+            notional_lamports,
+            ctx.accounts.config.max_keeper_tip_lamports,
+            1,
+            1,
+            true,
+        )?;
+        pay_keeper_tip(
+            fund_key,
+            &ctx.accounts.fund_vault.to_account_info(),
+            &ctx.accounts.executor.to_account_info(),
+            notional_lamports,
+            tip_lamports,
+        )?;
+    }
+
+    let expected_wsol_vault =
+        anchor_spl::associated_token::get_associated_token_address(&fund_key, &native_mint::ID);
+    require!(
+        expected_wsol_vault == ctx.accounts.fund_wsol_vault.key(),
+        ErrorCode::InvalidOrderVault
+    );
+    let wsol_info = ctx.accounts.fund_wsol_vault.to_account_info();
+    if wsol_info.data_is_empty() {
+        return Ok(());
+    }
+
+    let config_key = ctx.accounts.config.key();
+    let fund_id_bytes = ctx.accounts.fund_state.fund_id.to_le_bytes();
+    let signer_seeds: &[&[u8]] = &[
+        b"fund",
+        config_key.as_ref(),
+        ctx.accounts.fund_state.creator.as_ref(),
+        fund_id_bytes.as_ref(),
+        &[ctx.accounts.fund_state.bump],
+    ];
+    let wsol_rent = Rent::get()?.minimum_balance(wsol_info.data_len());
+    unwrap_wsol(
+        &ctx.accounts.token_program.to_account_info(),
+        &wsol_info,
+        &ctx.accounts.fund_vault.to_account_info(),
+        &ctx.accounts.fund_state.to_account_info(),
+        &[signer_seeds],
+    )?;
+    if ctx.accounts.session.wsol_created {
+        move_lamports(
+            &ctx.accounts.fund_vault.to_account_info(),
+            &ctx.accounts.opener.to_account_info(),
+            wsol_rent,
+        )?;
+    }
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CloseRebalanceSession<'info> {
+    #[account(mut)]
+    pub executor: Signer<'info>,
+    #[account(
+        seeds = [b"config", config.config_id.to_le_bytes().as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, GlobalConfig>,
+    #[account(
+        seeds = [b"fund", config.key().as_ref(), fund_state.creator.as_ref(), fund_state.fund_id.to_le_bytes().as_ref()],
+        bump = fund_state.bump,
+        has_one = config
+    )]
+    pub fund_state: Account<'info, FundState>,
+    #[account(
+        mut,
+        seeds = [b"vault", fund_state.key().as_ref()],
+        bump = fund_state.vault_bump
+    )]
+    pub fund_vault: Account<'info, FundVault>,
+    #[account(
+        mut,
+        seeds = [b"rebalance_session", fund_state.key().as_ref()],
+        bump = session.bump,
+        has_one = opener,
+        close = opener
+    )]
+    pub session: Account<'info, RebalanceSession>,
+    /// CHECK: keeper that opened the session and paid its rent
+    #[account(mut)]
+    pub opener: UncheckedAccount<'info>,
+    /// CHECK: ATA for fund_state + WSOL, unwrapped into the fund vault
+    #[account(mut)]
+    pub fund_wsol_vault: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"keeper", config.key().as_ref(), executor.key().as_ref()],
+        bump = keeper_record.bump
+    )]
+    pub keeper_record: Option<Account<'info, KeeperRecord>>,
+    pub token_program: Program<'info, Token>,
+}
@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::instructions::set_fund_role::require_fund_role;
+use crate::state::fund::FundState;
+use crate::state::fund_roles::{FundRoles, ROLE_OPS};
+use crate::state::nav_history::NavHistory;
+
+pub fn configure_nav_history(ctx: Context<ConfigureNavHistory>, interval_secs: i64) -> Result<()> {
+    require_fund_role(
+        ctx.accounts.fund_state.key(),
+        &ctx.accounts.fund_state,
+        ctx.accounts.fund_roles.as_ref(),
+        &ctx.accounts.manager.key(),
+        ROLE_OPS,
+    )?;
+    require!(interval_secs > 0, ErrorCode::InvalidNavHistoryInterval);
+
+    let history = &mut ctx.accounts.nav_history;
+    history.fund = ctx.accounts.fund_state.key();
+    history.interval_secs = interval_secs;
+    history.bump = ctx.bumps.nav_history;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ConfigureNavHistory<'info> {
+    #[account(mut)]
+    pub manager: Signer<'info>,
+    #[account(
+        seeds = [b"fund", fund_state.config.as_ref(), fund_state.creator.as_ref(), fund_state.fund_id.to_le_bytes().as_ref()],
+        bump = fund_state.bump,
+    )]
+    pub fund_state: Account<'info, FundState>,
+    #[account(
+        init_if_needed,
+        payer = manager,
+        space = 8 + NavHistory::LEN,
+        seeds = [b"nav_history", fund_state.key().as_ref()],
+        bump
+    )]
+    pub nav_history: Account<'info, NavHistory>,
+    #[account(
+        seeds = [b"fund_roles", fund_state.key().as_ref()],
+        bump = fund_roles.bump
+    )]
+    pub fund_roles: Option<Account<'info, FundRoles>>,
+    pub system_program: Program<'info, System>,
+}
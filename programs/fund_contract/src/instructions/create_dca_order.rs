@@ -4,10 +4,16 @@ use anchor_spl::token::{self, Mint, Token, TokenAccount};
 use anchor_spl::token::spl_token::native_mint;
 
 use crate::errors::ErrorCode;
-use crate::state::dca_order::{DcaOrder, DCA_SIDE_BUY, DCA_SIDE_SELL, DCA_STATUS_OPEN};
+use crate::instructions::set_fund_role::require_fund_role;
+use crate::instructions::create_limit_order::{load_quote_accounts, track_quote_escrow};
+use crate::state::dca_order::{
+    DcaOrder, DCA_MODE_FIXED, DCA_MODE_VALUE_AVERAGE, DCA_SIDE_BUY, DCA_SIDE_SELL,
+    DCA_STATUS_OPEN,
+};
 use crate::state::fund::{FundState, FundVault, FUND_TYPE_TRADING};
+use crate::state::fund_roles::{FundRoles, ROLE_TRADER};
 use crate::state::global_config::GlobalConfig;
-use crate::state::whitelist::FundWhitelist;
+use crate::state::whitelist::{FundWhitelist, ASSET_KIND_TOKEN};
 
 const MAX_ACTIVE_DCA: u16 = 20;
 
@@ -19,14 +25,35 @@ pub fn create_dca_order<'info>(
     interval_secs: i64,
     min_out: u64,
     expiry_ts: i64,
+    max_slippage_bps: u16,
+    band_lower_price: i64,
+    band_upper_price: i64,
+    price_expo: i32,
+    mode: u8,
 ) -> Result<()> {
     require!(side == DCA_SIDE_BUY || side == DCA_SIDE_SELL, ErrorCode::InvalidOrderSide);
     require!(total_amount > 0, ErrorCode::MathOverflow);
     require!(slice_amount > 0, ErrorCode::InvalidDcaSlice);
     require!(slice_amount <= total_amount, ErrorCode::InvalidDcaSlice);
     require!(interval_secs > 0, ErrorCode::InvalidDcaInterval);
-    require!(min_out > 0, ErrorCode::InvalidMinOut);
-    require!(ctx.accounts.fund_state.manager == ctx.accounts.manager.key(), ErrorCode::Unauthorized);
+    require!(
+        max_slippage_bps <= ctx.accounts.config.max_slippage_bps,
+        ErrorCode::InvalidSlippage
+    );
+    validate_price_band(band_lower_price, band_upper_price)?;
+    match mode {
+        DCA_MODE_FIXED => {}
+        DCA_MODE_VALUE_AVERAGE => require!(side == DCA_SIDE_BUY, ErrorCode::InvalidDcaMode),
+        _ => return err!(ErrorCode::InvalidDcaMode),
+    }
+    require_fund_role(
+        ctx.accounts.fund_state.key(),
+        &ctx.accounts.fund_state,
+        ctx.accounts.fund_roles.as_ref(),
+        &ctx.accounts.manager.key(),
+        ROLE_TRADER,
+    )?;
+    require!(!ctx.accounts.fund_state.paused, ErrorCode::FundPaused);
     require!(
         ctx.accounts.fund_state.fund_type == FUND_TYPE_TRADING,
         ErrorCode::InvalidFundType
@@ -38,9 +65,16 @@ pub fn create_dca_order<'info>(
     );
 
     require!(ctx.accounts.whitelist.enabled, ErrorCode::InvalidTokenVault);
+    require!(ctx.accounts.whitelist.kind == ASSET_KIND_TOKEN, ErrorCode::InvalidAssetKind);
     require!(ctx.accounts.whitelist.fund == ctx.accounts.fund_state.key(), ErrorCode::InvalidTokenVault);
     require!(ctx.accounts.whitelist.mint == ctx.accounts.mint.key(), ErrorCode::InvalidTokenVault);
 
+    let quote = load_quote_accounts(
+        &ctx.accounts.fund_state.key(),
+        &ctx.accounts.mint.key(),
+        ctx.remaining_accounts,
+    )?;
+
     let order_id = ctx.accounts.fund_state.next_order_id;
     ctx.accounts.fund_state.next_order_id = order_id
         .checked_add(1)
@@ -54,6 +88,7 @@ pub fn create_dca_order<'info>(
     order.fund = ctx.accounts.fund_state.key();
     order.side = side;
     order.mint = ctx.accounts.mint.key();
+    order.quote_mint = quote.as_ref().map_or(Pubkey::default(), |q| q.mint.key());
     order.total_amount = total_amount;
     order.slice_amount = slice_amount;
     order.remaining_amount = total_amount;
@@ -62,7 +97,19 @@ pub fn create_dca_order<'info>(
         .checked_add(interval_secs)
         .ok_or(ErrorCode::MathOverflow)?;
     order.min_out = min_out;
+    order.max_slippage_bps = if max_slippage_bps == 0 {
+        ctx.accounts.config.max_slippage_bps
+    } else {
+        max_slippage_bps
+    };
+    order.band_lower_price = band_lower_price;
+    order.band_upper_price = band_upper_price;
+    order.price_expo = price_expo;
+    order.mode = mode;
+    order.period_count = 0;
+    order.acquired_amount = 0;
     order.price_feed = ctx.accounts.whitelist.pyth_feed;
+    order.quote_price_feed = quote.as_ref().map_or(Pubkey::default(), |q| q.price_feed);
     order.pyth_program_id = ctx.accounts.config.pyth_program_id;
     order.expiry_ts = expiry_ts;
     order.status = DCA_STATUS_OPEN;
@@ -113,7 +160,7 @@ pub fn create_dca_order<'info>(
     require!(vault_auth == ctx.accounts.order_vault_auth.key(), ErrorCode::InvalidOrderVault);
 
     match side {
-        DCA_SIDE_BUY => {
+        DCA_SIDE_BUY if quote.is_none() => {
             let expected_wsol_vault = anchor_spl::associated_token::get_associated_token_address(
                 &vault_auth,
                 &native_mint::ID,
@@ -168,10 +215,17 @@ pub fn create_dca_order<'info>(
                     .ok_or(ErrorCode::MathOverflow)?;
             }
         }
-        DCA_SIDE_SELL => {
+        DCA_SIDE_BUY | DCA_SIDE_SELL => {
+            let (escrow_mint, fund_escrow_vault) = match (side, quote.as_ref()) {
+                (DCA_SIDE_BUY, Some(q)) => (q.mint.clone(), q.fund_vault.clone()),
+                _ => (
+                    ctx.accounts.mint.to_account_info(),
+                    ctx.accounts.fund_token_vault.to_account_info(),
+                ),
+            };
             let expected_vault = anchor_spl::associated_token::get_associated_token_address(
                 &vault_auth,
-                &ctx.accounts.mint.key(),
+                escrow_mint.key,
             );
             require!(expected_vault == ctx.accounts.order_token_vault.key(), ErrorCode::InvalidOrderVault);
 
@@ -183,7 +237,7 @@ pub fn create_dca_order<'info>(
                         payer: ctx.accounts.manager.to_account_info(),
                         associated_token: ctx.accounts.order_token_vault.to_account_info(),
                         authority: ctx.accounts.order_vault_auth.to_account_info(),
-                        mint: ctx.accounts.mint.to_account_info(),
+                        mint: escrow_mint.clone(),
                         system_program: ctx.accounts.system_program.to_account_info(),
                         token_program: ctx.accounts.token_program.to_account_info(),
                     },
@@ -200,7 +254,7 @@ pub fn create_dca_order<'info>(
                 let mut data_slice: &[u8] = &data;
                 let order_vault = TokenAccount::try_deserialize(&mut data_slice)
                     .map_err(|_| ErrorCode::InvalidOrderVault)?;
-                require!(order_vault.mint == ctx.accounts.mint.key(), ErrorCode::InvalidOrderVault);
+                require!(order_vault.mint == *escrow_mint.key, ErrorCode::InvalidOrderVault);
                 require!(order_vault.owner == ctx.accounts.order_vault_auth.key(), ErrorCode::InvalidOrderVault);
                 require!(order_vault.amount == 0, ErrorCode::InvalidOrderVault);
             }
@@ -213,7 +267,7 @@ pub fn create_dca_order<'info>(
             let signer_seeds: &[&[u8]] = &[
                 b"fund",
                 config_key.as_ref(),
-                ctx.accounts.fund_state.manager.as_ref(),
+                ctx.accounts.fund_state.creator.as_ref(),
                 fund_id_bytes.as_ref(),
                 &[ctx.accounts.fund_state.bump],
             ];
@@ -222,7 +276,7 @@ pub fn create_dca_order<'info>(
             let transfer_ctx = CpiContext::new_with_signer(
                 ctx.accounts.token_program.to_account_info(),
                 token::Transfer {
-                    from: ctx.accounts.fund_token_vault.to_account_info(),
+                    from: fund_escrow_vault,
                     to: ctx.accounts.order_token_vault.to_account_info(),
                     authority: ctx.accounts.fund_state.to_account_info(),
                 },
@@ -233,11 +287,26 @@ pub fn create_dca_order<'info>(
         _ => return err!(ErrorCode::InvalidOrderSide),
     }
 
+    match (side, quote.as_ref()) {
+        (DCA_SIDE_BUY, Some(q)) => track_quote_escrow(ctx.program_id, q.whitelist, 0, total_amount),
+        (DCA_SIDE_BUY, None) => ctx.accounts.fund_state.track_escrow(0, total_amount),
+        _ => ctx.accounts.whitelist.track_escrow(0, total_amount),
+    }
+}
+
+pub(crate) fn validate_price_band(band_lower_price: i64, band_upper_price: i64) -> Result<()> {
+    require!(
+        band_lower_price >= 0 && band_upper_price >= 0,
+        ErrorCode::InvalidDcaBand
+    );
+    if band_lower_price > 0 && band_upper_price > 0 {
+        require!(band_lower_price < band_upper_price, ErrorCode::InvalidDcaBand);
+    }
     Ok(())
 }
 
 #[derive(Accounts)]
-#[instruction(side: u8, total_amount: u64, slice_amount: u64, interval_secs: i64, min_out: u64, expiry_ts: i64)]
+#[instruction(side: u8, total_amount: u64, slice_amount: u64, interval_secs: i64, min_out: u64, expiry_ts: i64, max_slippage_bps: u16, band_lower_price: i64, band_upper_price: i64, price_expo: i32, mode: u8)]
 pub struct CreateDcaOrder<'info> {
     #[account(mut)]
     pub manager: Signer<'info>,
@@ -248,7 +317,7 @@ pub struct CreateDcaOrder<'info> {
     pub config: Account<'info, GlobalConfig>,
     #[account(
         mut,
-        seeds = [b"fund", config.key().as_ref(), fund_state.manager.as_ref(), fund_state.fund_id.to_le_bytes().as_ref()],
+        seeds = [b"fund", config.key().as_ref(), fund_state.creator.as_ref(), fund_state.fund_id.to_le_bytes().as_ref()],
         bump = fund_state.bump,
         has_one = config
     )]
@@ -260,6 +329,7 @@ pub struct CreateDcaOrder<'info> {
     )]
     pub fund_vault: Account<'info, FundVault>,
     pub mint: Account<'info, Mint>,
+    #[account(mut)]
     pub whitelist: Account<'info, FundWhitelist>,
     #[account(
         init,
@@ -281,6 +351,11 @@ pub struct CreateDcaOrder<'info> {
     pub fund_token_vault: Account<'info, TokenAccount>,
     #[account(address = native_mint::ID)]
     pub wsol_mint: Account<'info, Mint>,
+    #[account(
+        seeds = [b"fund_roles", fund_state.key().as_ref()],
+        bump = fund_roles.bump
+    )]
+    pub fund_roles: Option<Account<'info, FundRoles>>,
     pub system_program: Program<'info, System>,
     pub token_program: Program<'info, Token>,
     pub associated_token_program: Program<'info, AssociatedToken>,
@@ -4,10 +4,15 @@ use anchor_spl::token::{self, Mint, Token, TokenAccount};
 use anchor_spl::token::spl_token::native_mint;
 
 use crate::errors::ErrorCode;
+use crate::instructions::set_fund_role::require_fund_role;
 use crate::state::fund::{FundState, FundVault, FUND_TYPE_TRADING};
+use crate::state::fund_roles::{FundRoles, ROLE_TRADER};
 use crate::state::global_config::GlobalConfig;
-use crate::state::limit_order::{LimitOrder, ORDER_STATUS_OPEN, SIDE_BUY, SIDE_SELL};
-use crate::state::whitelist::FundWhitelist;
+use crate::state::limit_order::{
+    LimitOrder, ORDER_LEG_NONE, ORDER_STATUS_OPEN, ORDER_TYPE_BRACKET, ORDER_TYPE_LIMIT,
+    ORDER_TYPE_STOP, ORDER_TYPE_TRAILING_STOP, SIDE_BUY, SIDE_SELL,
+};
+use crate::state::whitelist::{FundWhitelist, ASSET_KIND_TOKEN};
 
 pub fn create_limit_order<'info>(
     ctx: Context<'_, '_, 'info, 'info, CreateLimitOrder<'info>>,
@@ -17,22 +22,39 @@ pub fn create_limit_order<'info>(
     limit_price: i64,
     price_expo: i32,
     expiry_ts: i64,
+    order_type: u8,
+    stop_price: i64,
+    trail_bps: u16,
 ) -> Result<()> {
     require!(amount_in > 0, ErrorCode::MathOverflow);
     require!(min_out > 0, ErrorCode::InvalidMinOut);
-    require!(limit_price > 0, ErrorCode::InvalidOracle);
-    require!(ctx.accounts.fund_state.manager == ctx.accounts.manager.key(), ErrorCode::Unauthorized);
+    validate_order_prices(side, order_type, limit_price, stop_price, trail_bps)?;
+    require_fund_role(
+        ctx.accounts.fund_state.key(),
+        &ctx.accounts.fund_state,
+        ctx.accounts.fund_roles.as_ref(),
+        &ctx.accounts.manager.key(),
+        ROLE_TRADER,
+    )?;
+    require!(!ctx.accounts.fund_state.paused, ErrorCode::FundPaused);
     require!(
         ctx.accounts.fund_state.fund_type == FUND_TYPE_TRADING,
         ErrorCode::InvalidFundType
     );
 
     require!(ctx.accounts.whitelist.enabled, ErrorCode::InvalidTokenVault);
+    require!(ctx.accounts.whitelist.kind == ASSET_KIND_TOKEN, ErrorCode::InvalidAssetKind);
     require!(ctx.accounts.whitelist.fund == ctx.accounts.fund_state.key(), ErrorCode::InvalidTokenVault);
     require!(ctx.accounts.whitelist.mint == ctx.accounts.mint.key(), ErrorCode::InvalidTokenVault);
 
     require!(side == SIDE_BUY || side == SIDE_SELL, ErrorCode::InvalidOrderSide);
 
+    let quote = load_quote_accounts(
+        &ctx.accounts.fund_state.key(),
+        &ctx.accounts.mint.key(),
+        ctx.remaining_accounts,
+    )?;
+
     let order_id = ctx.accounts.fund_state.next_order_id;
     ctx.accounts.fund_state.next_order_id = order_id
         .checked_add(1)
@@ -48,15 +70,24 @@ pub fn create_limit_order<'info>(
     order.fund = ctx.accounts.fund_state.key();
     order.side = side;
     order.mint = ctx.accounts.mint.key();
+    order.quote_mint = quote.as_ref().map_or(Pubkey::default(), |q| q.mint.key());
     order.amount_in = amount_in;
     order.min_out = min_out;
+    order.order_type = order_type;
     order.limit_price = limit_price;
+    order.stop_price = stop_price;
+    order.trail_bps = trail_bps;
+    order.peak_price = if order_type == ORDER_TYPE_TRAILING_STOP { stop_price } else { 0 };
     order.price_expo = price_expo;
     order.price_feed = ctx.accounts.whitelist.pyth_feed;
+    order.quote_price_feed = quote.as_ref().map_or(Pubkey::default(), |q| q.price_feed);
     order.pyth_program_id = ctx.accounts.config.pyth_program_id;
     order.created_ts = Clock::get()?.unix_timestamp;
     order.expiry_ts = expiry_ts;
     order.status = ORDER_STATUS_OPEN;
+    order.filled_in = 0;
+    order.filled_out = 0;
+    order.active_leg = ORDER_LEG_NONE;
     order.bump = ctx.bumps.order;
 
     let expected_fund_token_vault = anchor_spl::associated_token::get_associated_token_address(
@@ -73,7 +104,7 @@ pub fn create_limit_order<'info>(
     );
 
     match side {
-        SIDE_BUY => {
+        SIDE_BUY if quote.is_none() => {
             let (vault_auth, _auth_bump) = Pubkey::find_program_address(
                 &[b"limit_order_vault_auth", order.key().as_ref()],
                 ctx.program_id,
@@ -169,7 +200,14 @@ pub fn create_limit_order<'info>(
                     .ok_or(ErrorCode::MathOverflow)?;
             }
         }
-        SIDE_SELL => {
+        SIDE_BUY | SIDE_SELL => {
+            let (escrow_mint, fund_escrow_vault) = match (side, quote.as_ref()) {
+                (SIDE_BUY, Some(q)) => (q.mint.clone(), q.fund_vault.clone()),
+                _ => (
+                    ctx.accounts.mint.to_account_info(),
+                    ctx.accounts.fund_token_vault.to_account_info(),
+                ),
+            };
             let (vault_auth, auth_bump) = Pubkey::find_program_address(
                 &[b"limit_order_vault_auth", order.key().as_ref()],
                 ctx.program_id,
@@ -178,7 +216,7 @@ pub fn create_limit_order<'info>(
 
             let expected_vault = anchor_spl::associated_token::get_associated_token_address(
                 &vault_auth,
-                &ctx.accounts.mint.key(),
+                escrow_mint.key,
             );
             require!(expected_vault == ctx.accounts.order_token_vault.key(), ErrorCode::InvalidOrderVault);
 
@@ -190,7 +228,7 @@ pub fn create_limit_order<'info>(
                         payer: ctx.accounts.manager.to_account_info(),
                         associated_token: ctx.accounts.order_token_vault.to_account_info(),
                         authority: ctx.accounts.order_vault_auth.to_account_info(),
-                        mint: ctx.accounts.mint.to_account_info(),
+                        mint: escrow_mint.clone(),
                         system_program: ctx.accounts.system_program.to_account_info(),
                         token_program: ctx.accounts.token_program.to_account_info(),
                     },
@@ -207,7 +245,7 @@ pub fn create_limit_order<'info>(
                 let mut data_slice: &[u8] = &data;
                 let order_vault = TokenAccount::try_deserialize(&mut data_slice)
                     .map_err(|_| ErrorCode::InvalidOrderVault)?;
-                require!(order_vault.mint == ctx.accounts.mint.key(), ErrorCode::InvalidOrderVault);
+                require!(order_vault.mint == *escrow_mint.key, ErrorCode::InvalidOrderVault);
                 require!(order_vault.owner == ctx.accounts.order_vault_auth.key(), ErrorCode::InvalidOrderVault);
                 require!(order_vault.amount == 0, ErrorCode::InvalidOrderVault);
             }
@@ -220,7 +258,7 @@ pub fn create_limit_order<'info>(
             let signer_seeds: &[&[u8]] = &[
                 b"fund",
                 config_key.as_ref(),
-                ctx.accounts.fund_state.manager.as_ref(),
+                ctx.accounts.fund_state.creator.as_ref(),
                 fund_id_bytes.as_ref(),
                 &[ctx.accounts.fund_state.bump],
             ];
@@ -229,7 +267,7 @@ pub fn create_limit_order<'info>(
             let transfer_ctx = CpiContext::new_with_signer(
                 ctx.accounts.token_program.to_account_info(),
                 token::Transfer {
-                    from: ctx.accounts.fund_token_vault.to_account_info(),
+                    from: fund_escrow_vault,
                     to: ctx.accounts.order_token_vault.to_account_info(),
                     authority: ctx.accounts.fund_state.to_account_info(),
                 },
@@ -242,11 +280,124 @@ pub fn create_limit_order<'info>(
         _ => return err!(ErrorCode::InvalidOrderSide),
     }
 
+    match (side, quote.as_ref()) {
+        (SIDE_BUY, Some(q)) => track_quote_escrow(ctx.program_id, q.whitelist, 0, amount_in),
+        (SIDE_BUY, None) => ctx.accounts.fund_state.track_escrow(0, amount_in),
+        _ => ctx.accounts.whitelist.track_escrow(0, amount_in),
+    }
+}
+
+pub(crate) fn validate_order_prices(
+    side: u8,
+    order_type: u8,
+    limit_price: i64,
+    stop_price: i64,
+    trail_bps: u16,
+) -> Result<()> {
+    match order_type {
+        ORDER_TYPE_LIMIT => {
+            require!(limit_price > 0, ErrorCode::InvalidOracle);
+            require!(stop_price == 0, ErrorCode::InvalidOrderType);
+        }
+        ORDER_TYPE_STOP => {
+            require!(stop_price > 0, ErrorCode::InvalidOracle);
+            require!(limit_price == 0, ErrorCode::InvalidOrderType);
+        }
+        ORDER_TYPE_BRACKET => {
+            require!(limit_price > 0, ErrorCode::InvalidOracle);
+            require!(stop_price > 0, ErrorCode::InvalidOracle);
+            match side {
+                SIDE_BUY => require!(limit_price < stop_price, ErrorCode::InvalidOrderType),
+                SIDE_SELL => require!(stop_price < limit_price, ErrorCode::InvalidOrderType),
+                _ => return err!(ErrorCode::InvalidOrderSide),
+            }
+        }
+        ORDER_TYPE_TRAILING_STOP => {
+            require!(stop_price > 0, ErrorCode::InvalidOracle);
+            require!(limit_price == 0, ErrorCode::InvalidOrderType);
+            require!(trail_bps > 0 && trail_bps < 10_000, ErrorCode::InvalidOrderType);
+        }
+        _ => return err!(ErrorCode::InvalidOrderType),
+    }
+    if order_type != ORDER_TYPE_TRAILING_STOP {
+        require!(trail_bps == 0, ErrorCode::InvalidOrderType);
+    }
     Ok(())
 }
 
+pub(crate) struct QuoteAccounts<'info> {
+    pub(crate) whitelist: &'info AccountInfo<'info>,
+    pub(crate) mint: AccountInfo<'info>,
+    pub(crate) price_feed: Pubkey,
+    pub(crate) fund_vault: AccountInfo<'info>,
+}
+
+pub(crate) fn load_quote_accounts<'info>(
+    fund_key: &Pubkey,
+    base_mint: &Pubkey,
+    accounts: &'info [AccountInfo<'info>],
+) -> Result<Option<QuoteAccounts<'info>>> {
+    if accounts.is_empty() {
+        return Ok(None);
+    }
+    require!(accounts.len() == 3, ErrorCode::InvalidRemainingAccounts);
+    let whitelist: Account<FundWhitelist> =
+        Account::try_from(&accounts[0]).map_err(|_| ErrorCode::InvalidTokenVault)?;
+    require!(whitelist.enabled, ErrorCode::InvalidTokenVault);
+    require!(whitelist.kind == ASSET_KIND_TOKEN, ErrorCode::InvalidAssetKind);
+    require!(whitelist.fund == *fund_key, ErrorCode::InvalidTokenVault);
+    require!(whitelist.mint == *accounts[1].key, ErrorCode::InvalidTokenVault);
+    require!(whitelist.mint != *base_mint, ErrorCode::InvalidTokenVault);
+
+    let expected_fund_vault =
+        anchor_spl::associated_token::get_associated_token_address(fund_key, &whitelist.mint);
+    require!(
+        expected_fund_vault == *accounts[2].key,
+        ErrorCode::InvalidTokenVault
+    );
+    let fund_vault: Account<TokenAccount> =
+        Account::try_from(&accounts[2]).map_err(|_| ErrorCode::InvalidTokenVault)?;
+    require!(fund_vault.owner == *fund_key, ErrorCode::InvalidTokenVault);
+    require!(fund_vault.mint == whitelist.mint, ErrorCode::InvalidTokenVault);
+
+    Ok(Some(QuoteAccounts {
+        whitelist: &accounts[0],
+        mint: accounts[1].clone(),
+        price_feed: whitelist.pyth_feed,
+        fund_vault: accounts[2].clone(),
+    }))
+}
+
+pub(crate) fn track_quote_escrow<'a>(
+    program_id: &Pubkey,
+    quote_whitelist: &'a AccountInfo<'a>,
+    current: u64,
+    target: u64,
+) -> Result<()> {
+    let mut whitelist: Account<FundWhitelist> =
+        Account::try_from(quote_whitelist).map_err(|_| ErrorCode::InvalidTokenVault)?;
+    whitelist.track_escrow(current, target)?;
+    whitelist.exit(program_id)
+}
+
+pub(crate) fn track_order_escrow(
+    escrows_sol: bool,
+    escrow_mint: &Pubkey,
+    fund_state: &mut Account<FundState>,
+    whitelist: &mut Account<FundWhitelist>,
+    current: u64,
+    target: u64,
+) -> Result<()> {
+    if escrows_sol {
+        return fund_state.track_escrow(current, target);
+    }
+    require!(whitelist.fund == fund_state.key(), ErrorCode::InvalidTokenVault);
+    require!(whitelist.mint == *escrow_mint, ErrorCode::InvalidTokenVault);
+    whitelist.track_escrow(current, target)
+}
+
 #[derive(Accounts)]
-#[instruction(side: u8, amount_in: u64, min_out: u64, limit_price: i64, price_expo: i32, expiry_ts: i64)]
+#[instruction(side: u8, amount_in: u64, min_out: u64, limit_price: i64, price_expo: i32, expiry_ts: i64, order_type: u8, stop_price: i64, trail_bps: u16)]
 pub struct CreateLimitOrder<'info> {
     #[account(mut)]
     pub manager: Signer<'info>,
@@ -257,7 +408,7 @@ pub struct CreateLimitOrder<'info> {
     pub config: Account<'info, GlobalConfig>,
     #[account(
         mut,
-        seeds = [b"fund", config.key().as_ref(), fund_state.manager.as_ref(), fund_state.fund_id.to_le_bytes().as_ref()],
+        seeds = [b"fund", config.key().as_ref(), fund_state.creator.as_ref(), fund_state.fund_id.to_le_bytes().as_ref()],
         bump = fund_state.bump,
         has_one = config
     )]
@@ -269,6 +420,7 @@ pub struct CreateLimitOrder<'info> {
     )]
     pub fund_vault: Account<'info, FundVault>,
     pub mint: Account<'info, Mint>,
+    #[account(mut)]
     pub whitelist: Account<'info, FundWhitelist>,
     #[account(
         init,
@@ -290,6 +442,11 @@ pub struct CreateLimitOrder<'info> {
     pub fund_token_vault: Account<'info, TokenAccount>,
     #[account(address = native_mint::ID)]
     pub wsol_mint: Account<'info, Mint>,
+    #[account(
+        seeds = [b"fund_roles", fund_state.key().as_ref()],
+        bump = fund_roles.bump
+    )]
+    pub fund_roles: Option<Account<'info, FundRoles>>,
     pub system_program: Program<'info, System>,
     pub token_program: Program<'info, Token>,
     pub associated_token_program: Program<'info, AssociatedToken>,
@@ -1,19 +1,16 @@
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::{get_associated_token_address, AssociatedToken};
 use anchor_spl::token::{mint_to, Mint, MintTo, Token, TokenAccount};
-use pyth_sdk_solana::state::SolanaPriceAccount;
 
 use crate::errors::ErrorCode;
+use crate::oracle::{expected_sol_out_from_token, load_pyth_price, PythPrice, LAMPORTS_PER_SOL_U64};
 use crate::state::fund::{FundState, FundVault};
 use crate::state::global_config::GlobalConfig;
-use crate::state::whitelist::FundWhitelist;
-use crate::state::limit_order::{LimitOrder, ORDER_STATUS_OPEN, SIDE_BUY, SIDE_SELL};
-use crate::state::dca_order::{DcaOrder, DCA_STATUS_OPEN, DCA_SIDE_BUY, DCA_SIDE_SELL};
-use anchor_spl::token::spl_token::native_mint;
+use crate::instructions::refresh_nav::snapshot_nav_lamports;
+use crate::state::nav_history::NavHistory;
+use crate::state::nav_snapshot::{NavAssetValue, NavSnapshot};
+use crate::state::whitelist::{FundWhitelist, ASSET_KIND_FUND_SHARES};
 
-const ORACLE_MAX_AGE_SECS: u64 = 60;
-const LAMPORTS_PER_SOL_U64: u64 = 1_000_000_000;
-const MAX_CONF_BPS: u64 = 200;
 
 pub fn deposit<'info>(
     ctx: Context<'_, '_, 'info, 'info, Deposit<'info>>,
@@ -35,15 +32,13 @@ pub fn deposit<'info>(
         .ok_or(ErrorCode::MathOverflow)? as u64;
 
     let fund_vault_balance = ctx.accounts.fund_vault.to_account_info().lamports();
-    let nav_lamports = compute_nav_lamports(
+    let nav_lamports = resolve_nav_lamports(
         ctx.program_id,
         ctx.accounts.fund_state.key(),
+        &ctx.accounts.fund_state,
         fund_vault_balance,
         ctx.accounts.config.sol_usd_pyth_feed,
         ctx.accounts.config.pyth_program_id,
-        ctx.accounts.fund_state.enabled_token_count,
-        ctx.accounts.fund_state.active_limit_count,
-        ctx.accounts.fund_state.active_dca_count,
         ctx.remaining_accounts,
     )?;
 
@@ -84,12 +79,12 @@ pub fn deposit<'info>(
     }
 
     let config_key = ctx.accounts.config.key();
-    let manager_key = ctx.accounts.fund_state.manager;
+    let creator_key = ctx.accounts.fund_state.creator;
     let fund_id_bytes = ctx.accounts.fund_state.fund_id.to_le_bytes();
     let signer_seeds: &[&[u8]] = &[
         b"fund",
         config_key.as_ref(),
-        manager_key.as_ref(),
+        creator_key.as_ref(),
         fund_id_bytes.as_ref(),
         &[ctx.accounts.fund_state.bump],
     ];
@@ -112,6 +107,14 @@ pub fn deposit<'info>(
         .checked_add(shares_to_mint)
         .ok_or(ErrorCode::MathOverflow)?;
 
+    let total_shares_after = fund_state.total_shares;
+    if let Some(history) = ctx.accounts.nav_history.as_mut() {
+        let nav_after = nav_lamports
+            .checked_add(net_lamports)
+            .ok_or(ErrorCode::MathOverflow)?;
+        history.record(Clock::get()?.unix_timestamp, nav_after, total_shares_after);
+    }
+
     Ok(())
 }
 
@@ -127,7 +130,7 @@ pub struct Deposit<'info> {
     pub config: Account<'info, GlobalConfig>,
     #[account(
         mut,
-        seeds = [b"fund", config.key().as_ref(), fund_state.manager.as_ref(), fund_state.fund_id.to_le_bytes().as_ref()],
+        seeds = [b"fund", config.key().as_ref(), fund_state.creator.as_ref(), fund_state.fund_id.to_le_bytes().as_ref()],
         bump = fund_state.bump,
         has_one = config
     )]
@@ -153,47 +156,150 @@ pub struct Deposit<'info> {
     pub investor_share_account: Account<'info, TokenAccount>,
     #[account(mut)]
     pub fee_treasury: SystemAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"nav_history", fund_state.key().as_ref()],
+        bump = nav_history.bump
+    )]
+    pub nav_history: Option<Account<'info, NavHistory>>,
     pub system_program: Program<'info, System>,
     pub token_program: Program<'info, Token>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub rent: Sysvar<'info, Rent>,
 }
 
-pub(crate) fn compute_nav_lamports<'info>(
+pub(crate) struct NavBreakdown {
+    pub(crate) nav_lamports: u64,
+    pub(crate) sol_lamports: u64,
+    pub(crate) token_value_lamports: u64,
+    pub(crate) sol_price: i64,
+    pub(crate) sol_price_expo: i32,
+    pub(crate) sol_price_publish_time: i64,
+    pub(crate) assets: Vec<NavAssetValue>,
+}
+
+pub(crate) fn resolve_nav_lamports<'info>(
     program_id: &Pubkey,
     fund_key: Pubkey,
+    fund_state: &FundState,
     sol_lamports: u64,
     sol_usd_pyth_feed: Pubkey,
     pyth_program_id: Pubkey,
-    enabled_token_count: u16,
-    active_limit_count: u16,
-    active_dca_count: u16,
     remaining: &'info [AccountInfo<'info>],
 ) -> Result<u64> {
-    let mut nav = sol_lamports as i128;
+    if fund_state.nav_snapshot_required {
+        require!(remaining.len() == 1, ErrorCode::InvalidRemainingAccounts);
+        return snapshot_nav_lamports(program_id, fund_key, fund_state, sol_lamports, &remaining[0]);
+    }
+    Ok(compute_nav(
+        program_id,
+        fund_key,
+        sol_lamports,
+        fund_state.escrowed_lamports,
+        sol_usd_pyth_feed,
+        pyth_program_id,
+        fund_state.enabled_token_count,
+        remaining,
+    )?
+    .nav_lamports)
+}
+
+pub(crate) fn compute_nav<'info>(
+    program_id: &Pubkey,
+    fund_key: Pubkey,
+    sol_lamports: u64,
+    escrowed_lamports: u64,
+    sol_usd_pyth_feed: Pubkey,
+    pyth_program_id: Pubkey,
+    enabled_token_count: u16,
+    remaining: &'info [AccountInfo<'info>],
+) -> Result<NavBreakdown> {
+    let sol_total = sol_lamports
+        .checked_add(escrowed_lamports)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let mut breakdown = NavBreakdown {
+        nav_lamports: sol_total,
+        sol_lamports: sol_total,
+        token_value_lamports: 0,
+        sol_price: 0,
+        sol_price_expo: 0,
+        sol_price_publish_time: 0,
+        assets: Vec::with_capacity(enabled_token_count as usize),
+    };
 
     if enabled_token_count == 0 {
-        require!(
-            remaining.is_empty() && active_limit_count == 0 && active_dca_count == 0,
-            ErrorCode::InvalidRemainingAccounts
-        );
-        return Ok(nav as u64);
+        require!(remaining.is_empty(), ErrorCode::InvalidRemainingAccounts);
+        return Ok(breakdown);
     }
 
-    let base_len = 1 + 3 * (enabled_token_count as usize);
-    let limit_len = 3 * (active_limit_count as usize);
-    let dca_len = 3 * (active_dca_count as usize);
-    let expected_len = base_len + limit_len + dca_len;
+    require!(!remaining.is_empty(), ErrorCode::InvalidRemainingAccounts);
+    let sol_price_info = &remaining[0];
+    require!(
+        sol_price_info.key == &sol_usd_pyth_feed,
+        ErrorCode::InvalidOracle
+    );
+    require!(
+        sol_price_info.owner == &pyth_program_id,
+        ErrorCode::InvalidOracle
+    );
+    let sol_price = load_pyth_price(sol_price_info)?;
+    breakdown.sol_price = sol_price.price;
+    breakdown.sol_price_expo = sol_price.expo;
+    breakdown.sol_price_publish_time = sol_price.publish_time;
+
+    let entries = value_nav_entries(
+        program_id,
+        fund_key,
+        pyth_program_id,
+        &sol_price,
+        None,
+        enabled_token_count as usize,
+        &remaining[1..],
+    )?;
+    breakdown.token_value_lamports = entries.token_value_lamports;
+    breakdown.assets = entries.assets;
+
+    breakdown.nav_lamports = breakdown
+        .sol_lamports
+        .checked_add(breakdown.token_value_lamports)
+        .ok_or(ErrorCode::MathOverflow)?;
+    require!(breakdown.nav_lamports > 0, ErrorCode::InvalidNav);
+    Ok(breakdown)
+}
+
+pub(crate) struct NavEntries {
+    pub(crate) token_value_lamports: u64,
+    pub(crate) last_mint: Option<Pubkey>,
+    pub(crate) assets: Vec<NavAssetValue>,
+}
+
+pub(crate) fn value_nav_entries<'info>(
+    program_id: &Pubkey,
+    fund_key: Pubkey,
+    pyth_program_id: Pubkey,
+    sol_price: &PythPrice,
+    mut prev_mint: Option<Pubkey>,
+    entry_count: usize,
+    remaining: &'info [AccountInfo<'info>],
+) -> Result<NavEntries> {
+    let priced_len = entry_count
+        .checked_mul(3)
+        .ok_or(ErrorCode::MathOverflow)?;
     require!(
-        remaining.len() == expected_len,
+        remaining.len() >= priced_len,
         ErrorCode::InvalidRemainingAccounts
     );
+    let (triplets, underlying_funds) = remaining.split_at(priced_len);
+    let mut underlying_iter = underlying_funds.iter();
+
+    let clock = Clock::get()?;
+    let mut token_value_lamports: u64 = 0;
+    let mut assets = Vec::with_capacity(entry_count);
+    for triplet in triplets.chunks(3) {
+        let whitelist_info = &triplet[0];
+        let token_vault_info = &triplet[1];
+        let token_price_info = &triplet[2];
 
-    let mut idx = 1;
-    let mut prev_mint: Option<Pubkey> = None;
-    let mut token_prices: Vec<(Pubkey, u8, i64, i32)> = Vec::new();
-    while idx < base_len {
-        let whitelist_info = &remaining[idx];
         let whitelist: Account<FundWhitelist> = Account::try_from(whitelist_info)?;
         let (expected_whitelist, _) = Pubkey::find_program_address(
             &[b"whitelist", fund_key.as_ref(), whitelist.mint.as_ref()],
@@ -215,30 +321,6 @@ pub(crate) fn compute_nav_lamports<'info>(
             );
         }
         prev_mint = Some(whitelist.mint);
-        token_prices.push((whitelist.mint, whitelist.decimals, 0, 0));
-        idx += 3;
-    }
-
-    let clock = Clock::get()?;
-    let sol_price_info = &remaining[0];
-    require!(
-        sol_price_info.key == &sol_usd_pyth_feed,
-        ErrorCode::InvalidOracle
-    );
-    require!(
-        sol_price_info.owner == &pyth_program_id,
-        ErrorCode::InvalidOracle
-    );
-    let sol_price = load_pyth_price(sol_price_info, &clock)?;
-
-    let mut idx = 1;
-    while idx < base_len {
-        let whitelist_info = &remaining[idx];
-        let token_vault_info = &remaining[idx + 1];
-        let token_price_info = &remaining[idx + 2];
-        idx += 3;
-
-        let whitelist: Account<FundWhitelist> = Account::try_from(whitelist_info)?;
 
         let token_vault: Account<TokenAccount> = Account::try_from(token_vault_info)?;
         let expected_vault = get_associated_token_address(&fund_key, &whitelist.mint);
@@ -252,270 +334,115 @@ pub(crate) fn compute_nav_lamports<'info>(
             ErrorCode::InvalidTokenVault
         );
 
-        require!(
-            token_price_info.key == &whitelist.pyth_feed,
-            ErrorCode::InvalidOracle
-        );
-        require!(
-            token_price_info.owner == &pyth_program_id,
-            ErrorCode::InvalidOracle
-        );
-        let token_price = load_pyth_price(token_price_info, &clock)?;
-        let value = token_value_in_lamports(
-            token_vault.amount,
-            whitelist.decimals,
-            token_price.price,
-            token_price.expo,
-            sol_price.price,
-            sol_price.expo,
-        )?;
-        if let Some(entry) = token_prices
-            .iter_mut()
-            .find(|entry| entry.0 == whitelist.mint)
-        {
-            entry.2 = token_price.price;
-            entry.3 = token_price.expo;
-        }
-        nav = nav
-            .checked_add(value as i128)
+        let holding = token_vault
+            .amount
+            .checked_add(whitelist.escrowed_amount)
             .ok_or(ErrorCode::MathOverflow)?;
-    }
-
-    let mut order_idx = base_len;
-    let mut prev_order: Option<Pubkey> = None;
-    for _ in 0..active_limit_count {
-        let order_info = &remaining[order_idx];
-        let order_sol_info = &remaining[order_idx + 1];
-        let order_token_info = &remaining[order_idx + 2];
-        order_idx += 3;
-
-        if let Some(prev) = prev_order {
+        let asset = if whitelist.kind == ASSET_KIND_FUND_SHARES {
+            let underlying_info = underlying_iter
+                .next()
+                .ok_or(ErrorCode::InvalidRemainingAccounts)?;
+            fund_share_value(
+                program_id,
+                &whitelist,
+                holding,
+                token_price_info,
+                underlying_info,
+                &clock,
+            )?
+        } else {
             require!(
-                prev.to_bytes() < order_info.key.to_bytes(),
-                ErrorCode::InvalidRemainingAccounts
+                token_price_info.key == &whitelist.pyth_feed,
+                ErrorCode::InvalidOracle
             );
-        }
-        prev_order = Some(*order_info.key);
-
-        let order: Account<LimitOrder> = Account::try_from(order_info)?;
-        require!(order.fund == fund_key, ErrorCode::InvalidTokenVault);
-        require!(order.status == ORDER_STATUS_OPEN, ErrorCode::OrderNotOpen);
-
-        let (vault_auth, _bump) = Pubkey::find_program_address(
-            &[b"limit_order_vault_auth", order_info.key.as_ref()],
-            program_id,
-        );
-        let (expected_sol_vault, _) = Pubkey::find_program_address(
-            &[b"limit_order_sol_vault", order_info.key.as_ref()],
-            program_id,
-        );
-        require!(
-            expected_sol_vault == *order_sol_info.key,
-            ErrorCode::InvalidOrderVault
-        );
-
-        match order.side {
-            SIDE_BUY => {
-                let expected_token_vault =
-                    anchor_spl::associated_token::get_associated_token_address(
-                        &vault_auth,
-                        &native_mint::ID,
-                    );
-                require!(
-                    expected_token_vault == *order_token_info.key,
-                    ErrorCode::InvalidOrderVault
-                );
-                nav = nav
-                    .checked_add(order_sol_info.lamports() as i128)
-                    .ok_or(ErrorCode::MathOverflow)?;
-            }
-            SIDE_SELL => {
-                let expected_token_vault =
-                    anchor_spl::associated_token::get_associated_token_address(
-                        &vault_auth,
-                        &order.mint,
-                    );
-                require!(
-                    expected_token_vault == *order_token_info.key,
-                    ErrorCode::InvalidOrderVault
-                );
-                let order_token: Account<TokenAccount> =
-                    Account::try_from(order_token_info)?;
-                require!(order_token.mint == order.mint, ErrorCode::InvalidOrderVault);
-                require!(order_token.owner == vault_auth, ErrorCode::InvalidOrderVault);
-                let token_info = token_prices
-                    .iter()
-                    .find(|entry| entry.0 == order.mint)
-                    .ok_or(ErrorCode::InvalidTokenVault)?;
-                let value = token_value_in_lamports(
-                    order_token.amount,
-                    token_info.1,
-                    token_info.2,
-                    token_info.3,
-                    sol_price.price,
-                    sol_price.expo,
-                )?;
-                nav = nav
-                    .checked_add(value as i128)
-                    .ok_or(ErrorCode::MathOverflow)?;
-            }
-            _ => return err!(ErrorCode::InvalidOrderSide),
-        }
-    }
-
-    let mut prev_dca: Option<Pubkey> = None;
-    for _ in 0..active_dca_count {
-        let order_info = &remaining[order_idx];
-        let order_sol_info = &remaining[order_idx + 1];
-        let order_token_info = &remaining[order_idx + 2];
-        order_idx += 3;
-
-        if let Some(prev) = prev_dca {
             require!(
-                prev.to_bytes() < order_info.key.to_bytes(),
-                ErrorCode::InvalidRemainingAccounts
+                token_price_info.owner == &pyth_program_id,
+                ErrorCode::InvalidOracle
             );
-        }
-        prev_dca = Some(*order_info.key);
-
-        let order: Account<DcaOrder> = Account::try_from(order_info)?;
-        require!(order.fund == fund_key, ErrorCode::InvalidTokenVault);
-        require!(order.status == DCA_STATUS_OPEN, ErrorCode::OrderNotOpen);
-
-        let (vault_auth, _bump) = Pubkey::find_program_address(
-            &[b"dca_order_vault_auth", order_info.key.as_ref()],
-            program_id,
-        );
-        let (expected_sol_vault, _) = Pubkey::find_program_address(
-            &[b"dca_order_sol_vault", order_info.key.as_ref()],
-            program_id,
-        );
-        require!(
-            expected_sol_vault == *order_sol_info.key,
-            ErrorCode::InvalidOrderVault
-        );
-
-        match order.side {
-            DCA_SIDE_BUY => {
-                let expected_token_vault =
-                    anchor_spl::associated_token::get_associated_token_address(
-                        &vault_auth,
-                        &native_mint::ID,
-                    );
-                require!(
-                    expected_token_vault == *order_token_info.key,
-                    ErrorCode::InvalidOrderVault
-                );
-                nav = nav
-                    .checked_add(order_sol_info.lamports() as i128)
-                    .ok_or(ErrorCode::MathOverflow)?;
-            }
-            DCA_SIDE_SELL => {
-                let expected_token_vault =
-                    anchor_spl::associated_token::get_associated_token_address(
-                        &vault_auth,
-                        &order.mint,
-                    );
-                require!(
-                    expected_token_vault == *order_token_info.key,
-                    ErrorCode::InvalidOrderVault
-                );
-                let order_token: Account<TokenAccount> =
-                    Account::try_from(order_token_info)?;
-                require!(order_token.mint == order.mint, ErrorCode::InvalidOrderVault);
-                require!(order_token.owner == vault_auth, ErrorCode::InvalidOrderVault);
-                let token_info = token_prices
-                    .iter()
-                    .find(|entry| entry.0 == order.mint)
-                    .ok_or(ErrorCode::InvalidTokenVault)?;
-                let value = token_value_in_lamports(
-                    order_token.amount,
-                    token_info.1,
-                    token_info.2,
-                    token_info.3,
-                    sol_price.price,
-                    sol_price.expo,
-                )?;
-                nav = nav
-                    .checked_add(value as i128)
-                    .ok_or(ErrorCode::MathOverflow)?;
+            let token_price = load_pyth_price(token_price_info)?;
+            let value = expected_sol_out_from_token(
+                holding,
+                whitelist.decimals,
+                token_price.price,
+                token_price.expo,
+                sol_price.price,
+                sol_price.expo,
+            )?;
+            NavAssetValue {
+                mint: whitelist.mint,
+                amount: holding,
+                price: token_price.price,
+                price_expo: token_price.expo,
+                price_publish_time: token_price.publish_time,
+                value_lamports: value,
             }
-            _ => return err!(ErrorCode::InvalidOrderSide),
-        }
-    }
-
-    require!(nav > 0, ErrorCode::InvalidNav);
-    Ok(nav as u64)
-}
-
-fn token_value_in_lamports(
-    amount: u64,
-    decimals: u8,
-    token_price: i64,
-    token_expo: i32,
-    sol_price: i64,
-    sol_expo: i32,
-) -> Result<u64> {
-    require!(token_price > 0, ErrorCode::InvalidOracle);
-    require!(sol_price > 0, ErrorCode::InvalidOracle);
-
-    let mut numerator = (amount as i128)
-        .checked_mul(token_price as i128)
-        .ok_or(ErrorCode::MathOverflow)?
-        .checked_mul(LAMPORTS_PER_SOL_U64 as i128)
-        .ok_or(ErrorCode::MathOverflow)?;
-    let mut denominator = sol_price as i128;
-
-    let exp = token_expo
-        .checked_sub(decimals as i32)
-        .and_then(|v| v.checked_sub(sol_expo))
-        .ok_or(ErrorCode::MathOverflow)?;
-
-    if exp >= 0 {
-        let scale = pow10_i128(exp as u32)?;
-        numerator = numerator
-            .checked_mul(scale)
-            .ok_or(ErrorCode::MathOverflow)?;
-    } else {
-        let scale = pow10_i128((-exp) as u32)?;
-        denominator = denominator
-            .checked_mul(scale)
+        };
+        token_value_lamports = token_value_lamports
+            .checked_add(asset.value_lamports)
             .ok_or(ErrorCode::MathOverflow)?;
+        assets.push(asset);
     }
+    require!(
+        underlying_iter.next().is_none(),
+        ErrorCode::InvalidRemainingAccounts
+    );
 
-    require!(denominator > 0, ErrorCode::MathOverflow);
-    Ok((numerator / denominator) as u64)
-}
-
-fn pow10_i128(exp: u32) -> Result<i128> {
-    let mut value: i128 = 1;
-    for _ in 0..exp {
-        value = value.checked_mul(10).ok_or(ErrorCode::MathOverflow)?;
-    }
-    Ok(value)
+    Ok(NavEntries {
+        token_value_lamports,
+        last_mint: prev_mint,
+        assets,
+    })
 }
 
-struct PythPrice {
-    price: i64,
-    expo: i32,
-}
+fn fund_share_value<'info>(
+    program_id: &Pubkey,
+    whitelist: &FundWhitelist,
+    holding: u64,
+    snapshot_info: &'info AccountInfo<'info>,
+    underlying_info: &'info AccountInfo<'info>,
+    clock: &Clock,
+) -> Result<NavAssetValue> {
+    require!(
+        underlying_info.key == &whitelist.underlying_fund,
+        ErrorCode::InvalidRemainingAccounts
+    );
+    let underlying: Account<FundState> = Account::try_from(underlying_info)?;
+    let (expected_snapshot, _) = Pubkey::find_program_address(
+        &[b"nav_snapshot", whitelist.underlying_fund.as_ref()],
+        program_id,
+    );
+    require!(
+        expected_snapshot == *snapshot_info.key,
+        ErrorCode::InvalidRemainingAccounts
+    );
+    let snapshot: Account<NavSnapshot> = Account::try_from(snapshot_info)?;
+    require!(
+        snapshot.fund == whitelist.underlying_fund,
+        ErrorCode::InvalidRemainingAccounts
+    );
+    require!(
+        snapshot.is_fresh(&underlying, clock.slot)
+            && snapshot.total_shares == underlying.total_shares,
+        ErrorCode::StaleNavSnapshot
+    );
+    require!(snapshot.total_shares > 0, ErrorCode::InvalidNav);
 
-fn load_pyth_price(price_info: &AccountInfo, clock: &Clock) -> Result<PythPrice> {
-    let feed = SolanaPriceAccount::account_info_to_feed(price_info)
-        .map_err(|_| ErrorCode::InvalidOracle)?;
-    let price = feed
-        .get_price_no_older_than(clock.unix_timestamp, ORACLE_MAX_AGE_SECS)
-        .ok_or(ErrorCode::StaleOracle)?;
-    require!(price.price > 0, ErrorCode::InvalidOracle);
-    let price_u128 = price.price as u128;
-    let max_conf = price_u128
-        .checked_mul(MAX_CONF_BPS as u128)
+    let total_shares = snapshot.total_shares as u128;
+    let value = (holding as u128)
+        .checked_mul(snapshot.nav_lamports as u128)
         .ok_or(ErrorCode::MathOverflow)?
-        .checked_div(10_000)
-        .ok_or(ErrorCode::MathOverflow)? as u64;
-    require!(price.conf <= max_conf, ErrorCode::InvalidOracleConfidence);
-    Ok(PythPrice {
-        price: price.price,
-        expo: price.expo,
+        / total_shares;
+    let share_price = (snapshot.nav_lamports as u128)
+        .checked_mul(LAMPORTS_PER_SOL_U64 as u128)
+        .ok_or(ErrorCode::MathOverflow)?
+        / total_shares;
+    Ok(NavAssetValue {
+        mint: whitelist.mint,
+        amount: holding,
+        price: share_price as i64,
+        price_expo: -9,
+        price_publish_time: snapshot.updated_ts,
+        value_lamports: value as u64,
     })
 }
+
@@ -2,32 +2,51 @@ use anchor_lang::prelude::*;
 use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
 use anchor_lang::solana_program::program::invoke_signed;
 use anchor_spl::token::{self, CloseAccount, Token, TokenAccount};
-use anchor_spl::token::spl_token::native_mint;
-use pyth_sdk_solana::state::SolanaPriceAccount;
 
 use crate::errors::ErrorCode;
-use crate::state::dca_order::{DcaOrder, DCA_SIDE_BUY, DCA_SIDE_SELL, DCA_STATUS_EXECUTED, DCA_STATUS_OPEN};
-use crate::state::fund::{FundState, FundVault, FUND_TYPE_TRADING};
+use crate::events::DcaOrderClosed;
+use crate::instructions::create_limit_order::track_quote_escrow;
+use crate::instructions::execute_limit_order::{
+    load_quote_leg, pro_rata_min_out, QuoteLeg, QUOTE_ACCOUNT_COUNT,
+};
+use crate::instructions::expire_limit_order::drain_order_sol_vault;
+use crate::instructions::rebalance_strategy::read_token_amount;
+use crate::instructions::set_keeper_tip::pay_keeper_tip;
+use crate::instructions::set_permissionless_execution::{
+    pay_price_improvement, require_order_executor,
+};
+use crate::oracle::{
+    apply_confidence_premium, apply_max_slippage, confidence_bps, cross_price,
+    expected_sol_out_from_token, expected_token_out_from_sol, expected_token_out_from_token,
+    load_pyth_price, scale_price,
+};
+use crate::state::dca_order::{
+    DcaOrder, DCA_MODE_VALUE_AVERAGE, DCA_SIDE_BUY, DCA_SIDE_SELL, DCA_STATUS_EXECUTED,
+    DCA_STATUS_OPEN,
+};
+use crate::state::fund::{pro_rata, FundState, FundVault, FUND_TYPE_TRADING};
 use crate::state::global_config::GlobalConfig;
+use crate::state::keeper_record::KeeperRecord;
 use crate::state::whitelist::FundWhitelist;
 
-const ORACLE_MAX_AGE_SECS: u64 = 60;
-const MAX_CONF_BPS: u64 = 200;
-const JUPITER_PROGRAM_ID: Pubkey = pubkey!("JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4");
-const LAMPORTS_PER_SOL_U64: u64 = 1_000_000_000;
+const VALUE_AVERAGE_MAX_SLICE_MULTIPLIER: u64 = 2;
 
 pub fn execute_dca_order<'info>(
     ctx: Context<'_, '_, 'info, 'info, ExecuteDcaOrder<'info>>,
     swap_data: Vec<u8>,
 ) -> Result<()> {
-    require!(
-        ctx.accounts.executor.key() == ctx.accounts.config.keeper,
-        ErrorCode::Unauthorized
-    );
+    let registered_keeper = require_order_executor(
+        &ctx.accounts.config,
+        ctx.accounts.keeper_record.as_ref(),
+        &ctx.accounts.fund_state,
+        &ctx.accounts.executor.key(),
+        &ctx.accounts.fund_state.key(),
+    )?;
     require!(
         ctx.accounts.fund_state.fund_type == FUND_TYPE_TRADING,
         ErrorCode::InvalidFundType
     );
+    require!(!ctx.accounts.fund_state.paused, ErrorCode::FundPaused);
 
     let order = &mut ctx.accounts.order;
     require!(order.status == DCA_STATUS_OPEN, ErrorCode::OrderNotOpen);
@@ -59,14 +78,68 @@ pub fn execute_dca_order<'info>(
     );
     let sol_price = load_pyth_price(sol_price_info)?;
 
+    let fund_key = ctx.accounts.fund_state.key();
+    let (quote, swap_accounts) = if order.quote_mint == Pubkey::default() {
+        (None, ctx.remaining_accounts)
+    } else {
+        require!(
+            ctx.remaining_accounts.len() >= QUOTE_ACCOUNT_COUNT,
+            ErrorCode::InvalidRemainingAccounts
+        );
+        let (quote_accounts, swap_accounts) =
+            ctx.remaining_accounts.split_at(QUOTE_ACCOUNT_COUNT);
+        let quote = load_quote_leg(
+            &order.quote_mint,
+            &order.quote_price_feed,
+            &order.pyth_program_id,
+            &fund_key,
+            quote_accounts,
+        )?;
+        (Some(quote), swap_accounts)
+    };
+
+    let decimals = ctx.accounts.whitelist.decimals;
+    let slice_amount = if order.mode == DCA_MODE_VALUE_AVERAGE {
+        let held_value = match quote.as_ref() {
+            Some(q) => expected_token_out_from_token(
+                order.acquired_amount,
+                decimals,
+                price.price,
+                price.expo,
+                q.decimals,
+                q.price,
+                q.expo,
+            )?,
+            None => expected_sol_out_from_token(
+                order.acquired_amount,
+                decimals,
+                price.price,
+                price.expo,
+                sol_price.price,
+                sol_price.expo,
+            )?,
+        };
+        value_average_slice(order, held_value)?
+    } else {
+        order.slice_amount.min(order.remaining_amount)
+    };
+    let band_price = match quote.as_ref() {
+        Some(q) => cross_price(price.price, price.expo, q.price, q.expo, order.price_expo)?,
+        None => scale_price(price.price, price.expo, order.price_expo)?,
+    };
+    if slice_amount == 0 || !price_in_band(order, band_price) {
+        return advance_schedule(order);
+    }
+
     require!(!swap_data.is_empty(), ErrorCode::InvalidSwapProgram);
-    require!(!ctx.remaining_accounts.is_empty(), ErrorCode::InvalidSwapProgram);
+    require!(!swap_accounts.is_empty(), ErrorCode::InvalidSwapProgram);
     require!(
-        ctx.accounts.swap_program.key() == JUPITER_PROGRAM_ID,
+        ctx.accounts
+            .config
+            .is_swap_program_allowed(&ctx.accounts.swap_program.key()),
         ErrorCode::InvalidSwapProgram
     );
 
-    let fund_key = ctx.accounts.fund_state.key();
     let expected_fund_token_vault = anchor_spl::associated_token::get_associated_token_address(
         &fund_key,
         &order.mint,
@@ -83,77 +156,50 @@ pub fn execute_dca_order<'info>(
         ErrorCode::InvalidOrderVault
     );
 
-    match order.side {
-        DCA_SIDE_BUY => {
-            let (sol_vault, _bump) = Pubkey::find_program_address(
-                &[b"dca_order_sol_vault", order.key().as_ref()],
-                ctx.program_id,
-            );
-            require!(sol_vault == ctx.accounts.order_sol_vault.key(), ErrorCode::InvalidOrderVault);
-            require!(
-                ctx.accounts.order_sol_vault.owner == &anchor_lang::solana_program::system_program::ID,
-                ErrorCode::InvalidOrderVault
-            );
-            let expected_wsol_vault =
-                anchor_spl::associated_token::get_associated_token_address(
-                    &vault_auth,
-                    &native_mint::ID,
-                );
-            require!(
-                expected_wsol_vault == ctx.accounts.order_token_vault.key(),
-                ErrorCode::InvalidOrderVault
-            );
-            require!(
-                ctx.accounts.order_token_vault.mint == native_mint::ID,
-                ErrorCode::InvalidOrderVault
-            );
-            require!(
-                ctx.accounts.order_token_vault.owner == ctx.accounts.order_vault_auth.key(),
-                ErrorCode::InvalidOrderVault
-            );
-        }
-        DCA_SIDE_SELL => {
-            let expected_order_vault =
-                anchor_spl::associated_token::get_associated_token_address(
-                    &vault_auth,
-                    &order.mint,
-                );
-            require!(
-                expected_order_vault == ctx.accounts.order_token_vault.key(),
-                ErrorCode::InvalidOrderVault
-            );
-            require!(
-                ctx.accounts.order_token_vault.mint == order.mint,
-                ErrorCode::InvalidOrderVault
-            );
-            require!(
-                ctx.accounts.order_token_vault.owner == ctx.accounts.order_vault_auth.key(),
-                ErrorCode::InvalidOrderVault
-            );
-        }
-        _ => return err!(ErrorCode::InvalidOrderSide),
+    if order.escrows_sol() {
+        let (sol_vault, _bump) = Pubkey::find_program_address(
+            &[b"dca_order_sol_vault", order.key().as_ref()],
+            ctx.program_id,
+        );
+        require!(sol_vault == ctx.accounts.order_sol_vault.key(), ErrorCode::InvalidOrderVault);
+        require!(
+            ctx.accounts.order_sol_vault.owner == &anchor_lang::solana_program::system_program::ID,
+            ErrorCode::InvalidOrderVault
+        );
     }
+    let escrow_mint = order.escrow_mint();
+    let expected_order_vault =
+        anchor_spl::associated_token::get_associated_token_address(&vault_auth, &escrow_mint);
+    require!(
+        expected_order_vault == ctx.accounts.order_token_vault.key(),
+        ErrorCode::InvalidOrderVault
+    );
+    require!(
+        ctx.accounts.order_token_vault.mint == escrow_mint,
+        ErrorCode::InvalidOrderVault
+    );
+    require!(
+        ctx.accounts.order_token_vault.owner == ctx.accounts.order_vault_auth.key(),
+        ErrorCode::InvalidOrderVault
+    );
 
+    let output_account = match (order.side, quote.as_ref()) {
+        (DCA_SIDE_BUY, _) => ctx.accounts.fund_token_vault.key(),
+        (DCA_SIDE_SELL, Some(q)) => *q.fund_vault.key,
+        (DCA_SIDE_SELL, None) => ctx.accounts.fund_vault.key(),
+        _ => return err!(ErrorCode::InvalidOrderSide),
+    };
     let mut has_order_token_vault = false;
     let mut has_output_account = false;
     let mut has_vault_auth = false;
-    for acc in ctx.remaining_accounts.iter() {
+    for acc in swap_accounts.iter() {
         if *acc.key == ctx.accounts.order_token_vault.key() && acc.is_writable {
             has_order_token_vault = true;
         }
         if *acc.key == ctx.accounts.order_vault_auth.key() {
             has_vault_auth = true;
         }
-        if order.side == DCA_SIDE_BUY
-            && *acc.key == ctx.accounts.fund_token_vault.key()
-            && acc.is_writable
-        {
-            has_output_account = true;
-        }
-        if order.side == DCA_SIDE_SELL
-            && *acc.key == ctx.accounts.fund_vault.key()
-            && acc.is_writable
-        {
+        if *acc.key == output_account && acc.is_writable {
             has_output_account = true;
         }
     }
@@ -161,8 +207,7 @@ pub fn execute_dca_order<'info>(
     require!(has_output_account, ErrorCode::InvalidTokenVault);
     require!(has_vault_auth, ErrorCode::InvalidOrderVault);
 
-    let cpi_accounts: Vec<AccountMeta> = ctx
-        .remaining_accounts
+    let cpi_accounts: Vec<AccountMeta> = swap_accounts
         .iter()
         .map(|acc| AccountMeta {
             pubkey: *acc.key,
@@ -171,8 +216,8 @@ pub fn execute_dca_order<'info>(
         })
         .collect();
 
-    let mut infos: Vec<AccountInfo> = Vec::with_capacity(ctx.remaining_accounts.len());
-    for acc in ctx.remaining_accounts.iter() {
+    let mut infos: Vec<AccountInfo> = Vec::with_capacity(swap_accounts.len());
+    for acc in swap_accounts.iter() {
         infos.push(acc.clone());
     }
 
@@ -184,18 +229,9 @@ pub fn execute_dca_order<'info>(
     ];
     let signer_seeds_set = [signer_seeds];
 
-    let slice_amount = if order.remaining_amount >= order.slice_amount {
-        order.slice_amount
-    } else {
-        order.remaining_amount
-    };
-    require!(slice_amount > 0, ErrorCode::DcaCompleted);
-
-    let mut fund_token_before = ctx.accounts.fund_token_vault.amount;
     let mut order_token_before = ctx.accounts.order_token_vault.amount;
-    let mut fund_sol_before = ctx.accounts.fund_vault.to_account_info().lamports();
 
-    if order.side == DCA_SIDE_BUY {
+    if order.escrows_sol() {
         let (sol_vault, sol_vault_bump) = Pubkey::find_program_address(
             &[b"dca_order_sol_vault", order_key.as_ref()],
             ctx.program_id,
@@ -230,11 +266,15 @@ pub fn execute_dca_order<'info>(
 
         ctx.accounts.order_token_vault.reload()?;
         order_token_before = ctx.accounts.order_token_vault.amount;
-        fund_token_before = ctx.accounts.fund_token_vault.amount;
-        fund_sol_before = ctx.accounts.fund_vault.to_account_info().lamports();
     } else {
         require!(order_token_before >= slice_amount, ErrorCode::InsufficientLiquidity);
     }
+    let out_before = output_amount(
+        order.side,
+        quote.as_ref(),
+        &ctx.accounts.fund_token_vault,
+        &ctx.accounts.fund_vault.to_account_info(),
+    )?;
 
     invoke_signed(
         &Instruction {
@@ -248,242 +288,283 @@ pub fn execute_dca_order<'info>(
 
     ctx.accounts.fund_token_vault.reload()?;
     ctx.accounts.order_token_vault.reload()?;
-    let fund_token_after = ctx.accounts.fund_token_vault.amount;
     let order_token_after = ctx.accounts.order_token_vault.amount;
-    let fund_sol_after = ctx.accounts.fund_vault.to_account_info().lamports();
-
-    match order.side {
-        DCA_SIDE_BUY => {
-            let token_delta = fund_token_after
-                .checked_sub(fund_token_before)
-                .ok_or(ErrorCode::MathOverflow)?;
-            let expected_out = expected_token_out_from_sol(
-                slice_amount,
-                ctx.accounts.whitelist.decimals,
-                price.price,
-                price.expo,
-                sol_price.price,
-                sol_price.expo,
-            )?;
-            let min_expected = apply_max_slippage(
-                expected_out,
-                ctx.accounts.config.max_slippage_bps,
-            )?;
-            require!(token_delta >= min_expected, ErrorCode::InvalidTokenVault);
-            require!(token_delta >= order.min_out, ErrorCode::InvalidTokenVault);
-            require!(order_token_before == slice_amount, ErrorCode::InvalidOrderVault);
-            require!(order_token_after == 0, ErrorCode::InvalidOrderVault);
-        }
-        DCA_SIDE_SELL => {
-            let sol_delta = fund_sol_after
-                .checked_sub(fund_sol_before)
-                .ok_or(ErrorCode::MathOverflow)?;
-            let expected_out = expected_sol_out_from_token(
-                slice_amount,
-                ctx.accounts.whitelist.decimals,
-                price.price,
-                price.expo,
-                sol_price.price,
-                sol_price.expo,
-            )?;
-            let min_expected = apply_max_slippage(
-                expected_out,
-                ctx.accounts.config.max_slippage_bps,
-            )?;
-            require!(sol_delta >= min_expected, ErrorCode::InvalidTokenVault);
-            require!(sol_delta >= order.min_out, ErrorCode::InvalidTokenVault);
-            let expected_after = order_token_before
-                .checked_sub(slice_amount)
-                .ok_or(ErrorCode::MathOverflow)?;
-            require!(order_token_after == expected_after, ErrorCode::InvalidOrderVault);
-        }
+    let out_after = output_amount(
+        order.side,
+        quote.as_ref(),
+        &ctx.accounts.fund_token_vault,
+        &ctx.accounts.fund_vault.to_account_info(),
+    )?;
+    let out_delta = out_after
+        .checked_sub(out_before)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    let expected_out = match (order.side, quote.as_ref()) {
+        (DCA_SIDE_BUY, Some(q)) => expected_token_out_from_token(
+            slice_amount,
+            q.decimals,
+            q.price,
+            q.expo,
+            decimals,
+            price.price,
+            price.expo,
+        )?,
+        (DCA_SIDE_SELL, Some(q)) => expected_token_out_from_token(
+            slice_amount,
+            decimals,
+            price.price,
+            price.expo,
+            q.decimals,
+            q.price,
+            q.expo,
+        )?,
+        (DCA_SIDE_BUY, None) => expected_token_out_from_sol(
+            slice_amount,
+            decimals,
+            price.price,
+            price.expo,
+            sol_price.price,
+            sol_price.expo,
+        )?,
+        (DCA_SIDE_SELL, None) => expected_sol_out_from_token(
+            slice_amount,
+            decimals,
+            price.price,
+            price.expo,
+            sol_price.price,
+            sol_price.expo,
+        )?,
         _ => return err!(ErrorCode::InvalidOrderSide),
+    };
+    let max_slippage_bps = order
+        .max_slippage_bps
+        .min(ctx.accounts.config.max_slippage_bps);
+    let min_expected = apply_max_slippage(expected_out, max_slippage_bps)?;
+    let slice_min_out = pro_rata_min_out(order.min_out, slice_amount, order.slice_amount)?;
+    require!(out_delta >= min_expected, ErrorCode::InvalidTokenVault);
+    require!(out_delta >= slice_min_out, ErrorCode::InvalidTokenVault);
+
+    if order.escrows_sol() {
+        require!(order_token_before == slice_amount, ErrorCode::InvalidOrderVault);
+        require!(order_token_after == 0, ErrorCode::InvalidOrderVault);
+    } else {
+        let expected_after = order_token_before
+            .checked_sub(slice_amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+        require!(order_token_after == expected_after, ErrorCode::InvalidOrderVault);
+    }
+    if order.side == DCA_SIDE_BUY {
+        order.acquired_amount = order
+            .acquired_amount
+            .checked_add(out_delta)
+            .ok_or(ErrorCode::MathOverflow)?;
     }
 
-    order.remaining_amount = order
+    let remaining_after = order
         .remaining_amount
         .checked_sub(slice_amount)
         .ok_or(ErrorCode::MathOverflow)?;
-    order.next_exec_ts = order
-        .next_exec_ts
-        .checked_add(order.interval_secs)
-        .ok_or(ErrorCode::MathOverflow)?;
-
-    if order.remaining_amount == 0 {
-        order.status = DCA_STATUS_EXECUTED;
-        ctx.accounts.fund_state.active_dca_count = ctx
+    match (order.side, quote.as_ref()) {
+        (DCA_SIDE_BUY, Some(q)) => track_quote_escrow(
+            ctx.program_id,
+            q.whitelist,
+            order.remaining_amount,
+            remaining_after,
+        )?,
+        (DCA_SIDE_BUY, None) => ctx
             .accounts
             .fund_state
-            .active_dca_count
-            .checked_sub(1)
-            .ok_or(ErrorCode::MathOverflow)?;
+            .track_escrow(order.remaining_amount, remaining_after)?,
+        _ => ctx
+            .accounts
+            .whitelist
+            .track_escrow(order.remaining_amount, remaining_after)?,
+    }
+    ctx.accounts.fund_state.mark_holdings_changed()?;
+    let notional_lamports = match (order.side, quote.as_ref()) {
+        (DCA_SIDE_BUY, None) => slice_amount,
+        (DCA_SIDE_BUY, Some(q)) => expected_sol_out_from_token(
+            slice_amount,
+            q.decimals,
+            q.price,
+            q.expo,
+            sol_price.price,
+            sol_price.expo,
+        )?,
+        _ => expected_sol_out_from_token(
+            slice_amount,
+            decimals,
+            price.price,
+            price.expo,
+            sol_price.price,
+            sol_price.expo,
+        )?,
+    };
+    let tip_lamports = ctx.accounts.fund_state.keeper_tip(
+        notional_lamports,
+        ctx.accounts.config.max_keeper_tip_lamports,
+        slice_amount,
+        order.total_amount,
+        registered_keeper,
+    )?;
+    let tip_paid = pay_keeper_tip(
+        fund_key,
+        &ctx.accounts.fund_vault.to_account_info(),
+        &ctx.accounts.executor.to_account_info(),
+        notional_lamports,
+        tip_lamports,
+    )?;
+    let oracle_conf_bps = confidence_bps(&price)?
+        .checked_add(match quote.as_ref() {
+            Some(q) => q.conf_bps,
+            None => confidence_bps(&sol_price)?,
+        })
+        .ok_or(ErrorCode::MathOverflow)?;
+    let improvement_floor = apply_confidence_premium(expected_out, oracle_conf_bps)?;
+    let surplus = out_delta.saturating_sub(improvement_floor);
+    let surplus_lamports = match (order.side, quote.as_ref()) {
+        _ if surplus == 0 || !ctx.accounts.fund_state.permissionless_execution => 0,
+        (DCA_SIDE_SELL, None) => surplus,
+        (DCA_SIDE_SELL, Some(q)) => expected_sol_out_from_token(
+            surplus,
+            q.decimals,
+            q.price,
+            q.expo,
+            sol_price.price,
+            sol_price.expo,
+        )?,
+        _ => expected_sol_out_from_token(
+            surplus,
+            decimals,
+            price.price,
+            price.expo,
+            sol_price.price,
+            sol_price.expo,
+        )?,
+    };
+    let payout_cap = pro_rata(
+        ctx.accounts.config.max_keeper_tip_lamports,
+        slice_amount,
+        order.total_amount,
+    )?;
+    let share_lamports = ctx
+        .accounts
+        .fund_state
+        .executor_share(surplus_lamports, payout_cap.saturating_sub(tip_paid))?;
+    pay_price_improvement(
+        fund_key,
+        &ctx.accounts.fund_vault.to_account_info(),
+        &ctx.accounts.executor.to_account_info(),
+        improvement_floor,
+        out_delta,
+        surplus_lamports,
+        share_lamports,
+    )?;
+    order.remaining_amount = remaining_after;
+    advance_schedule(order)?;
 
-        if order.side == DCA_SIDE_SELL || order.side == DCA_SIDE_BUY {
-            if ctx.accounts.order_token_vault.amount == 0 {
-                let close_ctx = CpiContext::new_with_signer(
-                    ctx.accounts.token_program.to_account_info(),
-                    CloseAccount {
-                        account: ctx.accounts.order_token_vault.to_account_info(),
-                        destination: ctx.accounts.fund_vault.to_account_info(),
-                        authority: ctx.accounts.order_vault_auth.to_account_info(),
-                    },
-                    &signer_seeds_set,
-                );
-                token::close_account(close_ctx)?;
-            }
-        }
-        let sol_balance = ctx.accounts.order_sol_vault.to_account_info().lamports();
-        if sol_balance > 0 {
-            let (sol_vault, sol_vault_bump) = Pubkey::find_program_address(
-                &[b"dca_order_sol_vault", order_key.as_ref()],
-                ctx.program_id,
-            );
-            require!(sol_vault == ctx.accounts.order_sol_vault.key(), ErrorCode::InvalidOrderVault);
-            let sol_vault_seeds: &[&[u8]] = &[
-                b"dca_order_sol_vault",
-                order_key.as_ref(),
-                &[sol_vault_bump],
-            ];
-            let sol_vault_signer = [sol_vault_seeds];
-            let transfer_ctx = CpiContext::new_with_signer(
-                ctx.accounts.system_program.to_account_info(),
-                anchor_lang::system_program::Transfer {
-                    from: ctx.accounts.order_sol_vault.to_account_info(),
-                    to: ctx.accounts.fund_vault.to_account_info(),
-                },
-                &sol_vault_signer,
-            );
-            anchor_lang::system_program::transfer(transfer_ctx, sol_balance)?;
-        }
+    if order.remaining_amount > 0 {
+        return Ok(());
     }
 
-    Ok(())
-}
+    let close_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.order_token_vault.to_account_info(),
+            destination: ctx.accounts.manager.to_account_info(),
+            authority: ctx.accounts.order_vault_auth.to_account_info(),
+        },
+        &signer_seeds_set,
+    );
+    token::close_account(close_ctx)?;
+    if ctx.accounts.order_sol_vault.to_account_info().lamports() > 0 {
+        let (sol_vault, sol_vault_bump) = Pubkey::find_program_address(
+            &[b"dca_order_sol_vault", order_key.as_ref()],
+            ctx.program_id,
+        );
+        require!(sol_vault == ctx.accounts.order_sol_vault.key(), ErrorCode::InvalidOrderVault);
+        let sol_vault_seeds: &[&[u8]] = &[
+            b"dca_order_sol_vault",
+            order_key.as_ref(),
+            &[sol_vault_bump],
+        ];
+        drain_order_sol_vault(
+            &ctx.accounts.order_sol_vault.to_account_info(),
+            &ctx.accounts.fund_vault.to_account_info(),
+            &ctx.accounts.manager.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            sol_vault_seeds,
+        )?;
+    }
 
-struct PythPrice {
-    price: i64,
-    expo: i32,
-}
+    order.status = DCA_STATUS_EXECUTED;
+    ctx.accounts.fund_state.active_dca_count = ctx
+        .accounts
+        .fund_state
+        .active_dca_count
+        .checked_sub(1)
+        .ok_or(ErrorCode::MathOverflow)?;
+    emit!(DcaOrderClosed::new(order_key, order, Clock::get()?.unix_timestamp));
 
-fn load_pyth_price(price_info: &AccountInfo) -> Result<PythPrice> {
-    let feed = SolanaPriceAccount::account_info_to_feed(price_info)
-        .map_err(|_| ErrorCode::InvalidOracle)?;
-    let price = feed
-        .get_price_no_older_than(Clock::get()?.unix_timestamp, ORACLE_MAX_AGE_SECS)
-        .ok_or(ErrorCode::StaleOracle)?;
-    require!(price.price > 0, ErrorCode::InvalidOracle);
-    let price_u128 = price.price as u128;
-    let max_conf = price_u128
-        .checked_mul(MAX_CONF_BPS as u128)
-        .ok_or(ErrorCode::MathOverflow)?
-        .checked_div(10_000)
-        .ok_or(ErrorCode::MathOverflow)? as u64;
-    require!(price.conf <= max_conf, ErrorCode::InvalidOracleConfidence);
-    Ok(PythPrice {
-        price: price.price,
-        expo: price.expo,
-    })
+    ctx.accounts
+        .order
+        .close(ctx.accounts.manager.to_account_info())
 }
 
-fn apply_max_slippage(expected_out: u64, max_slippage_bps: u16) -> Result<u64> {
-    require!(max_slippage_bps <= 10_000, ErrorCode::InvalidFeeBps);
-    let expected = expected_out as u128;
-    let factor = 10_000u128
-        .checked_sub(max_slippage_bps as u128)
+fn advance_schedule(order: &mut DcaOrder) -> Result<()> {
+    order.next_exec_ts = order
+        .next_exec_ts
+        .checked_add(order.interval_secs)
         .ok_or(ErrorCode::MathOverflow)?;
-    let min_expected = expected
-        .checked_mul(factor)
-        .ok_or(ErrorCode::MathOverflow)?
-        .checked_div(10_000u128)
+    order.period_count = order
+        .period_count
+        .checked_add(1)
         .ok_or(ErrorCode::MathOverflow)?;
-    Ok(min_expected as u64)
+    Ok(())
 }
 
-fn pow10_i128(exp: u32) -> Result<i128> {
-    let mut value: i128 = 1;
-    for _ in 0..exp {
-        value = value.checked_mul(10).ok_or(ErrorCode::MathOverflow)?;
+fn output_amount(
+    side: u8,
+    quote: Option<&QuoteLeg>,
+    fund_token_vault: &TokenAccount,
+    fund_vault: &AccountInfo,
+) -> Result<u64> {
+    match (side, quote) {
+        (DCA_SIDE_BUY, _) => Ok(fund_token_vault.amount),
+        (DCA_SIDE_SELL, Some(q)) => read_token_amount(q.fund_vault),
+        (DCA_SIDE_SELL, None) => Ok(fund_vault.lamports()),
+        _ => err!(ErrorCode::InvalidOrderSide),
     }
-    Ok(value)
 }
 
-fn expected_sol_out_from_token(
-    amount: u64,
-    decimals: u8,
-    token_price: i64,
-    token_expo: i32,
-    sol_price: i64,
-    sol_expo: i32,
-) -> Result<u64> {
-    require!(token_price > 0, ErrorCode::InvalidOracle);
-    require!(sol_price > 0, ErrorCode::InvalidOracle);
-    let mut numerator = (amount as i128)
-        .checked_mul(token_price as i128)
-        .ok_or(ErrorCode::MathOverflow)?
-        .checked_mul(LAMPORTS_PER_SOL_U64 as i128)
-        .ok_or(ErrorCode::MathOverflow)?;
-    let mut denominator = sol_price as i128;
-
-    let exp = token_expo
-        .checked_sub(decimals as i32)
-        .and_then(|v| v.checked_sub(sol_expo))
-        .ok_or(ErrorCode::MathOverflow)?;
-
-    if exp >= 0 {
-        let scale = pow10_i128(exp as u32)?;
-        numerator = numerator
-            .checked_mul(scale)
-            .ok_or(ErrorCode::MathOverflow)?;
-    } else {
-        let scale = pow10_i128((-exp) as u32)?;
-        denominator = denominator
-            .checked_mul(scale)
-            .ok_or(ErrorCode::MathOverflow)?;
+fn price_in_band(order: &DcaOrder, price: i128) -> bool {
+    if order.band_lower_price > 0 && price < order.band_lower_price as i128 {
+        return false;
     }
-    require!(denominator > 0, ErrorCode::MathOverflow);
-    Ok((numerator / denominator) as u64)
+    if order.band_upper_price > 0 && price > order.band_upper_price as i128 {
+        return false;
+    }
+    true
 }
 
-fn expected_token_out_from_sol(
-    sol_lamports: u64,
-    decimals: u8,
-    token_price: i64,
-    token_expo: i32,
-    sol_price: i64,
-    sol_expo: i32,
-) -> Result<u64> {
-    require!(token_price > 0, ErrorCode::InvalidOracle);
-    require!(sol_price > 0, ErrorCode::InvalidOracle);
-    let mut numerator = (sol_lamports as i128)
-        .checked_mul(sol_price as i128)
+fn value_average_slice(order: &DcaOrder, held_value: u64) -> Result<u64> {
+    let target_value = (order.period_count as u64)
+        .checked_add(1)
         .ok_or(ErrorCode::MathOverflow)?
-        .checked_mul(pow10_i128(decimals as u32)?)
+        .checked_mul(order.slice_amount)
         .ok_or(ErrorCode::MathOverflow)?;
-    let mut denominator = (token_price as i128)
-        .checked_mul(LAMPORTS_PER_SOL_U64 as i128)
-        .ok_or(ErrorCode::MathOverflow)?;
-
-    let exp = sol_expo
-        .checked_sub(token_expo)
-        .ok_or(ErrorCode::MathOverflow)?;
-    if exp >= 0 {
-        let scale = pow10_i128(exp as u32)?;
-        numerator = numerator
-            .checked_mul(scale)
-            .ok_or(ErrorCode::MathOverflow)?;
-    } else {
-        let scale = pow10_i128((-exp) as u32)?;
-        denominator = denominator
-            .checked_mul(scale)
-            .ok_or(ErrorCode::MathOverflow)?;
-    }
-    require!(denominator > 0, ErrorCode::MathOverflow);
-    Ok((numerator / denominator) as u64)
+    let max_slice = order
+        .slice_amount
+        .checked_mul(VALUE_AVERAGE_MAX_SLICE_MULTIPLIER)
+        .ok_or(ErrorCode::MathOverflow)?
+        .min(order.remaining_amount);
+    Ok(target_value.saturating_sub(held_value).min(max_slice))
 }
 
 #[derive(Accounts)]
 #[instruction(order_id: u64)]
 pub struct ExecuteDcaOrder<'info> {
+    #[account(mut)]
     pub executor: Signer<'info>,
     #[account(
         seeds = [b"config", config.config_id.to_le_bytes().as_ref()],
@@ -492,17 +573,21 @@ pub struct ExecuteDcaOrder<'info> {
     pub config: Account<'info, GlobalConfig>,
     #[account(
         mut,
-        seeds = [b"fund", config.key().as_ref(), fund_state.manager.as_ref(), fund_state.fund_id.to_le_bytes().as_ref()],
+        seeds = [b"fund", config.key().as_ref(), fund_state.creator.as_ref(), fund_state.fund_id.to_le_bytes().as_ref()],
         bump = fund_state.bump,
         has_one = config
     )]
     pub fund_state: Account<'info, FundState>,
+    /// CHECK: fund manager, receives the order rent
+    #[account(mut, address = fund_state.manager)]
+    pub manager: UncheckedAccount<'info>,
     #[account(
         mut,
         seeds = [b"vault", fund_state.key().as_ref()],
         bump = fund_state.vault_bump
     )]
     pub fund_vault: Account<'info, FundVault>,
+    #[account(mut)]
     pub whitelist: Account<'info, FundWhitelist>,
     #[account(mut)]
     pub fund_token_vault: Account<'info, TokenAccount>,
@@ -523,8 +608,13 @@ pub struct ExecuteDcaOrder<'info> {
     pub price_feed: AccountInfo<'info>,
     /// CHECK: Pyth SOL/USD price feed
     pub sol_price_feed: AccountInfo<'info>,
-    /// CHECK: swap program id
+    /// CHECK: swap program id, checked against the config registry
     pub swap_program: AccountInfo<'info>,
+    #[account(
+        seeds = [b"keeper", config.key().as_ref(), executor.key().as_ref()],
+        bump = keeper_record.bump
+    )]
+    pub keeper_record: Option<Account<'info, KeeperRecord>>,
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
 }
@@ -2,38 +2,67 @@ use anchor_lang::prelude::*;
 use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
 use anchor_lang::solana_program::program::invoke_signed;
 use anchor_spl::token::{self, CloseAccount, Token, TokenAccount};
-use anchor_spl::token::spl_token::native_mint;
-use pyth_sdk_solana::state::SolanaPriceAccount;
 
 use crate::errors::ErrorCode;
-use crate::state::fund::{FundState, FundVault, FUND_TYPE_TRADING};
+use crate::events::LimitOrderClosed;
+use crate::instructions::create_limit_order::track_quote_escrow;
+use crate::instructions::expire_limit_order::drain_order_sol_vault;
+use crate::instructions::rebalance_strategy::read_token_amount;
+use crate::instructions::set_keeper_tip::pay_keeper_tip;
+use crate::instructions::set_permissionless_execution::{
+    pay_price_improvement, require_order_executor,
+};
+use crate::oracle::{
+    apply_confidence_premium, apply_max_slippage, confidence_bps, cross_price,
+    expected_sol_out_from_token, expected_token_out_from_sol, expected_token_out_from_token,
+    load_pyth_price, scale_price,
+};
+use crate::state::fund::{pro_rata, FundState, FundVault, FUND_TYPE_TRADING};
 use crate::state::global_config::GlobalConfig;
-use crate::state::limit_order::{LimitOrder, ORDER_STATUS_EXECUTED, ORDER_STATUS_OPEN, SIDE_BUY, SIDE_SELL};
+use crate::state::keeper_record::KeeperRecord;
+use crate::state::limit_order::{
+    LimitOrder, MIN_FILL_BPS, ORDER_LEG_LIMIT, ORDER_LEG_STOP, ORDER_STATUS_EXECUTED, ORDER_STATUS_OPEN,
+    ORDER_TYPE_BRACKET, ORDER_TYPE_LIMIT, ORDER_TYPE_STOP, ORDER_TYPE_TRAILING_STOP, SIDE_BUY,
+    SIDE_SELL,
+};
 use crate::state::whitelist::FundWhitelist;
 
-const ORACLE_MAX_AGE_SECS: u64 = 60;
-const MAX_CONF_BPS: u64 = 200;
-const JUPITER_PROGRAM_ID: Pubkey = pubkey!("JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4");
-const LAMPORTS_PER_SOL_U64: u64 = 1_000_000_000;
+pub(crate) const QUOTE_ACCOUNT_COUNT: usize = 3;
 
 pub fn execute_limit_order<'info>(
     ctx: Context<'_, '_, 'info, 'info, ExecuteLimitOrder<'info>>,
+    fill_amount: u64,
     swap_data: Vec<u8>,
 ) -> Result<()> {
     let order = &mut ctx.accounts.order;
-    require!(
-        ctx.accounts.executor.key() == ctx.accounts.config.keeper,
-        ErrorCode::Unauthorized
-    );
+    let registered_keeper = require_order_executor(
+        &ctx.accounts.config,
+        ctx.accounts.keeper_record.as_ref(),
+        &ctx.accounts.fund_state,
+        &ctx.accounts.executor.key(),
+        &ctx.accounts.fund_state.key(),
+    )?;
     require!(
         ctx.accounts.fund_state.fund_type == FUND_TYPE_TRADING,
         ErrorCode::InvalidFundType
     );
+    require!(!ctx.accounts.fund_state.paused, ErrorCode::FundPaused);
     require!(order.status == ORDER_STATUS_OPEN, ErrorCode::OrderNotOpen);
     require!(order.fund == ctx.accounts.fund_state.key(), ErrorCode::InvalidTokenVault);
     if order.expiry_ts != 0 {
         require!(Clock::get()?.unix_timestamp <= order.expiry_ts, ErrorCode::OrderExpired);
     }
+    let remaining_in = order
+        .amount_in
+        .checked_sub(order.filled_in)
+        .ok_or(ErrorCode::MathOverflow)?;
+    require!(fill_amount > 0, ErrorCode::InvalidFillAmount);
+    require!(fill_amount <= remaining_in, ErrorCode::InvalidFillAmount);
+    require!(
+        fill_amount == remaining_in || fill_amount >= min_fill_amount(order.amount_in)?,
+        ErrorCode::InvalidFillAmount
+    );
+    let fill_min_out = pro_rata_min_out(order.min_out, fill_amount, order.amount_in)?;
 
     require!(order.side == SIDE_BUY || order.side == SIDE_SELL, ErrorCode::InvalidOrderSide);
     require!(ctx.accounts.whitelist.enabled, ErrorCode::InvalidTokenVault);
@@ -57,27 +86,45 @@ pub fn execute_limit_order<'info>(
         ErrorCode::InvalidOracle
     );
     let sol_price = load_pyth_price(sol_price_info)?;
-    let price_at_limit_expo = scale_price(price.price, price.expo, order.price_expo)?;
-    let limit_price = order.limit_price as i128;
 
-    match order.side {
-        SIDE_BUY => {
-            require!(price_at_limit_expo <= limit_price, ErrorCode::OrderNotTriggered);
-        }
-        SIDE_SELL => {
-            require!(price_at_limit_expo >= limit_price, ErrorCode::OrderNotTriggered);
-        }
-        _ => return err!(ErrorCode::InvalidOrderSide),
+    let fund_key = ctx.accounts.fund_state.key();
+    let (quote, swap_accounts) = if order.quote_mint == Pubkey::default() {
+        (None, ctx.remaining_accounts)
+    } else {
+        require!(
+            ctx.remaining_accounts.len() >= QUOTE_ACCOUNT_COUNT,
+            ErrorCode::InvalidRemainingAccounts
+        );
+        let (quote_accounts, swap_accounts) =
+            ctx.remaining_accounts.split_at(QUOTE_ACCOUNT_COUNT);
+        let quote = load_quote_leg(
+            &order.quote_mint,
+            &order.quote_price_feed,
+            &order.pyth_program_id,
+            &fund_key,
+            quote_accounts,
+        )?;
+        (Some(quote), swap_accounts)
+    };
+
+    let price_at_limit_expo = match quote.as_ref() {
+        Some(q) => cross_price(price.price, price.expo, q.price, q.expo, order.price_expo)?,
+        None => scale_price(price.price, price.expo, order.price_expo)?,
+    };
+    if order.order_type == ORDER_TYPE_TRAILING_STOP {
+        record_trailing_peak(order, price_at_limit_expo)?;
     }
+    let triggered_leg = triggered_leg(order, price_at_limit_expo)?;
 
     require!(!swap_data.is_empty(), ErrorCode::InvalidSwapProgram);
-    require!(!ctx.remaining_accounts.is_empty(), ErrorCode::InvalidSwapProgram);
+    require!(!swap_accounts.is_empty(), ErrorCode::InvalidSwapProgram);
     require!(
-        ctx.accounts.swap_program.key() == JUPITER_PROGRAM_ID,
+        ctx.accounts
+            .config
+            .is_swap_program_allowed(&ctx.accounts.swap_program.key()),
         ErrorCode::InvalidSwapProgram
     );
 
-    let fund_key = ctx.accounts.fund_state.key();
     let expected_fund_token_vault = anchor_spl::associated_token::get_associated_token_address(
         &fund_key,
         &order.mint,
@@ -85,54 +132,33 @@ pub fn execute_limit_order<'info>(
     require!(expected_fund_token_vault == ctx.accounts.fund_token_vault.key(), ErrorCode::InvalidTokenVault);
     require!(ctx.accounts.fund_token_vault.mint == order.mint, ErrorCode::InvalidTokenVault);
 
-    match order.side {
-        SIDE_BUY => {
-            let (expected_sol_vault, _bump) = Pubkey::find_program_address(
-                &[b"limit_order_sol_vault", order.key().as_ref()],
-                ctx.program_id,
-            );
-            require!(expected_sol_vault == ctx.accounts.order_sol_vault.key(), ErrorCode::InvalidOrderVault);
-        }
-        SIDE_SELL => {
-            let (vault_auth, _bump) = Pubkey::find_program_address(
-                &[b"limit_order_vault_auth", order.key().as_ref()],
-                ctx.program_id,
-            );
-            require!(vault_auth == ctx.accounts.order_vault_auth.key(), ErrorCode::InvalidOrderVault);
-            let expected_order_vault = anchor_spl::associated_token::get_associated_token_address(
-                &vault_auth,
-                &order.mint,
-            );
-            require!(expected_order_vault == ctx.accounts.order_token_vault.key(), ErrorCode::InvalidOrderVault);
-            require!(ctx.accounts.order_token_vault.mint == order.mint, ErrorCode::InvalidOrderVault);
-        }
-        _ => return err!(ErrorCode::InvalidOrderSide),
+    if order.escrows_sol() {
+        let (expected_sol_vault, _bump) = Pubkey::find_program_address(
+            &[b"limit_order_sol_vault", order.key().as_ref()],
+            ctx.program_id,
+        );
+        require!(expected_sol_vault == ctx.accounts.order_sol_vault.key(), ErrorCode::InvalidOrderVault);
     }
 
-    let mut fund_token_before = ctx.accounts.fund_token_vault.amount;
+    let output_account = match (order.side, quote.as_ref()) {
+        (SIDE_BUY, _) => ctx.accounts.fund_token_vault.key(),
+        (SIDE_SELL, Some(q)) => *q.fund_vault.key,
+        (SIDE_SELL, None) => ctx.accounts.fund_vault.key(),
+        _ => return err!(ErrorCode::InvalidOrderSide),
+    };
     let mut order_token_before = ctx.accounts.order_token_vault.amount;
-    let mut fund_sol_before = ctx.accounts.fund_vault.to_account_info().lamports();
 
     let mut has_order_token_vault = false;
     let mut has_output_account = false;
     let mut has_vault_auth = false;
-    for acc in ctx.remaining_accounts.iter() {
+    for acc in swap_accounts.iter() {
         if *acc.key == ctx.accounts.order_token_vault.key() && acc.is_writable {
             has_order_token_vault = true;
         }
         if *acc.key == ctx.accounts.order_vault_auth.key() {
             has_vault_auth = true;
         }
-        if order.side == SIDE_BUY
-            && *acc.key == ctx.accounts.fund_token_vault.key()
-            && acc.is_writable
-        {
-            has_output_account = true;
-        }
-        if order.side == SIDE_SELL
-            && *acc.key == ctx.accounts.fund_vault.key()
-            && acc.is_writable
-        {
+        if *acc.key == output_account && acc.is_writable {
             has_output_account = true;
         }
     }
@@ -140,8 +166,7 @@ pub fn execute_limit_order<'info>(
     require!(has_output_account, ErrorCode::InvalidTokenVault);
     require!(has_vault_auth, ErrorCode::InvalidOrderVault);
 
-    let cpi_accounts: Vec<AccountMeta> = ctx
-        .remaining_accounts
+    let cpi_accounts: Vec<AccountMeta> = swap_accounts
         .iter()
         .map(|acc| AccountMeta {
             pubkey: *acc.key,
@@ -150,8 +175,8 @@ pub fn execute_limit_order<'info>(
         })
         .collect();
 
-    let mut infos: Vec<AccountInfo> = Vec::with_capacity(ctx.remaining_accounts.len());
-    for acc in ctx.remaining_accounts.iter() {
+    let mut infos: Vec<AccountInfo> = Vec::with_capacity(swap_accounts.len());
+    for acc in swap_accounts.iter() {
         infos.push(acc.clone());
     }
 
@@ -164,47 +189,21 @@ pub fn execute_limit_order<'info>(
         ErrorCode::InvalidOrderVault
     );
 
-    match order.side {
-        SIDE_BUY => {
-            require!(
-                ctx.accounts.order_token_vault.mint == native_mint::ID,
-                ErrorCode::InvalidOrderVault
-            );
-            require!(
-                ctx.accounts.order_token_vault.owner == ctx.accounts.order_vault_auth.key(),
-                ErrorCode::InvalidOrderVault
-            );
-            let expected_wsol_vault =
-                anchor_spl::associated_token::get_associated_token_address(
-                    &vault_auth,
-                    &native_mint::ID,
-                );
-            require!(
-                expected_wsol_vault == ctx.accounts.order_token_vault.key(),
-                ErrorCode::InvalidOrderVault
-            );
-        }
-        SIDE_SELL => {
-            require!(
-                ctx.accounts.order_token_vault.mint == order.mint,
-                ErrorCode::InvalidOrderVault
-            );
-            require!(
-                ctx.accounts.order_token_vault.owner == ctx.accounts.order_vault_auth.key(),
-                ErrorCode::InvalidOrderVault
-            );
-            let expected_order_vault =
-                anchor_spl::associated_token::get_associated_token_address(
-                    &vault_auth,
-                    &order.mint,
-                );
-            require!(
-                expected_order_vault == ctx.accounts.order_token_vault.key(),
-                ErrorCode::InvalidOrderVault
-            );
-        }
-        _ => return err!(ErrorCode::InvalidOrderSide),
-    }
+    let escrow_mint = order.escrow_mint();
+    require!(
+        ctx.accounts.order_token_vault.mint == escrow_mint,
+        ErrorCode::InvalidOrderVault
+    );
+    require!(
+        ctx.accounts.order_token_vault.owner == ctx.accounts.order_vault_auth.key(),
+        ErrorCode::InvalidOrderVault
+    );
+    let expected_order_vault =
+        anchor_spl::associated_token::get_associated_token_address(&vault_auth, &escrow_mint);
+    require!(
+        expected_order_vault == ctx.accounts.order_token_vault.key(),
+        ErrorCode::InvalidOrderVault
+    );
     let order_key = order.key();
     let signer_seeds: &[&[u8]] = &[
         b"limit_order_vault_auth",
@@ -213,7 +212,7 @@ pub fn execute_limit_order<'info>(
     ];
     let signer_seeds_set = [signer_seeds];
 
-    if order.side == SIDE_BUY {
+    if order.escrows_sol() {
         let (sol_vault, sol_vault_bump) = Pubkey::find_program_address(
             &[b"limit_order_sol_vault", order_key.as_ref()],
             ctx.program_id,
@@ -225,7 +224,7 @@ pub fn execute_limit_order<'info>(
         );
 
         let sol_balance = ctx.accounts.order_sol_vault.to_account_info().lamports();
-        require!(sol_balance >= order.amount_in, ErrorCode::InsufficientLiquidity);
+        require!(sol_balance >= fill_amount, ErrorCode::InsufficientLiquidity);
 
         let sol_vault_seeds: &[&[u8]] = &[
             b"limit_order_sol_vault",
@@ -241,7 +240,7 @@ pub fn execute_limit_order<'info>(
             },
             &sol_vault_signer,
         );
-        anchor_lang::system_program::transfer(transfer_ctx, order.amount_in)?;
+        anchor_lang::system_program::transfer(transfer_ctx, fill_amount)?;
 
         let sync_ctx = CpiContext::new(
             ctx.accounts.token_program.to_account_info(),
@@ -253,9 +252,13 @@ pub fn execute_limit_order<'info>(
 
         ctx.accounts.order_token_vault.reload()?;
         order_token_before = ctx.accounts.order_token_vault.amount;
-        fund_token_before = ctx.accounts.fund_token_vault.amount;
-        fund_sol_before = ctx.accounts.fund_vault.to_account_info().lamports();
     }
+    let out_before = output_amount(
+        order.side,
+        quote.as_ref(),
+        &ctx.accounts.fund_token_vault,
+        &ctx.accounts.fund_vault.to_account_info(),
+    )?;
 
     invoke_signed(
         &Instruction {
@@ -269,76 +272,206 @@ pub fn execute_limit_order<'info>(
 
     ctx.accounts.fund_token_vault.reload()?;
     ctx.accounts.order_token_vault.reload()?;
-    let fund_token_after = ctx.accounts.fund_token_vault.amount;
     let order_token_after = ctx.accounts.order_token_vault.amount;
-    let fund_sol_after = ctx.accounts.fund_vault.to_account_info().lamports();
+    let out_after = output_amount(
+        order.side,
+        quote.as_ref(),
+        &ctx.accounts.fund_token_vault,
+        &ctx.accounts.fund_vault.to_account_info(),
+    )?;
+    let filled_out = out_after
+        .checked_sub(out_before)
+        .ok_or(ErrorCode::MathOverflow)?;
 
-    match order.side {
-        SIDE_BUY => {
-            let token_delta = fund_token_after
-                .checked_sub(fund_token_before)
-                .ok_or(ErrorCode::MathOverflow)?;
-            let expected_out = expected_token_out_from_sol(
-                order.amount_in,
-                ctx.accounts.whitelist.decimals,
-                price.price,
-                price.expo,
-                sol_price.price,
-                sol_price.expo,
-            )?;
-            let min_expected = apply_max_slippage(
-                expected_out,
-                ctx.accounts.config.max_slippage_bps,
-            )?;
-            require!(token_delta >= min_expected, ErrorCode::InvalidTokenVault);
-            require!(token_delta >= order.min_out, ErrorCode::InvalidTokenVault);
-            require!(order_token_before == order.amount_in, ErrorCode::InvalidOrderVault);
-            require!(order_token_after == 0, ErrorCode::InvalidOrderVault);
-
-            let close_ctx = CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                CloseAccount {
-                    account: ctx.accounts.order_token_vault.to_account_info(),
-                    destination: ctx.accounts.fund_vault.to_account_info(),
-                    authority: ctx.accounts.order_vault_auth.to_account_info(),
-                },
-                &signer_seeds_set,
-            );
-            token::close_account(close_ctx)?;
-        }
-        SIDE_SELL => {
-            let sol_delta = fund_sol_after
-                .checked_sub(fund_sol_before)
-                .ok_or(ErrorCode::MathOverflow)?;
-            let expected_out = expected_sol_out_from_token(
-                order.amount_in,
-                ctx.accounts.whitelist.decimals,
-                price.price,
-                price.expo,
-                sol_price.price,
-                sol_price.expo,
-            )?;
-            let min_expected = apply_max_slippage(
-                expected_out,
-                ctx.accounts.config.max_slippage_bps,
-            )?;
-            require!(sol_delta >= min_expected, ErrorCode::InvalidTokenVault);
-            require!(sol_delta >= order.min_out, ErrorCode::InvalidTokenVault);
-            require!(order_token_before == order.amount_in, ErrorCode::InvalidOrderVault);
-            require!(order_token_after == 0, ErrorCode::InvalidOrderVault);
-
-            let close_ctx = CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                CloseAccount {
-                    account: ctx.accounts.order_token_vault.to_account_info(),
-                    destination: ctx.accounts.fund_vault.to_account_info(),
-                    authority: ctx.accounts.order_vault_auth.to_account_info(),
-                },
-                &signer_seeds_set,
-            );
-            token::close_account(close_ctx)?;
-        }
+    let decimals = ctx.accounts.whitelist.decimals;
+    let expected_out = match (order.side, quote.as_ref()) {
+        (SIDE_BUY, Some(q)) => expected_token_out_from_token(
+            fill_amount,
+            q.decimals,
+            q.price,
+            q.expo,
+            decimals,
+            price.price,
+            price.expo,
+        )?,
+        (SIDE_SELL, Some(q)) => expected_token_out_from_token(
+            fill_amount,
+            decimals,
+            price.price,
+            price.expo,
+            q.decimals,
+            q.price,
+            q.expo,
+        )?,
+        (SIDE_BUY, None) => expected_token_out_from_sol(
+            fill_amount,
+            decimals,
+            price.price,
+            price.expo,
+            sol_price.price,
+            sol_price.expo,
+        )?,
+        (SIDE_SELL, None) => expected_sol_out_from_token(
+            fill_amount,
+            decimals,
+            price.price,
+            price.expo,
+            sol_price.price,
+            sol_price.expo,
+        )?,
         _ => return err!(ErrorCode::InvalidOrderSide),
+    };
+    let min_expected = apply_max_slippage(expected_out, ctx.accounts.config.max_slippage_bps)?;
+    require!(filled_out >= min_expected, ErrorCode::InvalidTokenVault);
+    require!(filled_out >= fill_min_out, ErrorCode::InvalidTokenVault);
+
+    if order.escrows_sol() {
+        require!(order_token_before == fill_amount, ErrorCode::InvalidOrderVault);
+        require!(order_token_after == 0, ErrorCode::InvalidOrderVault);
+    } else {
+        require!(order_token_before == remaining_in, ErrorCode::InvalidOrderVault);
+        let expected_left = remaining_in
+            .checked_sub(fill_amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+        require!(order_token_after == expected_left, ErrorCode::InvalidOrderVault);
+    }
+    let remaining_after = remaining_in
+        .checked_sub(fill_amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+    match (order.side, quote.as_ref()) {
+        (SIDE_BUY, Some(q)) => {
+            track_quote_escrow(ctx.program_id, q.whitelist, remaining_in, remaining_after)?
+        }
+        (SIDE_BUY, None) => ctx
+            .accounts
+            .fund_state
+            .track_escrow(remaining_in, remaining_after)?,
+        _ => ctx
+            .accounts
+            .whitelist
+            .track_escrow(remaining_in, remaining_after)?,
+    }
+    ctx.accounts.fund_state.mark_holdings_changed()?;
+    let notional_lamports = match (order.side, quote.as_ref()) {
+        (SIDE_BUY, None) => fill_amount,
+        (SIDE_BUY, Some(q)) => expected_sol_out_from_token(
+            fill_amount,
+            q.decimals,
+            q.price,
+            q.expo,
+            sol_price.price,
+            sol_price.expo,
+        )?,
+        _ => expected_sol_out_from_token(
+            fill_amount,
+            decimals,
+            price.price,
+            price.expo,
+            sol_price.price,
+            sol_price.expo,
+        )?,
+    };
+    let tip_lamports = ctx.accounts.fund_state.keeper_tip(
+        notional_lamports,
+        ctx.accounts.config.max_keeper_tip_lamports,
+        fill_amount,
+        order.amount_in,
+        registered_keeper,
+    )?;
+    let tip_paid = pay_keeper_tip(
+        fund_key,
+        &ctx.accounts.fund_vault.to_account_info(),
+        &ctx.accounts.executor.to_account_info(),
+        notional_lamports,
+        tip_lamports,
+    )?;
+    let oracle_conf_bps = confidence_bps(&price)?
+        .checked_add(match quote.as_ref() {
+            Some(q) => q.conf_bps,
+            None => confidence_bps(&sol_price)?,
+        })
+        .ok_or(ErrorCode::MathOverflow)?;
+    let improvement_floor = apply_confidence_premium(expected_out, oracle_conf_bps)?;
+    let surplus = filled_out.saturating_sub(improvement_floor);
+    let surplus_lamports = match (order.side, quote.as_ref()) {
+        _ if surplus == 0 || !ctx.accounts.fund_state.permissionless_execution => 0,
+        (SIDE_SELL, None) => surplus,
+        (SIDE_SELL, Some(q)) => expected_sol_out_from_token(
+            surplus,
+            q.decimals,
+            q.price,
+            q.expo,
+            sol_price.price,
+            sol_price.expo,
+        )?,
+        _ => expected_sol_out_from_token(
+            surplus,
+            decimals,
+            price.price,
+            price.expo,
+            sol_price.price,
+            sol_price.expo,
+        )?,
+    };
+    let payout_cap = pro_rata(
+        ctx.accounts.config.max_keeper_tip_lamports,
+        fill_amount,
+        order.amount_in,
+    )?;
+    let share_lamports = ctx
+        .accounts
+        .fund_state
+        .executor_share(surplus_lamports, payout_cap.saturating_sub(tip_paid))?;
+    pay_price_improvement(
+        fund_key,
+        &ctx.accounts.fund_vault.to_account_info(),
+        &ctx.accounts.executor.to_account_info(),
+        improvement_floor,
+        filled_out,
+        surplus_lamports,
+        share_lamports,
+    )?;
+
+    order.active_leg = triggered_leg;
+    order.filled_in = order
+        .filled_in
+        .checked_add(fill_amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+    order.filled_out = order
+        .filled_out
+        .checked_add(filled_out)
+        .ok_or(ErrorCode::MathOverflow)?;
+    if order.filled_in < order.amount_in {
+        return Ok(());
+    }
+
+    let close_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.order_token_vault.to_account_info(),
+            destination: ctx.accounts.manager.to_account_info(),
+            authority: ctx.accounts.order_vault_auth.to_account_info(),
+        },
+        &signer_seeds_set,
+    );
+    token::close_account(close_ctx)?;
+    if order.escrows_sol() {
+        let (_, sol_vault_bump) = Pubkey::find_program_address(
+            &[b"limit_order_sol_vault", order_key.as_ref()],
+            ctx.program_id,
+        );
+        let sol_vault_seeds: &[&[u8]] = &[
+            b"limit_order_sol_vault",
+            order_key.as_ref(),
+            &[sol_vault_bump],
+        ];
+        drain_order_sol_vault(
+            &ctx.accounts.order_sol_vault.to_account_info(),
+            &ctx.accounts.fund_vault.to_account_info(),
+            &ctx.accounts.manager.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            sol_vault_seeds,
+        )?;
     }
 
     order.status = ORDER_STATUS_EXECUTED;
@@ -348,149 +481,160 @@ pub fn execute_limit_order<'info>(
         .active_limit_count
         .checked_sub(1)
         .ok_or(ErrorCode::MathOverflow)?;
+    emit!(LimitOrderClosed::new(order_key, order, Clock::get()?.unix_timestamp));
 
-    Ok(())
+    ctx.accounts
+        .order
+        .close(ctx.accounts.manager.to_account_info())
 }
 
-struct PythPrice {
-    price: i64,
-    expo: i32,
+fn output_amount(
+    side: u8,
+    quote: Option<&QuoteLeg>,
+    fund_token_vault: &TokenAccount,
+    fund_vault: &AccountInfo,
+) -> Result<u64> {
+    match (side, quote) {
+        (SIDE_BUY, _) => Ok(fund_token_vault.amount),
+        (SIDE_SELL, Some(q)) => read_token_amount(q.fund_vault),
+        (SIDE_SELL, None) => Ok(fund_vault.lamports()),
+        _ => err!(ErrorCode::InvalidOrderSide),
+    }
 }
 
-fn load_pyth_price(price_info: &AccountInfo) -> Result<PythPrice> {
-    let feed = SolanaPriceAccount::account_info_to_feed(price_info)
-        .map_err(|_| ErrorCode::InvalidOracle)?;
-    let price = feed
-        .get_price_no_older_than(Clock::get()?.unix_timestamp, ORACLE_MAX_AGE_SECS)
-        .ok_or(ErrorCode::StaleOracle)?;
-    require!(price.price > 0, ErrorCode::InvalidOracle);
-    let price_u128 = price.price as u128;
-    let max_conf = price_u128
-        .checked_mul(MAX_CONF_BPS as u128)
-        .ok_or(ErrorCode::MathOverflow)?
-        .checked_div(10_000)
-        .ok_or(ErrorCode::MathOverflow)? as u64;
-    require!(price.conf <= max_conf, ErrorCode::InvalidOracleConfidence);
-    Ok(PythPrice {
-        price: price.price,
-        expo: price.expo,
-    })
+fn triggered_leg(order: &LimitOrder, price: i128) -> Result<u8> {
+    let limit_hit = match order.side {
+        SIDE_BUY => price <= order.limit_price as i128,
+        SIDE_SELL => price >= order.limit_price as i128,
+        _ => return err!(ErrorCode::InvalidOrderSide),
+    };
+    let stop_hit = match order.side {
+        SIDE_BUY => price >= order.stop_price as i128,
+        SIDE_SELL => price <= order.stop_price as i128,
+        _ => return err!(ErrorCode::InvalidOrderSide),
+    };
+    let leg = match order.order_type {
+        ORDER_TYPE_LIMIT if limit_hit => ORDER_LEG_LIMIT,
+        ORDER_TYPE_STOP if stop_hit => ORDER_LEG_STOP,
+        ORDER_TYPE_BRACKET if limit_hit && order.active_leg != ORDER_LEG_STOP => ORDER_LEG_LIMIT,
+        ORDER_TYPE_BRACKET if stop_hit && order.active_leg != ORDER_LEG_LIMIT => ORDER_LEG_STOP,
+        ORDER_TYPE_TRAILING_STOP if trailing_stop_hit(order, price)? => ORDER_LEG_STOP,
+        ORDER_TYPE_LIMIT | ORDER_TYPE_STOP | ORDER_TYPE_BRACKET | ORDER_TYPE_TRAILING_STOP => {
+            return err!(ErrorCode::OrderNotTriggered)
+        }
+        _ => return err!(ErrorCode::InvalidOrderType),
+    };
+    Ok(leg)
 }
 
-fn scale_price(price: i64, expo: i32, target_expo: i32) -> Result<i128> {
-    let mut value = price as i128;
-    if expo == target_expo {
-        return Ok(value);
-    }
-    if expo > target_expo {
-        let scale = pow10_i128((expo - target_expo) as u32)?;
-        value = value.checked_mul(scale).ok_or(ErrorCode::MathOverflow)?;
-    } else {
-        let scale = pow10_i128((target_expo - expo) as u32)?;
-        value = value.checked_div(scale).ok_or(ErrorCode::MathOverflow)?;
+fn trailing_stop_hit(order: &LimitOrder, price: i128) -> Result<bool> {
+    let scaled_price = price.checked_mul(10_000).ok_or(ErrorCode::MathOverflow)?;
+    let peak = order.peak_price as i128;
+    match order.side {
+        SIDE_SELL => {
+            let factor = 10_000i128 - order.trail_bps as i128;
+            let trigger = peak.checked_mul(factor).ok_or(ErrorCode::MathOverflow)?;
+            Ok(scaled_price <= trigger)
+        }
+        SIDE_BUY => {
+            let factor = 10_000i128 + order.trail_bps as i128;
+            let trigger = peak.checked_mul(factor).ok_or(ErrorCode::MathOverflow)?;
+            Ok(scaled_price >= trigger)
+        }
+        _ => err!(ErrorCode::InvalidOrderSide),
     }
-    Ok(value)
 }
 
-fn pow10_i128(exp: u32) -> Result<i128> {
-    let mut value: i128 = 1;
-    for _ in 0..exp {
-        value = value.checked_mul(10).ok_or(ErrorCode::MathOverflow)?;
+pub(crate) fn record_trailing_peak(order: &mut LimitOrder, price: i128) -> Result<()> {
+    let improves = match order.side {
+        SIDE_SELL => price > order.peak_price as i128,
+        SIDE_BUY => price < order.peak_price as i128,
+        _ => return err!(ErrorCode::InvalidOrderSide),
+    };
+    if improves {
+        order.peak_price = i64::try_from(price).map_err(|_| ErrorCode::MathOverflow)?;
     }
-    Ok(value)
+    Ok(())
 }
 
-fn apply_max_slippage(expected_out: u64, max_slippage_bps: u16) -> Result<u64> {
-    require!(max_slippage_bps <= 10_000, ErrorCode::InvalidFeeBps);
-    let expected = expected_out as u128;
-    let factor = 10_000u128
-        .checked_sub(max_slippage_bps as u128)
-        .ok_or(ErrorCode::MathOverflow)?;
-    let min_expected = expected
-        .checked_mul(factor)
+fn min_fill_amount(amount_in: u64) -> Result<u64> {
+    let scaled = (amount_in as u128)
+        .checked_mul(MIN_FILL_BPS as u128)
+        .ok_or(ErrorCode::MathOverflow)?
+        .checked_add(9_999)
         .ok_or(ErrorCode::MathOverflow)?
-        .checked_div(10_000u128)
+        .checked_div(10_000)
         .ok_or(ErrorCode::MathOverflow)?;
-    Ok(min_expected as u64)
+    Ok(scaled as u64)
 }
 
-fn expected_sol_out_from_token(
-    amount: u64,
-    decimals: u8,
-    token_price: i64,
-    token_expo: i32,
-    sol_price: i64,
-    sol_expo: i32,
-) -> Result<u64> {
-    require!(token_price > 0, ErrorCode::InvalidOracle);
-    require!(sol_price > 0, ErrorCode::InvalidOracle);
-    let mut numerator = (amount as i128)
-        .checked_mul(token_price as i128)
-        .ok_or(ErrorCode::MathOverflow)?
-        .checked_mul(LAMPORTS_PER_SOL_U64 as i128)
+pub(crate) fn pro_rata_min_out(min_out: u64, fill_amount: u64, amount_in: u64) -> Result<u64> {
+    let numerator = (min_out as u128)
+        .checked_mul(fill_amount as u128)
         .ok_or(ErrorCode::MathOverflow)?;
-    let mut denominator = sol_price as i128;
-
-    let exp = token_expo
-        .checked_sub(decimals as i32)
-        .and_then(|v| v.checked_sub(sol_expo))
+    let denominator = amount_in as u128;
+    require!(denominator > 0, ErrorCode::MathOverflow);
+    let scaled = numerator
+        .checked_add(denominator - 1)
+        .ok_or(ErrorCode::MathOverflow)?
+        .checked_div(denominator)
         .ok_or(ErrorCode::MathOverflow)?;
+    Ok(scaled as u64)
+}
 
-    if exp >= 0 {
-        let scale = pow10_i128(exp as u32)?;
-        numerator = numerator
-            .checked_mul(scale)
-            .ok_or(ErrorCode::MathOverflow)?;
-    } else {
-        let scale = pow10_i128((-exp) as u32)?;
-        denominator = denominator
-            .checked_mul(scale)
-            .ok_or(ErrorCode::MathOverflow)?;
-    }
-    require!(denominator > 0, ErrorCode::MathOverflow);
-    Ok((numerator / denominator) as u64)
+pub(crate) struct QuoteLeg<'info> {
+    pub(crate) whitelist: &'info AccountInfo<'info>,
+    pub(crate) decimals: u8,
+    pub(crate) price: i64,
+    pub(crate) expo: i32,
+    pub(crate) conf_bps: u64,
+    pub(crate) fund_vault: &'info AccountInfo<'info>,
 }
 
-fn expected_token_out_from_sol(
-    sol_lamports: u64,
-    decimals: u8,
-    token_price: i64,
-    token_expo: i32,
-    sol_price: i64,
-    sol_expo: i32,
-) -> Result<u64> {
-    require!(token_price > 0, ErrorCode::InvalidOracle);
-    require!(sol_price > 0, ErrorCode::InvalidOracle);
-    let mut numerator = (sol_lamports as i128)
-        .checked_mul(sol_price as i128)
-        .ok_or(ErrorCode::MathOverflow)?
-        .checked_mul(pow10_i128(decimals as u32)?)
-        .ok_or(ErrorCode::MathOverflow)?;
-    let mut denominator = (token_price as i128)
-        .checked_mul(LAMPORTS_PER_SOL_U64 as i128)
-        .ok_or(ErrorCode::MathOverflow)?;
+pub(crate) fn load_quote_leg<'info>(
+    quote_mint: &Pubkey,
+    quote_price_feed: &Pubkey,
+    pyth_program_id: &Pubkey,
+    fund_key: &Pubkey,
+    accounts: &'info [AccountInfo<'info>],
+) -> Result<QuoteLeg<'info>> {
+    let whitelist: Account<FundWhitelist> =
+        Account::try_from(&accounts[0]).map_err(|_| ErrorCode::InvalidTokenVault)?;
+    require!(whitelist.enabled, ErrorCode::InvalidTokenVault);
+    require!(whitelist.fund == *fund_key, ErrorCode::InvalidTokenVault);
+    require!(whitelist.mint == *quote_mint, ErrorCode::InvalidTokenVault);
+    require!(whitelist.pyth_feed == *quote_price_feed, ErrorCode::InvalidOracle);
+
+    let price_info = &accounts[1];
+    require!(price_info.key == quote_price_feed, ErrorCode::InvalidOracle);
+    require!(price_info.owner == pyth_program_id, ErrorCode::InvalidOracle);
+    let price = load_pyth_price(price_info)?;
 
-    let exp = sol_expo
-        .checked_sub(token_expo)
-        .ok_or(ErrorCode::MathOverflow)?;
-    if exp >= 0 {
-        let scale = pow10_i128(exp as u32)?;
-        numerator = numerator
-            .checked_mul(scale)
-            .ok_or(ErrorCode::MathOverflow)?;
-    } else {
-        let scale = pow10_i128((-exp) as u32)?;
-        denominator = denominator
-            .checked_mul(scale)
-            .ok_or(ErrorCode::MathOverflow)?;
-    }
-    require!(denominator > 0, ErrorCode::MathOverflow);
-    Ok((numerator / denominator) as u64)
+    let expected_fund_vault =
+        anchor_spl::associated_token::get_associated_token_address(fund_key, quote_mint);
+    require!(
+        expected_fund_vault == *accounts[2].key,
+        ErrorCode::InvalidTokenVault
+    );
+    let fund_vault: Account<TokenAccount> =
+        Account::try_from(&accounts[2]).map_err(|_| ErrorCode::InvalidTokenVault)?;
+    require!(fund_vault.owner == *fund_key, ErrorCode::InvalidTokenVault);
+    require!(fund_vault.mint == *quote_mint, ErrorCode::InvalidTokenVault);
+
+    Ok(QuoteLeg {
+        whitelist: &accounts[0],
+        decimals: whitelist.decimals,
+        price: price.price,
+        expo: price.expo,
+        conf_bps: confidence_bps(&price)?,
+        fund_vault: &accounts[2],
+    })
 }
 
 #[derive(Accounts)]
 #[instruction(order_id: u64)]
 pub struct ExecuteLimitOrder<'info> {
+    #[account(mut)]
     pub executor: Signer<'info>,
     #[account(
         seeds = [b"config", config.config_id.to_le_bytes().as_ref()],
@@ -499,17 +643,21 @@ pub struct ExecuteLimitOrder<'info> {
     pub config: Account<'info, GlobalConfig>,
     #[account(
         mut,
-        seeds = [b"fund", config.key().as_ref(), fund_state.manager.as_ref(), fund_state.fund_id.to_le_bytes().as_ref()],
+        seeds = [b"fund", config.key().as_ref(), fund_state.creator.as_ref(), fund_state.fund_id.to_le_bytes().as_ref()],
         bump = fund_state.bump,
         has_one = config
     )]
     pub fund_state: Account<'info, FundState>,
+    /// CHECK: fund manager, receives the order rent
+    #[account(mut, address = fund_state.manager)]
+    pub manager: UncheckedAccount<'info>,
     #[account(
         mut,
         seeds = [b"vault", fund_state.key().as_ref()],
         bump = fund_state.vault_bump
     )]
     pub fund_vault: Account<'info, FundVault>,
+    #[account(mut)]
     pub whitelist: Account<'info, FundWhitelist>,
     #[account(mut)]
     pub fund_token_vault: Account<'info, TokenAccount>,
@@ -530,8 +678,13 @@ pub struct ExecuteLimitOrder<'info> {
     pub price_feed: AccountInfo<'info>,
     /// CHECK: Pyth SOL/USD price feed
     pub sol_price_feed: AccountInfo<'info>,
-    /// CHECK: swap program id
+    /// CHECK: swap program id, checked against the config registry
     pub swap_program: AccountInfo<'info>,
+    #[account(
+        seeds = [b"keeper", config.key().as_ref(), executor.key().as_ref()],
+        bump = keeper_record.bump
+    )]
+    pub keeper_record: Option<Account<'info, KeeperRecord>>,
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
 }
@@ -0,0 +1,405 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_spl::token::{self, Token};
+use anchor_spl::token::spl_token::native_mint;
+
+use crate::errors::ErrorCode;
+use crate::instructions::rebalance_strategy::{abs_i128, read_token_amount, target_value_for_weight};
+use crate::instructions::register_keeper::require_keeper;
+use crate::oracle::{
+    apply_max_slippage, expected_sol_out_from_token, expected_token_out_from_sol,
+    load_pyth_price, PythPrice,
+};
+use crate::state::fund::{FundState, FundVault, FUND_TYPE_STRATEGY};
+use crate::state::global_config::GlobalConfig;
+use crate::state::keeper_record::{KeeperRecord, KEEPER_SCOPE_REBALANCE};
+use crate::state::rebalance_session::{
+    RebalanceSession, REBALANCE_SESSION_MAX_SECS, SESSION_PHASE_TRADING,
+};
+use crate::state::strategy::Strategy;
+use crate::state::whitelist::FundWhitelist;
+
+struct LegSide {
+    decimals: u8,
+    price: Option<PythPrice>,
+    target_lamports: i128,
+}
+
+pub fn execute_rebalance_leg<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ExecuteRebalanceLeg<'info>>,
+    input_mint: Pubkey,
+    output_mint: Pubkey,
+    amount_in: u64,
+    min_out: u64,
+    swap_data: Vec<u8>,
+) -> Result<()> {
+    require_keeper(
+        &ctx.accounts.config,
+        ctx.accounts.keeper_record.as_ref(),
+        &ctx.accounts.executor.key(),
+        &ctx.accounts.fund_state.key(),
+        KEEPER_SCOPE_REBALANCE,
+    )?;
+    require!(
+        ctx.accounts.fund_state.fund_type == FUND_TYPE_STRATEGY,
+        ErrorCode::InvalidFundType
+    );
+    require!(!ctx.accounts.fund_state.paused, ErrorCode::FundPaused);
+    require!(amount_in > 0, ErrorCode::InvalidRebalanceLeg);
+    require!(min_out > 0, ErrorCode::InvalidMinOut);
+    require!(input_mint != output_mint, ErrorCode::InvalidRebalanceLeg);
+
+    let now = Clock::get()?.unix_timestamp;
+    let session_deadline = ctx
+        .accounts
+        .session
+        .opened_ts
+        .checked_add(REBALANCE_SESSION_MAX_SECS)
+        .ok_or(ErrorCode::MathOverflow)?;
+    require!(now <= session_deadline, ErrorCode::RebalanceSessionExpired);
+    require!(
+        ctx.accounts.session.phase == SESSION_PHASE_TRADING,
+        ErrorCode::InvalidSessionPhase
+    );
+
+    require!(!swap_data.is_empty(), ErrorCode::InvalidSwapProgram);
+    require!(
+        ctx.accounts
+            .config
+            .is_swap_program_allowed(&ctx.accounts.swap_program.key()),
+        ErrorCode::InvalidSwapProgram
+    );
+
+    let strategy = &ctx.accounts.strategy;
+    require!(strategy.fund == ctx.accounts.fund_state.key(), ErrorCode::InvalidStrategy);
+
+    let input_is_sol = input_mint == native_mint::ID;
+    let output_is_sol = output_mint == native_mint::ID;
+    let validation_len = 2 * (usize::from(!input_is_sol) + usize::from(!output_is_sol));
+    require!(
+        ctx.remaining_accounts.len() > validation_len,
+        ErrorCode::InvalidRemainingAccounts
+    );
+    let (validation_accounts, cpi_account_infos) =
+        ctx.remaining_accounts.split_at(validation_len);
+
+    let sol_price_info = &ctx.accounts.sol_price_feed;
+    require!(
+        sol_price_info.key == &ctx.accounts.config.sol_usd_pyth_feed,
+        ErrorCode::InvalidOracle
+    );
+    require!(
+        sol_price_info.owner == &ctx.accounts.config.pyth_program_id,
+        ErrorCode::InvalidOracle
+    );
+    let sol_price = load_pyth_price(sol_price_info)?;
+
+    let fund_key = ctx.accounts.fund_state.key();
+    let session = &ctx.accounts.session;
+    let session_nav = session.nav_lamports as i128;
+    let mut validation_iter = validation_accounts.iter();
+    let mut load_side = |mint: Pubkey, is_sol: bool| -> Result<LegSide> {
+        if is_sol {
+            return Ok(LegSide {
+                decimals: 9,
+                price: None,
+                target_lamports: target_value_for_weight(session_nav, session.sol_weight_bps)?,
+            });
+        }
+        let entry = session
+            .entries
+            .iter()
+            .find(|entry| entry.mint == mint)
+            .ok_or(ErrorCode::InvalidStrategy)?;
+        let wl_info = validation_iter.next().ok_or(ErrorCode::InvalidRemainingAccounts)?;
+        let price_info = validation_iter.next().ok_or(ErrorCode::InvalidRemainingAccounts)?;
+        let whitelist: Account<FundWhitelist> =
+            Account::try_from(wl_info).map_err(|_| ErrorCode::InvalidTokenVault)?;
+        require!(whitelist.enabled, ErrorCode::InvalidTokenVault);
+        require!(whitelist.fund == fund_key, ErrorCode::InvalidTokenVault);
+        require!(whitelist.mint == mint, ErrorCode::InvalidTokenVault);
+        require!(whitelist.pyth_feed == *price_info.key, ErrorCode::InvalidOracle);
+        require!(
+            price_info.owner == &ctx.accounts.config.pyth_program_id,
+            ErrorCode::InvalidOracle
+        );
+        Ok(LegSide {
+            decimals: whitelist.decimals,
+            price: Some(load_pyth_price(price_info)?),
+            target_lamports: target_value_for_weight(session_nav, entry.weight_bps)?,
+        })
+    };
+    let input_side = load_side(input_mint, input_is_sol)?;
+    let output_side = load_side(output_mint, output_is_sol)?;
+
+    let expected_input_vault =
+        anchor_spl::associated_token::get_associated_token_address(&fund_key, &input_mint);
+    require!(
+        expected_input_vault == ctx.accounts.input_token_vault.key(),
+        ErrorCode::InvalidTokenVault
+    );
+    let expected_output_vault =
+        anchor_spl::associated_token::get_associated_token_address(&fund_key, &output_mint);
+    require!(
+        expected_output_vault == ctx.accounts.output_token_vault.key(),
+        ErrorCode::InvalidTokenVault
+    );
+
+    let mut has_input_vault = false;
+    let mut has_output_vault = false;
+    let mut has_fund_state = false;
+    for acc in cpi_account_infos.iter() {
+        if *acc.key == ctx.accounts.input_token_vault.key() && acc.is_writable {
+            has_input_vault = true;
+        }
+        if *acc.key == ctx.accounts.output_token_vault.key() && acc.is_writable {
+            has_output_vault = true;
+        }
+        if output_is_sol && *acc.key == ctx.accounts.fund_vault.key() && acc.is_writable {
+            has_output_vault = true;
+        }
+        if *acc.key == fund_key {
+            has_fund_state = true;
+        }
+    }
+    require!(has_input_vault, ErrorCode::InvalidTokenVault);
+    require!(has_output_vault, ErrorCode::InvalidTokenVault);
+    require!(has_fund_state, ErrorCode::InvalidSwapProgram);
+
+    let fund_vault_info = ctx.accounts.fund_vault.to_account_info();
+    let input_vault_info = ctx.accounts.input_token_vault.to_account_info();
+    let output_vault_info = ctx.accounts.output_token_vault.to_account_info();
+    if input_is_sol {
+        require!(!input_vault_info.data_is_empty(), ErrorCode::InvalidOrderVault);
+    }
+    if output_is_sol {
+        require!(!output_vault_info.data_is_empty(), ErrorCode::InvalidOrderVault);
+    }
+    let input_holding_before = side_balance(&fund_vault_info, &input_vault_info, input_is_sol)?;
+    let output_before = side_balance(&fund_vault_info, &output_vault_info, output_is_sol)?;
+
+    if input_is_sol {
+        let reserve_lamports = target_value_for_weight(
+            ctx.accounts.session.nav_lamports as i128,
+            strategy.liquidity_reserve_bps,
+        )?;
+        let remaining_lamports = (input_holding_before as i128)
+            .checked_sub(amount_in as i128)
+            .ok_or(ErrorCode::MathOverflow)?;
+        require!(
+            remaining_lamports >= reserve_lamports,
+            ErrorCode::InsufficientLiquidity
+        );
+
+        {
+            let fund_vault_info = ctx.accounts.fund_vault.to_account_info();
+            let wsol_info = ctx.accounts.input_token_vault.to_account_info();
+            let mut fund_lamports = fund_vault_info.try_borrow_mut_lamports()?;
+            let mut wsol_lamports = wsol_info.try_borrow_mut_lamports()?;
+            **fund_lamports = (**fund_lamports)
+                .checked_sub(amount_in)
+                .ok_or(ErrorCode::InsufficientLiquidity)?;
+            **wsol_lamports = (**wsol_lamports)
+                .checked_add(amount_in)
+                .ok_or(ErrorCode::MathOverflow)?;
+        }
+
+        let sync_ctx = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token::SyncNative {
+                account: ctx.accounts.input_token_vault.to_account_info(),
+            },
+        );
+        token::sync_native(sync_ctx)?;
+    }
+
+    let input_before = vault_amount(&input_vault_info)?;
+    require!(input_before >= amount_in, ErrorCode::InsufficientLiquidity);
+
+    let config_key = ctx.accounts.config.key();
+    let fund_id_bytes = ctx.accounts.fund_state.fund_id.to_le_bytes();
+    let signer_seeds: &[&[u8]] = &[
+        b"fund",
+        config_key.as_ref(),
+        ctx.accounts.fund_state.creator.as_ref(),
+        fund_id_bytes.as_ref(),
+        &[ctx.accounts.fund_state.bump],
+    ];
+    let signer_seeds_set = [signer_seeds];
+
+    let cpi_metas: Vec<AccountMeta> = cpi_account_infos
+        .iter()
+        .map(|acc| AccountMeta {
+            pubkey: *acc.key,
+            is_signer: acc.is_signer || *acc.key == fund_key,
+            is_writable: acc.is_writable,
+        })
+        .collect();
+
+    let mut infos: Vec<AccountInfo> = Vec::with_capacity(cpi_account_infos.len());
+    for acc in cpi_account_infos.iter() {
+        infos.push(acc.clone());
+    }
+
+    invoke_signed(
+        &Instruction {
+            program_id: ctx.accounts.swap_program.key(),
+            accounts: cpi_metas,
+            data: swap_data,
+        },
+        &infos,
+        &signer_seeds_set,
+    )?;
+
+    let input_after = vault_amount(&input_vault_info)?;
+    let output_after = side_balance(&fund_vault_info, &output_vault_info, output_is_sol)?;
+
+    require!(input_after <= input_before, ErrorCode::InvalidTokenVault);
+    let spent = input_before
+        .checked_sub(input_after)
+        .ok_or(ErrorCode::MathOverflow)?;
+    require!(spent > 0, ErrorCode::InvalidTokenVault);
+    require!(spent <= amount_in, ErrorCode::InvalidTokenVault);
+    let received = output_after
+        .checked_sub(output_before)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    let spent_value_lamports = side_value_lamports(&input_side, spent, &sol_price)?;
+    let expected_out = match output_side.price {
+        Some(price) => expected_token_out_from_sol(
+            spent_value_lamports,
+            output_side.decimals,
+            price.price,
+            price.expo,
+            sol_price.price,
+            sol_price.expo,
+        )?,
+        None => spent_value_lamports,
+    };
+    let min_expected = apply_max_slippage(expected_out, ctx.accounts.config.max_slippage_bps)?;
+    require!(received >= min_expected, ErrorCode::InvalidTokenVault);
+    require!(received >= min_out, ErrorCode::InvalidTokenVault);
+
+    let input_holding_after = side_balance(&fund_vault_info, &input_vault_info, input_is_sol)?;
+    require!(
+        moves_toward_target(&input_side, input_holding_before, input_holding_after, &sol_price)?,
+        ErrorCode::InvalidRebalanceLeg
+    );
+    require!(
+        moves_toward_target(&output_side, output_before, output_after, &sol_price)?,
+        ErrorCode::InvalidRebalanceLeg
+    );
+    ctx.accounts.fund_state.mark_holdings_changed()?;
+
+    let session = &mut ctx.accounts.session;
+    session.leg_count = session
+        .leg_count
+        .checked_add(1)
+        .ok_or(ErrorCode::MathOverflow)?;
+    session.notional_lamports = session
+        .notional_lamports
+        .checked_add(spent_value_lamports)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    Ok(())
+}
+
+fn vault_amount(info: &AccountInfo) -> Result<u64> {
+    if info.data_is_empty() {
+        return Ok(0);
+    }
+    read_token_amount(info)
+}
+
+fn side_balance(fund_vault: &AccountInfo, vault: &AccountInfo, is_sol: bool) -> Result<u64> {
+    let token_amount = vault_amount(vault)?;
+    if !is_sol {
+        return Ok(token_amount);
+    }
+    fund_vault
+        .lamports()
+        .checked_add(token_amount)
+        .ok_or(ErrorCode::MathOverflow.into())
+}
+
+fn side_value_lamports(side: &LegSide, amount: u64, sol_price: &PythPrice) -> Result<u64> {
+    match side.price {
+        Some(price) => expected_sol_out_from_token(
+            amount,
+            side.decimals,
+            price.price,
+            price.expo,
+            sol_price.price,
+            sol_price.expo,
+        ),
+        None => Ok(amount),
+    }
+}
+
+fn moves_toward_target(
+    side: &LegSide,
+    before: u64,
+    after: u64,
+    sol_price: &PythPrice,
+) -> Result<bool> {
+    let distance = |amount: u64| -> Result<i128> {
+        let value = side_value_lamports(side, amount, sol_price)? as i128;
+        abs_i128(
+            value
+                .checked_sub(side.target_lamports)
+                .ok_or(ErrorCode::MathOverflow)?,
+        )
+    };
+    Ok(distance(after)? < distance(before)?)
+}
+
+#[derive(Accounts)]
+pub struct ExecuteRebalanceLeg<'info> {
+    pub executor: Signer<'info>,
+    #[account(
+        seeds = [b"config", config.config_id.to_le_bytes().as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, GlobalConfig>,
+    #[account(
+        mut,
+        seeds = [b"fund", config.key().as_ref(), fund_state.creator.as_ref(), fund_state.fund_id.to_le_bytes().as_ref()],
+        bump = fund_state.bump,
+        has_one = config
+    )]
+    pub fund_state: Account<'info, FundState>,
+    #[account(
+        mut,
+        seeds = [b"vault", fund_state.key().as_ref()],
+        bump = fund_state.vault_bump
+    )]
+    pub fund_vault: Account<'info, FundVault>,
+    #[account(
+        seeds = [b"strategy", fund_state.key().as_ref()],
+        bump = strategy.bump
+    )]
+    pub strategy: Account<'info, Strategy>,
+    #[account(
+        mut,
+        seeds = [b"rebalance_session", fund_state.key().as_ref()],
+        bump = session.bump
+    )]
+    pub session: Account<'info, RebalanceSession>,
+    /// CHECK: fund ATA for the input mint (session WSOL ATA when selling SOL)
+    #[account(mut)]
+    pub input_token_vault: UncheckedAccount<'info>,
+    /// CHECK: fund ATA for the output mint (session WSOL ATA when buying SOL)
+    #[account(mut)]
+    pub output_token_vault: UncheckedAccount<'info>,
+    /// CHECK: Pyth price feed for SOL/USD
+    pub sol_price_feed: AccountInfo<'info>,
+    /// CHECK: swap program id, checked against the config registry
+    pub swap_program: AccountInfo<'info>,
+    #[account(
+        seeds = [b"keeper", config.key().as_ref(), executor.key().as_ref()],
+        bump = keeper_record.bump
+    )]
+    pub keeper_record: Option<Account<'info, KeeperRecord>>,
+    pub token_program: Program<'info, Token>,
+}
@@ -2,9 +2,10 @@ use anchor_lang::prelude::*;
 use anchor_spl::token::{burn, Burn, Mint, Token, TokenAccount};
 
 use crate::errors::ErrorCode;
-use crate::instructions::deposit::compute_nav_lamports;
+use crate::instructions::deposit::resolve_nav_lamports;
 use crate::state::fund::{FundState, FundVault};
 use crate::state::global_config::GlobalConfig;
+use crate::state::nav_history::NavHistory;
 use crate::state::withdraw_request::WithdrawRequest;
 
 pub fn execute_withdraw<'info>(
@@ -40,15 +41,13 @@ pub fn execute_withdraw<'info>(
     );
 
     let vault_balance = ctx.accounts.fund_vault.to_account_info().lamports();
-    let nav_lamports = compute_nav_lamports(
+    let nav_lamports = resolve_nav_lamports(
         ctx.program_id,
         ctx.accounts.fund_state.key(),
+        &ctx.accounts.fund_state,
         vault_balance,
         ctx.accounts.config.sol_usd_pyth_feed,
         ctx.accounts.config.pyth_program_id,
-        ctx.accounts.fund_state.enabled_token_count,
-        ctx.accounts.fund_state.active_limit_count,
-        ctx.accounts.fund_state.active_dca_count,
         ctx.remaining_accounts,
     )?;
     require!(nav_lamports > 0, ErrorCode::MathOverflow);
@@ -113,6 +112,17 @@ pub fn execute_withdraw<'info>(
         }
     }
 
+    if let Some(history) = ctx.accounts.nav_history.as_mut() {
+        let nav_after = nav_lamports
+            .checked_sub(gross_lamports)
+            .ok_or(ErrorCode::MathOverflow)?;
+        history.record(
+            clock.unix_timestamp,
+            nav_after,
+            ctx.accounts.fund_state.total_shares,
+        );
+    }
+
     Ok(())
 }
 
@@ -128,7 +138,7 @@ pub struct ExecuteWithdraw<'info> {
     pub config: Account<'info, GlobalConfig>,
     #[account(
         mut,
-        seeds = [b"fund", config.key().as_ref(), fund_state.manager.as_ref(), fund_state.fund_id.to_le_bytes().as_ref()],
+        seeds = [b"fund", config.key().as_ref(), fund_state.creator.as_ref(), fund_state.fund_id.to_le_bytes().as_ref()],
         bump = fund_state.bump,
         has_one = config
     )]
@@ -160,5 +170,11 @@ pub struct ExecuteWithdraw<'info> {
     pub withdraw_request: Account<'info, WithdrawRequest>,
     #[account(mut)]
     pub fee_treasury: SystemAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"nav_history", fund_state.key().as_ref()],
+        bump = nav_history.bump
+    )]
+    pub nav_history: Option<Account<'info, NavHistory>>,
     pub token_program: Program<'info, Token>,
 }
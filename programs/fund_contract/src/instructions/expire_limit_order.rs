@@ -0,0 +1,208 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, CloseAccount, Token, TokenAccount};
+use anchor_spl::token::spl_token::native_mint;
+
+use crate::errors::ErrorCode;
+use crate::events::LimitOrderClosed;
+use crate::instructions::create_limit_order::track_order_escrow;
+use crate::state::fund::{FundState, FundVault};
+use crate::state::limit_order::{
+    LimitOrder, ORDER_STATUS_EXPIRED, ORDER_STATUS_OPEN, SIDE_BUY, SIDE_SELL,
+};
+use crate::state::whitelist::FundWhitelist;
+
+pub fn expire_limit_order<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ExpireLimitOrder<'info>>,
+) -> Result<()> {
+    let order = &mut ctx.accounts.order;
+    require!(order.status == ORDER_STATUS_OPEN, ErrorCode::OrderNotOpen);
+    require!(order.fund == ctx.accounts.fund_state.key(), ErrorCode::InvalidTokenVault);
+    require!(is_expired(order.expiry_ts)?, ErrorCode::OrderNotExpired);
+
+    let order_key = order.key();
+    let (vault_auth, vault_auth_bump) = Pubkey::find_program_address(
+        &[b"limit_order_vault_auth", order_key.as_ref()],
+        ctx.program_id,
+    );
+    require!(vault_auth == ctx.accounts.order_vault_auth.key(), ErrorCode::InvalidOrderVault);
+    let signer_seeds: &[&[u8]] = &[
+        b"limit_order_vault_auth",
+        order_key.as_ref(),
+        &[vault_auth_bump],
+    ];
+    let signer_seeds_set = [signer_seeds];
+
+    match order.side {
+        SIDE_BUY if order.escrows_sol() => {
+            let (expected_sol_vault, sol_vault_bump) = Pubkey::find_program_address(
+                &[b"limit_order_sol_vault", order_key.as_ref()],
+                ctx.program_id,
+            );
+            require!(expected_sol_vault == ctx.accounts.order_sol_vault.key(), ErrorCode::InvalidOrderVault);
+            require!(
+                ctx.accounts.order_sol_vault.owner == &anchor_lang::solana_program::system_program::ID,
+                ErrorCode::InvalidOrderVault
+            );
+            require!(ctx.accounts.order_token_vault.mint == native_mint::ID, ErrorCode::InvalidOrderVault);
+            require!(ctx.accounts.order_token_vault.owner == vault_auth, ErrorCode::InvalidOrderVault);
+            require!(ctx.accounts.order_token_vault.amount == 0, ErrorCode::InvalidOrderVault);
+
+            let sol_vault_seeds: &[&[u8]] = &[
+                b"limit_order_sol_vault",
+                order_key.as_ref(),
+                &[sol_vault_bump],
+            ];
+            drain_order_sol_vault(
+                &ctx.accounts.order_sol_vault.to_account_info(),
+                &ctx.accounts.fund_vault.to_account_info(),
+                &ctx.accounts.manager.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
+                sol_vault_seeds,
+            )?;
+        }
+        SIDE_BUY | SIDE_SELL => {
+            let escrow_mint = order.escrow_mint();
+            let expected_order_vault = anchor_spl::associated_token::get_associated_token_address(
+                &vault_auth,
+                &escrow_mint,
+            );
+            require!(expected_order_vault == ctx.accounts.order_token_vault.key(), ErrorCode::InvalidOrderVault);
+            require!(ctx.accounts.order_token_vault.mint == escrow_mint, ErrorCode::InvalidOrderVault);
+            require!(ctx.accounts.fund_token_vault.owner == ctx.accounts.fund_state.key(), ErrorCode::InvalidTokenVault);
+            require!(ctx.accounts.fund_token_vault.mint == escrow_mint, ErrorCode::InvalidTokenVault);
+
+            let amount = ctx.accounts.order_token_vault.amount;
+            if amount > 0 {
+                let transfer_ctx = CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    token::Transfer {
+                        from: ctx.accounts.order_token_vault.to_account_info(),
+                        to: ctx.accounts.fund_token_vault.to_account_info(),
+                        authority: ctx.accounts.order_vault_auth.to_account_info(),
+                    },
+                    &signer_seeds_set,
+                );
+                token::transfer(transfer_ctx, amount)?;
+            }
+        }
+        _ => return err!(ErrorCode::InvalidOrderSide),
+    }
+
+    let close_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.order_token_vault.to_account_info(),
+            destination: ctx.accounts.manager.to_account_info(),
+            authority: ctx.accounts.order_vault_auth.to_account_info(),
+        },
+        &signer_seeds_set,
+    );
+    token::close_account(close_ctx)?;
+
+    ctx.accounts.fund_state.active_limit_count = ctx
+        .accounts
+        .fund_state
+        .active_limit_count
+        .checked_sub(1)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let remaining_in = order
+        .amount_in
+        .checked_sub(order.filled_in)
+        .ok_or(ErrorCode::MathOverflow)?;
+    track_order_escrow(
+        order.escrows_sol(),
+        &order.escrow_mint(),
+        &mut ctx.accounts.fund_state,
+        &mut ctx.accounts.whitelist,
+        remaining_in,
+        0,
+    )?;
+
+    order.status = ORDER_STATUS_EXPIRED;
+    emit!(LimitOrderClosed::new(order.key(), order, Clock::get()?.unix_timestamp));
+
+    Ok(())
+}
+
+pub(crate) fn is_expired(expiry_ts: i64) -> Result<bool> {
+    Ok(expiry_ts != 0 && Clock::get()?.unix_timestamp > expiry_ts)
+}
+
+pub(crate) fn drain_order_sol_vault<'info>(
+    order_sol_vault: &AccountInfo<'info>,
+    fund_vault: &AccountInfo<'info>,
+    manager: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    sol_vault_seeds: &[&[u8]],
+) -> Result<()> {
+    let balance = order_sol_vault.lamports();
+    let rent_lamports = Rent::get()?.minimum_balance(0).min(balance);
+    let escrow_lamports = balance - rent_lamports;
+    let sol_vault_signer = [sol_vault_seeds];
+
+    if escrow_lamports > 0 {
+        let transfer_ctx = CpiContext::new_with_signer(
+            system_program.clone(),
+            anchor_lang::system_program::Transfer {
+                from: order_sol_vault.clone(),
+                to: fund_vault.clone(),
+            },
+            &sol_vault_signer,
+        );
+        anchor_lang::system_program::transfer(transfer_ctx, escrow_lamports)?;
+    }
+    if rent_lamports > 0 {
+        let transfer_ctx = CpiContext::new_with_signer(
+            system_program.clone(),
+            anchor_lang::system_program::Transfer {
+                from: order_sol_vault.clone(),
+                to: manager.clone(),
+            },
+            &sol_vault_signer,
+        );
+        anchor_lang::system_program::transfer(transfer_ctx, rent_lamports)?;
+    }
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(order_id: u64)]
+pub struct ExpireLimitOrder<'info> {
+    pub caller: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"fund", fund_state.config.as_ref(), fund_state.creator.as_ref(), fund_state.fund_id.to_le_bytes().as_ref()],
+        bump = fund_state.bump,
+    )]
+    pub fund_state: Account<'info, FundState>,
+    /// CHECK: fund manager, receives the order rent
+    #[account(mut, address = fund_state.manager)]
+    pub manager: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"vault", fund_state.key().as_ref()],
+        bump = fund_state.vault_bump
+    )]
+    pub fund_vault: Account<'info, FundVault>,
+    #[account(mut)]
+    pub whitelist: Account<'info, FundWhitelist>,
+    #[account(mut)]
+    pub fund_token_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"limit_order", fund_state.key().as_ref(), order_id.to_le_bytes().as_ref()],
+        bump = order.bump,
+        close = manager
+    )]
+    pub order: Account<'info, LimitOrder>,
+    /// CHECK: validated against derived PDA in handler
+    #[account(mut)]
+    pub order_sol_vault: UncheckedAccount<'info>,
+    /// CHECK: PDA authority for order token vault
+    pub order_vault_auth: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub order_token_vault: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+
+use crate::instructions::deposit::resolve_nav_lamports;
+use crate::state::fund::{FundState, FundVault};
+use crate::state::global_config::GlobalConfig;
+
+const SHARE_UNIT: u128 = 1_000_000_000;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default)]
+pub struct NavView {
+    pub fund: Pubkey,
+    pub nav_lamports: u64,
+    pub total_shares: u64,
+    pub share_price_lamports: u64,
+    pub slot: u64,
+}
+
+pub fn get_nav<'info>(ctx: Context<'_, '_, 'info, 'info, GetNav<'info>>) -> Result<NavView> {
+    let fund_state = &ctx.accounts.fund_state;
+    let nav_lamports = resolve_nav_lamports(
+        ctx.program_id,
+        fund_state.key(),
+        fund_state,
+        ctx.accounts.fund_vault.to_account_info().lamports(),
+        ctx.accounts.config.sol_usd_pyth_feed,
+        ctx.accounts.config.pyth_program_id,
+        ctx.remaining_accounts,
+    )?;
+    let share_price_lamports = (nav_lamports as u128)
+        .checked_mul(SHARE_UNIT)
+        .and_then(|v| v.checked_div(fund_state.total_shares as u128))
+        .unwrap_or(0) as u64;
+
+    Ok(NavView {
+        fund: fund_state.key(),
+        nav_lamports,
+        total_shares: fund_state.total_shares,
+        share_price_lamports,
+        slot: Clock::get()?.slot,
+    })
+}
+
+#[cfg(feature = "cpi")]
+pub fn get_nav_cpi<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, crate::cpi::accounts::GetNav<'info>>,
+) -> Result<NavView> {
+    Ok(crate::cpi::get_nav(ctx)?.get())
+}
+
+#[derive(Accounts)]
+pub struct GetNav<'info> {
+    #[account(
+        seeds = [b"config", config.config_id.to_le_bytes().as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, GlobalConfig>,
+    #[account(
+        seeds = [b"fund", config.key().as_ref(), fund_state.creator.as_ref(), fund_state.fund_id.to_le_bytes().as_ref()],
+        bump = fund_state.bump,
+        has_one = config
+    )]
+    pub fund_state: Account<'info, FundState>,
+    #[account(
+        seeds = [b"vault", fund_state.key().as_ref()],
+        bump = fund_state.vault_bump
+    )]
+    pub fund_vault: Account<'info, FundVault>,
+}
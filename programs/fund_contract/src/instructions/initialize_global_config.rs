@@ -1,7 +1,7 @@
 use anchor_lang::prelude::*;
 
 use crate::errors::ErrorCode;
-use crate::state::global_config::GlobalConfig;
+use crate::state::global_config::{GlobalConfig, JUPITER_V6_PROGRAM_ID};
 
 pub fn initialize_global_config(
     ctx: Context<InitializeGlobalConfig>,
@@ -17,6 +17,7 @@ pub fn initialize_global_config(
     min_manager_deposit_lamports: u64,
     min_withdraw_timelock_secs: i64,
     max_withdraw_timelock_secs: i64,
+    config_timelock_secs: i64,
 ) -> Result<()> {
     require!(deposit_fee_bps <= 10_000, ErrorCode::InvalidFeeBps);
     require!(withdraw_fee_bps <= 10_000, ErrorCode::InvalidFeeBps);
@@ -28,6 +29,7 @@ pub fn initialize_global_config(
         max_withdraw_timelock_secs >= min_withdraw_timelock_secs,
         ErrorCode::InvalidTimelock
     );
+    require!(config_timelock_secs >= 0, ErrorCode::InvalidTimelock);
 
     let config = &mut ctx.accounts.config;
     config.config_id = config_id;
@@ -44,6 +46,11 @@ pub fn initialize_global_config(
     config.min_manager_deposit_lamports = min_manager_deposit_lamports;
     config.min_withdraw_timelock_secs = min_withdraw_timelock_secs;
     config.max_withdraw_timelock_secs = max_withdraw_timelock_secs;
+    config.pending_admin = Pubkey::default();
+    config.config_timelock_secs = config_timelock_secs;
+    config.clear_pending();
+    config.max_keeper_tip_lamports = 0;
+    config.swap_programs = vec![JUPITER_V6_PROGRAM_ID];
     config.bump = ctx.bumps.config;
 
     Ok(())
@@ -5,6 +5,7 @@ use anchor_spl::token::{mint_to, Mint, MintTo, Token, TokenAccount};
 use crate::errors::ErrorCode;
 use crate::state::fund::{FundState, FundVault, FUND_TYPE_STRATEGY};
 use crate::state::global_config::GlobalConfig;
+use crate::state::nav_snapshot::NAV_SNAPSHOT_MAX_AGE_SLOTS;
 
 pub fn initialize_strategy_fund(
     ctx: Context<InitializeStrategyFund>,
@@ -58,6 +59,20 @@ pub fn initialize_strategy_fund(
     fund.active_limit_count = 0;
     fund.active_dca_count = 0;
     fund.next_order_id = 0;
+    fund.escrowed_lamports = 0;
+    fund.holdings_nonce = 0;
+    fund.held_fund_count = 0;
+    fund.holder_count = 0;
+    fund.paused = false;
+    fund.creator = ctx.accounts.manager.key();
+    fund.pending_manager = Pubkey::default();
+    fund.pending_manager_effective_ts = 0;
+    fund.keeper_tip_lamports = 0;
+    fund.keeper_tip_bps = 0;
+    fund.permissionless_execution = false;
+    fund.executor_share_bps = 0;
+    fund.nav_snapshot_required = false;
+    fund.nav_snapshot_max_age_slots = NAV_SNAPSHOT_MAX_AGE_SLOTS;
     fund.bump = ctx.bumps.fund_state;
     fund.share_mint_bump = ctx.bumps.share_mint;
     fund.vault_bump = ctx.bumps.fund_vault;
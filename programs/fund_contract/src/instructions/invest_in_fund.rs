@@ -0,0 +1,288 @@
+//! Fund-of-funds CPIs act as a per-link `fund_proxy` PDA, not as the fund PDA.
+//! The underlying `deposit`, `request_withdraw` and `cancel_withdraw` pay and refund
+//! the investor through System Program transfers and account creation, which need a
+//! system-owned account without data; the fund PDA is owned by this program, so it
+//! cannot be that investor. The proxy holds only what one instruction moves through it
+//! and must be back to zero lamports before the instruction returns.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+
+use crate::errors::ErrorCode;
+use crate::instructions::set_fund_role::require_fund_role;
+use crate::program::FundContract;
+use crate::state::fund::{FundState, FundVault};
+use crate::state::fund_roles::{FundRoles, ROLE_TRADER};
+use crate::state::global_config::GlobalConfig;
+use crate::state::whitelist::{FundWhitelist, ASSET_KIND_FUND_SHARES};
+
+pub fn invest_in_fund<'info>(
+    ctx: Context<'_, '_, 'info, 'info, InvestInFund<'info>>,
+    amount_lamports: u64,
+    min_shares: u64,
+) -> Result<()> {
+    require!(amount_lamports > 0, ErrorCode::InvalidOrderAmount);
+    require!(min_shares > 0, ErrorCode::InvalidMinOut);
+    require_fund_role(
+        ctx.accounts.fund_state.key(),
+        &ctx.accounts.fund_state,
+        ctx.accounts.fund_roles.as_ref(),
+        &ctx.accounts.manager.key(),
+        ROLE_TRADER,
+    )?;
+    require!(!ctx.accounts.fund_state.paused, ErrorCode::FundPaused);
+    validate_fund_share_whitelist(
+        &ctx.accounts.fund_whitelist,
+        ctx.accounts.fund_state.key(),
+        ctx.accounts.underlying_fund.key(),
+    )?;
+
+    let fund_key = ctx.accounts.fund_state.key();
+    let underlying_key = ctx.accounts.underlying_fund.key();
+    let proxy_seeds: &[&[u8]] = &[
+        b"fund_proxy",
+        fund_key.as_ref(),
+        underlying_key.as_ref(),
+        &[ctx.bumps.proxy],
+    ];
+    let proxy_seeds_set = [proxy_seeds];
+    sweep_proxy(
+        &ctx.accounts.proxy.to_account_info(),
+        &ctx.accounts.fund_vault.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        &proxy_seeds_set,
+    )?;
+
+    let fund_vault_info = ctx.accounts.fund_vault.to_account_info();
+    let rent_min = Rent::get()?.minimum_balance(fund_vault_info.data_len());
+    let available = fund_vault_info
+        .lamports()
+        .checked_sub(rent_min)
+        .ok_or(ErrorCode::InsufficientLiquidity)?;
+    require!(available >= amount_lamports, ErrorCode::InsufficientLiquidity);
+    move_lamports(&fund_vault_info, &ctx.accounts.proxy.to_account_info(), amount_lamports)?;
+
+    let shares_before = ctx.accounts.proxy_share_account.amount;
+
+    let deposit_accounts = crate::accounts::Deposit {
+        investor: ctx.accounts.proxy.key(),
+        config: ctx.accounts.config.key(),
+        fund_state: ctx.accounts.underlying_fund.key(),
+        fund_vault: ctx.accounts.underlying_vault.key(),
+        share_mint: ctx.accounts.share_mint.key(),
+        investor_share_account: ctx.accounts.proxy_share_account.key(),
+        fee_treasury: ctx.accounts.fee_treasury.key(),
+        nav_history: None,
+        system_program: ctx.accounts.system_program.key(),
+        token_program: ctx.accounts.token_program.key(),
+        associated_token_program: ctx.accounts.associated_token_program.key(),
+        rent: ctx.accounts.rent.key(),
+    };
+    let mut account_infos = vec![
+        ctx.accounts.proxy.to_account_info(),
+        ctx.accounts.config.to_account_info(),
+        ctx.accounts.underlying_fund.to_account_info(),
+        ctx.accounts.underlying_vault.to_account_info(),
+        ctx.accounts.share_mint.to_account_info(),
+        ctx.accounts.proxy_share_account.to_account_info(),
+        ctx.accounts.fee_treasury.to_account_info(),
+        ctx.accounts.fund_program.to_account_info(),
+        ctx.accounts.system_program.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        ctx.accounts.associated_token_program.to_account_info(),
+        ctx.accounts.rent.to_account_info(),
+    ];
+    invoke_fund_program(
+        deposit_accounts.to_account_metas(None),
+        crate::instruction::Deposit { amount_lamports }.data(),
+        &mut account_infos,
+        ctx.remaining_accounts,
+        &proxy_seeds_set,
+    )?;
+    require!(ctx.accounts.proxy.lamports() == 0, ErrorCode::ProxyBalanceNotZero);
+
+    ctx.accounts.underlying_fund.reload()?;
+    ctx.accounts.proxy_share_account.reload()?;
+    let received = ctx
+        .accounts
+        .proxy_share_account
+        .amount
+        .checked_sub(shares_before)
+        .ok_or(ErrorCode::MathOverflow)?;
+    require!(received >= min_shares, ErrorCode::InvalidTokenVault);
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        token::Transfer {
+            from: ctx.accounts.proxy_share_account.to_account_info(),
+            to: ctx.accounts.fund_token_vault.to_account_info(),
+            authority: ctx.accounts.proxy.to_account_info(),
+        },
+        &proxy_seeds_set,
+    );
+    token::transfer(transfer_ctx, received)?;
+
+    ctx.accounts.fund_state.mark_holdings_changed()?;
+
+    Ok(())
+}
+
+pub(crate) fn validate_fund_share_whitelist(
+    whitelist: &FundWhitelist,
+    fund_key: Pubkey,
+    underlying_key: Pubkey,
+) -> Result<()> {
+    require!(whitelist.enabled, ErrorCode::InvalidTokenVault);
+    require!(whitelist.fund == fund_key, ErrorCode::InvalidTokenVault);
+    require!(
+        whitelist.kind == ASSET_KIND_FUND_SHARES,
+        ErrorCode::InvalidAssetKind
+    );
+    require!(
+        whitelist.underlying_fund == underlying_key,
+        ErrorCode::InvalidTokenVault
+    );
+    Ok(())
+}
+
+pub(crate) fn invoke_fund_program<'info>(
+    mut metas: Vec<AccountMeta>,
+    data: Vec<u8>,
+    account_infos: &mut Vec<AccountInfo<'info>>,
+    remaining: &[AccountInfo<'info>],
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    for info in remaining {
+        metas.push(if info.is_writable {
+            AccountMeta::new(*info.key, info.is_signer)
+        } else {
+            AccountMeta::new_readonly(*info.key, info.is_signer)
+        });
+        account_infos.push(info.clone());
+    }
+    let ix = Instruction {
+        program_id: crate::ID,
+        accounts: metas,
+        data,
+    };
+    invoke_signed(&ix, account_infos, signer_seeds)?;
+    Ok(())
+}
+
+pub(crate) fn sweep_proxy<'info>(
+    proxy: &AccountInfo<'info>,
+    fund_vault: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    proxy_seeds: &[&[&[u8]]],
+) -> Result<u64> {
+    let amount = proxy.lamports();
+    if amount == 0 {
+        return Ok(0);
+    }
+    let sweep_ctx = CpiContext::new_with_signer(
+        system_program.clone(),
+        anchor_lang::system_program::Transfer {
+            from: proxy.clone(),
+            to: fund_vault.clone(),
+        },
+        proxy_seeds,
+    );
+    anchor_lang::system_program::transfer(sweep_ctx, amount)?;
+    Ok(amount)
+}
+
+pub(crate) fn move_lamports(from: &AccountInfo, to: &AccountInfo, amount: u64) -> Result<()> {
+    let mut from_lamports = from.try_borrow_mut_lamports()?;
+    let mut to_lamports = to.try_borrow_mut_lamports()?;
+    **from_lamports = (**from_lamports)
+        .checked_sub(amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+    **to_lamports = (**to_lamports)
+        .checked_add(amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InvestInFund<'info> {
+    #[account(mut)]
+    pub manager: Signer<'info>,
+    #[account(
+        seeds = [b"config", config.config_id.to_le_bytes().as_ref()],
+        bump = config.bump,
+        has_one = fee_treasury
+    )]
+    pub config: Account<'info, GlobalConfig>,
+    #[account(
+        mut,
+        seeds = [b"fund", config.key().as_ref(), fund_state.creator.as_ref(), fund_state.fund_id.to_le_bytes().as_ref()],
+        bump = fund_state.bump,
+        has_one = config
+    )]
+    pub fund_state: Account<'info, FundState>,
+    #[account(
+        mut,
+        seeds = [b"vault", fund_state.key().as_ref()],
+        bump = fund_state.vault_bump
+    )]
+    pub fund_vault: Account<'info, FundVault>,
+    #[account(
+        seeds = [b"whitelist", fund_state.key().as_ref(), share_mint.key().as_ref()],
+        bump = fund_whitelist.bump
+    )]
+    pub fund_whitelist: Account<'info, FundWhitelist>,
+    #[account(
+        mut,
+        associated_token::mint = share_mint,
+        associated_token::authority = fund_state
+    )]
+    pub fund_token_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"fund", config.key().as_ref(), underlying_fund.creator.as_ref(), underlying_fund.fund_id.to_le_bytes().as_ref()],
+        bump = underlying_fund.bump,
+        has_one = config
+    )]
+    pub underlying_fund: Account<'info, FundState>,
+    #[account(
+        mut,
+        seeds = [b"vault", underlying_fund.key().as_ref()],
+        bump = underlying_fund.vault_bump
+    )]
+    pub underlying_vault: Account<'info, FundVault>,
+    #[account(
+        mut,
+        seeds = [b"shares", underlying_fund.key().as_ref()],
+        bump = underlying_fund.share_mint_bump
+    )]
+    pub share_mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [b"fund_proxy", fund_state.key().as_ref(), underlying_fund.key().as_ref()],
+        bump
+    )]
+    pub proxy: SystemAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = manager,
+        associated_token::mint = share_mint,
+        associated_token::authority = proxy
+    )]
+    pub proxy_share_account: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub fee_treasury: SystemAccount<'info>,
+    #[account(
+        seeds = [b"fund_roles", fund_state.key().as_ref()],
+        bump = fund_roles.bump
+    )]
+    pub fund_roles: Option<Account<'info, FundRoles>>,
+    pub fund_program: Program<'info, FundContract>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>,
+}
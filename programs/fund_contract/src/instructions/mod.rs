@@ -21,6 +21,39 @@ pub mod cancel_dca_order;
 pub mod set_strategy;
 pub mod rebalance_strategy;
 pub mod sweep_wsol;
+pub mod open_rebalance_session;
+pub mod execute_rebalance_leg;
+pub mod close_rebalance_session;
+pub mod append_strategy_allocations;
+pub mod value_rebalance_batch;
+pub mod poke_trailing_stop;
+pub mod amend_limit_order;
+pub mod amend_dca_order;
+pub mod expire_limit_order;
+pub mod expire_dca_order;
+pub mod refresh_nav;
+pub mod refresh_nav_batch;
+pub mod set_nav_snapshot_policy;
+pub mod configure_nav_history;
+pub mod get_nav;
+pub mod add_fund_shares;
+pub mod invest_in_fund;
+pub mod request_fund_redemption;
+pub mod complete_fund_redemption;
+pub mod cancel_fund_redemption;
+pub mod set_fund_role;
+pub mod set_fund_paused;
+pub mod propose_manager;
+pub mod accept_manager;
+pub mod propose_admin;
+pub mod accept_admin;
+pub mod apply_config_update;
+pub mod register_keeper;
+pub mod remove_keeper;
+pub mod set_max_keeper_tip;
+pub mod set_keeper_tip;
+pub mod set_permissionless_execution;
+pub mod set_swap_program;
 
 pub use initialize_global_config::*;
 pub use update_global_config::*;
@@ -45,3 +78,36 @@ pub use cancel_dca_order::*;
 pub use set_strategy::*;
 pub use rebalance_strategy::*;
 pub use sweep_wsol::*;
+pub use open_rebalance_session::*;
+pub use execute_rebalance_leg::*;
+pub use close_rebalance_session::*;
+pub use append_strategy_allocations::*;
+pub use value_rebalance_batch::*;
+pub use poke_trailing_stop::*;
+pub use amend_limit_order::*;
+pub use amend_dca_order::*;
+pub use expire_limit_order::*;
+pub use expire_dca_order::*;
+pub use refresh_nav::*;
+pub use refresh_nav_batch::*;
+pub use set_nav_snapshot_policy::*;
+pub use configure_nav_history::*;
+pub use get_nav::*;
+pub use add_fund_shares::*;
+pub use invest_in_fund::*;
+pub use request_fund_redemption::*;
+pub use complete_fund_redemption::*;
+pub use cancel_fund_redemption::*;
+pub use set_fund_role::*;
+pub use set_fund_paused::*;
+pub use propose_manager::*;
+pub use accept_manager::*;
+pub use propose_admin::*;
+pub use accept_admin::*;
+pub use apply_config_update::*;
+pub use register_keeper::*;
+pub use remove_keeper::*;
+pub use set_max_keeper_tip::*;
+pub use set_keeper_tip::*;
+pub use set_permissionless_execution::*;
+pub use set_swap_program::*;
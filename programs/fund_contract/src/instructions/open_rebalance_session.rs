@@ -0,0 +1,148 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::{create, AssociatedToken};
+use anchor_spl::token::spl_token::native_mint;
+use anchor_spl::token::{Mint, Token};
+
+use crate::errors::ErrorCode;
+use crate::instructions::rebalance_strategy::read_token_amount;
+use crate::instructions::register_keeper::require_keeper;
+use crate::state::fund::{FundState, FUND_TYPE_STRATEGY};
+use crate::state::global_config::GlobalConfig;
+use crate::state::keeper_record::{KeeperRecord, KEEPER_SCOPE_REBALANCE};
+use crate::state::rebalance_session::{RebalanceEntry, RebalanceSession, SESSION_PHASE_OPENING};
+use crate::state::strategy::Strategy;
+
+pub fn open_rebalance_session<'info>(
+    ctx: Context<'_, '_, 'info, 'info, OpenRebalanceSession<'info>>,
+) -> Result<()> {
+    require_keeper(
+        &ctx.accounts.config,
+        ctx.accounts.keeper_record.as_ref(),
+        &ctx.accounts.executor.key(),
+        &ctx.accounts.fund_state.key(),
+        KEEPER_SCOPE_REBALANCE,
+    )?;
+    require!(
+        ctx.accounts.fund_state.fund_type == FUND_TYPE_STRATEGY,
+        ErrorCode::InvalidFundType
+    );
+    require!(!ctx.accounts.fund_state.paused, ErrorCode::FundPaused);
+
+    let now = Clock::get()?.unix_timestamp;
+    let strategy = &mut ctx.accounts.strategy;
+    require!(strategy.fund == ctx.accounts.fund_state.key(), ErrorCode::InvalidStrategy);
+    strategy.activate_pending(now)?;
+    require!(!strategy.allocations.is_empty(), ErrorCode::InvalidStrategy);
+    require!(
+        ctx.accounts.fund_state.enabled_token_count as usize == strategy.allocations.len(),
+        ErrorCode::InvalidStrategy
+    );
+    require!(strategy.rebalance_cooldown_secs > 0, ErrorCode::InvalidStrategy);
+
+    let fund_key = ctx.accounts.fund_state.key();
+    let expected_wsol_vault =
+        anchor_spl::associated_token::get_associated_token_address(&fund_key, &native_mint::ID);
+    require!(
+        expected_wsol_vault == ctx.accounts.fund_wsol_vault.key(),
+        ErrorCode::InvalidOrderVault
+    );
+    let wsol_created = ctx.accounts.fund_wsol_vault.to_account_info().data_is_empty();
+    if !wsol_created {
+        let wsol_amount = read_token_amount(&ctx.accounts.fund_wsol_vault.to_account_info())?;
+        require!(wsol_amount == 0, ErrorCode::WsolNotCleared);
+    }
+
+    let next_allowed = strategy
+        .last_rebalance_ts
+        .checked_add(strategy.rebalance_cooldown_secs)
+        .ok_or(ErrorCode::MathOverflow)?;
+    require!(now >= next_allowed, ErrorCode::RebalanceNotNeeded);
+    strategy.last_rebalance_ts = now;
+
+    let entries: Vec<RebalanceEntry> = strategy
+        .allocations
+        .iter()
+        .enumerate()
+        .map(|(idx, alloc)| RebalanceEntry {
+            mint: alloc.mint,
+            weight_bps: strategy.target_weight_bps(idx, now),
+            value_lamports: 0,
+        })
+        .collect();
+    let sol_weight_bps = strategy.target_sol_weight_bps(now);
+
+    if wsol_created {
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.associated_token_program.to_account_info(),
+            anchor_spl::associated_token::Create {
+                payer: ctx.accounts.executor.to_account_info(),
+                associated_token: ctx.accounts.fund_wsol_vault.to_account_info(),
+                authority: ctx.accounts.fund_state.to_account_info(),
+                mint: ctx.accounts.wsol_mint.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+            },
+        );
+        create(cpi_ctx)?;
+    }
+
+    let session = &mut ctx.accounts.session;
+    session.fund = fund_key;
+    session.opener = ctx.accounts.executor.key();
+    session.nav_lamports = 0;
+    session.opened_ts = now;
+    session.leg_count = 0;
+    session.notional_lamports = 0;
+    session.phase = SESSION_PHASE_OPENING;
+    session.cursor = 0;
+    session.sol_weight_bps = sol_weight_bps;
+    session.entries = entries;
+    session.wsol_created = wsol_created;
+    session.bump = ctx.bumps.session;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct OpenRebalanceSession<'info> {
+    #[account(mut)]
+    pub executor: Signer<'info>,
+    #[account(
+        seeds = [b"config", config.config_id.to_le_bytes().as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, GlobalConfig>,
+    #[account(
+        seeds = [b"fund", config.key().as_ref(), fund_state.creator.as_ref(), fund_state.fund_id.to_le_bytes().as_ref()],
+        bump = fund_state.bump,
+        has_one = config
+    )]
+    pub fund_state: Account<'info, FundState>,
+    #[account(
+        mut,
+        seeds = [b"strategy", fund_state.key().as_ref()],
+        bump = strategy.bump
+    )]
+    pub strategy: Account<'info, Strategy>,
+    #[account(
+        init,
+        payer = executor,
+        space = 8 + RebalanceSession::space(strategy.allocations.len()),
+        seeds = [b"rebalance_session", fund_state.key().as_ref()],
+        bump
+    )]
+    pub session: Account<'info, RebalanceSession>,
+    /// CHECK: ATA for fund_state + WSOL, must be empty; created for the session if missing
+    #[account(mut)]
+    pub fund_wsol_vault: UncheckedAccount<'info>,
+    #[account(address = native_mint::ID)]
+    pub wsol_mint: Account<'info, Mint>,
+    #[account(
+        seeds = [b"keeper", config.key().as_ref(), executor.key().as_ref()],
+        bump = keeper_record.bump
+    )]
+    pub keeper_record: Option<Account<'info, KeeperRecord>>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::instructions::execute_limit_order::record_trailing_peak;
+use crate::oracle::{cross_price, load_pyth_price, scale_price};
+use crate::state::fund::FundState;
+use crate::state::limit_order::{LimitOrder, ORDER_STATUS_OPEN, ORDER_TYPE_TRAILING_STOP};
+
+pub fn poke_trailing_stop(ctx: Context<PokeTrailingStop>) -> Result<()> {
+    let order = &mut ctx.accounts.order;
+    require!(order.status == ORDER_STATUS_OPEN, ErrorCode::OrderNotOpen);
+    require!(order.fund == ctx.accounts.fund_state.key(), ErrorCode::InvalidTokenVault);
+    require!(
+        order.order_type == ORDER_TYPE_TRAILING_STOP,
+        ErrorCode::InvalidOrderType
+    );
+
+    let price_info = &ctx.accounts.price_feed;
+    require!(price_info.key == &order.price_feed, ErrorCode::InvalidOracle);
+    require!(price_info.owner == &order.pyth_program_id, ErrorCode::InvalidOracle);
+    let price = load_pyth_price(price_info)?;
+    let price_at_order_expo = if order.quote_mint == Pubkey::default() {
+        scale_price(price.price, price.expo, order.price_expo)?
+    } else {
+        let quote_info = ctx
+            .remaining_accounts
+            .first()
+            .ok_or(ErrorCode::InvalidRemainingAccounts)?;
+        require!(quote_info.key == &order.quote_price_feed, ErrorCode::InvalidOracle);
+        require!(quote_info.owner == &order.pyth_program_id, ErrorCode::InvalidOracle);
+        let quote_price = load_pyth_price(quote_info)?;
+        cross_price(
+            price.price,
+            price.expo,
+            quote_price.price,
+            quote_price.expo,
+            order.price_expo,
+        )?
+    };
+
+    record_trailing_peak(order, price_at_order_expo)
+}
+
+#[derive(Accounts)]
+#[instruction(order_id: u64)]
+pub struct PokeTrailingStop<'info> {
+    #[account(
+        seeds = [b"fund", fund_state.config.as_ref(), fund_state.creator.as_ref(), fund_state.fund_id.to_le_bytes().as_ref()],
+        bump = fund_state.bump,
+    )]
+    pub fund_state: Account<'info, FundState>,
+    #[account(
+        mut,
+        seeds = [b"limit_order", fund_state.key().as_ref(), order_id.to_le_bytes().as_ref()],
+        bump = order.bump
+    )]
+    pub order: Account<'info, LimitOrder>,
+    /// CHECK: Pyth price feed pinned on the order
+    pub price_feed: AccountInfo<'info>,
+}
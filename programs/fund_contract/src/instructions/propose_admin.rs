@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::state::global_config::GlobalConfig;
+
+pub fn propose_admin(ctx: Context<ProposeAdmin>, new_admin: Pubkey) -> Result<()> {
+    require!(new_admin != ctx.accounts.config.admin, ErrorCode::InvalidAdmin);
+    ctx.accounts.config.pending_admin = new_admin;
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(config_id: u64)]
+pub struct ProposeAdmin<'info> {
+    #[account(
+        mut,
+        seeds = [b"config", config_id.to_le_bytes().as_ref()],
+        bump = config.bump,
+        has_one = admin
+    )]
+    pub config: Account<'info, GlobalConfig>,
+    pub admin: Signer<'info>,
+}
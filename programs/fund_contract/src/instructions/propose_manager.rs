@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::events::ManagerTransferProposed;
+use crate::state::fund::FundState;
+
+pub fn propose_manager(
+    ctx: Context<ProposeManager>,
+    new_manager: Pubkey,
+    effective_ts: i64,
+) -> Result<()> {
+    let fund_state = &mut ctx.accounts.fund_state;
+    require!(
+        fund_state.manager == ctx.accounts.manager.key(),
+        ErrorCode::Unauthorized
+    );
+    require!(new_manager != fund_state.manager, ErrorCode::InvalidManager);
+
+    let now = Clock::get()?.unix_timestamp;
+    if new_manager == Pubkey::default() {
+        fund_state.pending_manager = Pubkey::default();
+        fund_state.pending_manager_effective_ts = 0;
+    } else {
+        let earliest_effective_ts = now
+            .checked_add(fund_state.withdraw_timelock_secs)
+            .ok_or(ErrorCode::MathOverflow)?;
+        require!(
+            effective_ts >= earliest_effective_ts,
+            ErrorCode::ManagerTransferTimelock
+        );
+        fund_state.pending_manager = new_manager;
+        fund_state.pending_manager_effective_ts = effective_ts;
+    }
+
+    emit!(ManagerTransferProposed {
+        fund: fund_state.key(),
+        manager: fund_state.manager,
+        pending_manager: fund_state.pending_manager,
+        effective_ts: fund_state.pending_manager_effective_ts,
+        proposed_ts: now,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ProposeManager<'info> {
+    pub manager: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"fund", fund_state.config.as_ref(), fund_state.creator.as_ref(), fund_state.fund_id.to_le_bytes().as_ref()],
+        bump = fund_state.bump,
+    )]
+    pub fund_state: Account<'info, FundState>,
+}
@@ -4,18 +4,20 @@ use anchor_lang::solana_program::program::invoke_signed;
 use anchor_spl::associated_token::{create, AssociatedToken};
 use anchor_spl::token::{self, Mint, Token, TokenAccount};
 use anchor_spl::token::spl_token::native_mint;
-use pyth_sdk_solana::state::SolanaPriceAccount;
 
 use crate::errors::ErrorCode;
+use crate::instructions::register_keeper::require_keeper;
+use crate::instructions::set_keeper_tip::pay_keeper_tip;
+use crate::oracle::{
+    apply_max_slippage, expected_sol_out_from_token, expected_token_out_from_sol,
+    load_pyth_price, PythPrice,
+};
 use crate::state::fund::{FundState, FundVault, FUND_TYPE_STRATEGY};
 use crate::state::global_config::GlobalConfig;
-use crate::state::strategy::{Strategy, StrategyAllocation};
+use crate::state::keeper_record::{KeeperRecord, KEEPER_SCOPE_REBALANCE};
+use crate::state::strategy::Strategy;
 use crate::state::whitelist::FundWhitelist;
 
-const ORACLE_MAX_AGE_SECS: u64 = 60;
-const MAX_CONF_BPS: u64 = 200;
-const JUPITER_PROGRAM_ID: Pubkey = pubkey!("JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4");
-const LAMPORTS_PER_SOL_U64: u64 = 1_000_000_000;
 const SELL_DUST_TOLERANCE: u64 = 2;
 
 pub fn rebalance_strategy<'info>(
@@ -24,21 +26,31 @@ pub fn rebalance_strategy<'info>(
     min_out: u64,
     swap_data: Vec<u8>,
 ) -> Result<()> {
-    require!(
-        ctx.accounts.executor.key() == ctx.accounts.config.keeper,
-        ErrorCode::Unauthorized
-    );
+    require_keeper(
+        &ctx.accounts.config,
+        ctx.accounts.keeper_record.as_ref(),
+        &ctx.accounts.executor.key(),
+        &ctx.accounts.fund_state.key(),
+        KEEPER_SCOPE_REBALANCE,
+    )?;
     require!(min_out > 0, ErrorCode::InvalidMinOut);
     require!(
         ctx.accounts.fund_state.fund_type == FUND_TYPE_STRATEGY,
         ErrorCode::InvalidFundType
     );
+    require!(!ctx.accounts.fund_state.paused, ErrorCode::FundPaused);
+    require!(
+        ctx.accounts.session.data_is_empty(),
+        ErrorCode::RebalanceSessionActive
+    );
 
+    let now = Clock::get()?.unix_timestamp;
     let strategy = &mut ctx.accounts.strategy;
     require!(strategy.fund == ctx.accounts.fund_state.key(), ErrorCode::InvalidStrategy);
-    require!(strategy.allocation_count > 0, ErrorCode::InvalidStrategy);
+    strategy.activate_pending(now)?;
+    require!(!strategy.allocations.is_empty(), ErrorCode::InvalidStrategy);
     require!(
-        ctx.accounts.fund_state.enabled_token_count as u8 == strategy.allocation_count,
+        ctx.accounts.fund_state.enabled_token_count as usize == strategy.allocations.len(),
         ErrorCode::InvalidStrategy
     );
     require!(strategy.rebalance_cooldown_secs > 0, ErrorCode::InvalidStrategy);
@@ -65,7 +77,6 @@ pub fn rebalance_strategy<'info>(
     };
     require!(wsol_amount == 0, ErrorCode::WsolNotCleared);
 
-    let now = Clock::get()?.unix_timestamp;
     let next_allowed = strategy
         .last_rebalance_ts
         .checked_add(strategy.rebalance_cooldown_secs)
@@ -75,7 +86,9 @@ pub fn rebalance_strategy<'info>(
     require!(!swap_data.is_empty(), ErrorCode::InvalidSwapProgram);
     require!(!ctx.remaining_accounts.is_empty(), ErrorCode::InvalidSwapProgram);
     require!(
-        ctx.accounts.swap_program.key() == JUPITER_PROGRAM_ID,
+        ctx.accounts
+            .config
+            .is_swap_program_allowed(&ctx.accounts.swap_program.key()),
         ErrorCode::InvalidSwapProgram
     );
 
@@ -90,7 +103,7 @@ pub fn rebalance_strategy<'info>(
     );
     let sol_price = load_pyth_price(sol_price_info)?;
 
-    let alloc_count = strategy.allocation_count as usize;
+    let alloc_count = strategy.allocations.len();
     let expected_remaining = 3 * alloc_count;
     require!(
         ctx.remaining_accounts.len() >= expected_remaining,
@@ -101,86 +114,44 @@ pub fn rebalance_strategy<'info>(
     require!(!cpi_account_infos.is_empty(), ErrorCode::InvalidSwapProgram);
 
     let fund_vault_lamports = ctx.accounts.fund_vault.to_account_info().lamports();
-    let nav_base_lamports = fund_vault_lamports;
-
-    let mut nav_lamports: i128 = nav_base_lamports as i128;
-    let mut target_weight: Option<u16> = None;
-    let target_value_lamports: i128;
-    let mut target_actual_value: i128 = 0;
-    let mut target_token_amount: u64 = 0;
-    let mut target_decimals: u8 = 0;
-    let mut target_price: Option<PythPrice> = None;
-
-    for idx in 0..alloc_count {
-        let alloc: StrategyAllocation = strategy.allocations[idx];
-        let wl_info = &validation_accounts[idx * 3];
-        let vault_info = &validation_accounts[idx * 3 + 1];
-        let price_info = &validation_accounts[idx * 3 + 2];
-
-        let whitelist: Account<FundWhitelist> =
-            Account::try_from(wl_info).map_err(|_| ErrorCode::InvalidTokenVault)?;
-        require!(whitelist.enabled, ErrorCode::InvalidTokenVault);
-        require!(whitelist.fund == fund_key, ErrorCode::InvalidTokenVault);
-        require!(whitelist.mint == alloc.mint, ErrorCode::InvalidTokenVault);
-        require!(whitelist.pyth_feed == *price_info.key, ErrorCode::InvalidOracle);
-        require!(price_info.owner == &ctx.accounts.config.pyth_program_id, ErrorCode::InvalidOracle);
-
-        let expected_vault = anchor_spl::associated_token::get_associated_token_address(
-            &fund_key,
-            &alloc.mint,
-        );
-        require!(*vault_info.key == expected_vault, ErrorCode::InvalidTokenVault);
-        let token_vault: Account<TokenAccount> =
-            Account::try_from(vault_info).map_err(|_| ErrorCode::InvalidTokenVault)?;
-        require!(token_vault.mint == alloc.mint, ErrorCode::InvalidTokenVault);
-
-        let token_price = load_pyth_price(price_info)?;
-        let token_value = expected_sol_out_from_token(
-            token_vault.amount,
-            whitelist.decimals,
-            token_price.price,
-            token_price.expo,
-            sol_price.price,
-            sol_price.expo,
-        )? as i128;
-        nav_lamports = nav_lamports
-            .checked_add(token_value)
-            .ok_or(ErrorCode::MathOverflow)?;
-
-        if alloc.mint == target_mint {
-            target_weight = Some(alloc.weight_bps);
-            target_actual_value = token_value;
-            target_token_amount = token_vault.amount;
-            target_decimals = whitelist.decimals;
-            target_price = Some(token_price);
-
-            require!(
-                token_vault.key() == ctx.accounts.fund_token_vault.key(),
-                ErrorCode::InvalidTokenVault
-            );
-        }
-    }
-
+    let holdings = load_strategy_holdings(
+        strategy,
+        &fund_key,
+        &ctx.accounts.config.pyth_program_id,
+        validation_accounts,
+        &sol_price,
+        now,
+    )?;
+    let nav_lamports = strategy_nav_lamports(fund_vault_lamports, &holdings)?;
     require!(nav_lamports > 0, ErrorCode::InvalidNav);
-    let weight_bps = target_weight.ok_or(ErrorCode::InvalidStrategy)? as i128;
-    target_value_lamports = nav_lamports
-        .checked_mul(weight_bps)
-        .ok_or(ErrorCode::MathOverflow)?
-        .checked_div(10_000)
-        .ok_or(ErrorCode::MathOverflow)?;
+
+    let target = holdings
+        .iter()
+        .find(|holding| holding.mint == target_mint)
+        .ok_or(ErrorCode::InvalidStrategy)?;
+    require!(
+        target.vault == ctx.accounts.fund_token_vault.key(),
+        ErrorCode::InvalidTokenVault
+    );
+    let target_actual_value = target.value_lamports;
+    let target_token_amount = target.amount;
+    let target_decimals = target.decimals;
+    let target_price = target.price;
+    let target_value_lamports = target_value_for_weight(nav_lamports, target.weight_bps)?;
 
     let deviation = target_actual_value
         .checked_sub(target_value_lamports)
         .ok_or(ErrorCode::MathOverflow)?;
-    let abs_deviation = abs_i128(deviation)?;
-    let threshold = nav_lamports
-        .checked_mul(strategy.rebalance_threshold_bps as i128)
-        .ok_or(ErrorCode::MathOverflow)?
-        .checked_div(10_000)
+    let threshold = target_value_for_weight(nav_lamports, strategy.rebalance_threshold_bps)?;
+    let sol_floor = target_value_for_weight(nav_lamports, strategy.target_sol_weight_bps(now))?;
+    let sol_deviation = (fund_vault_lamports as i128)
+        .checked_sub(sol_floor)
         .ok_or(ErrorCode::MathOverflow)?;
-    if abs_deviation <= threshold {
-        return err!(ErrorCode::RebalanceNotNeeded);
-    }
+    let abs_deviation = if abs_i128(deviation)? > threshold {
+        abs_i128(deviation)?
+    } else {
+        sol_driven_trade_lamports(&holdings, nav_lamports, deviation, sol_deviation, threshold)?
+    };
 
     let mut has_fund_token_vault = false;
     let mut has_fund_wsol = false;
@@ -208,7 +179,7 @@ pub fn rebalance_strategy<'info>(
     let signer_seeds: &[&[u8]] = &[
         b"fund",
         config_key.as_ref(),
-        ctx.accounts.fund_state.manager.as_ref(),
+        ctx.accounts.fund_state.creator.as_ref(),
         fund_id_bytes.as_ref(),
         &[ctx.accounts.fund_state.bump],
     ];
@@ -228,14 +199,15 @@ pub fn rebalance_strategy<'info>(
         infos.push(acc.clone());
     }
 
-    let target_price = target_price.ok_or(ErrorCode::InvalidStrategy)?;
-
-    if deviation < 0 {
+    let notional_lamports = if deviation < 0 {
         require!(has_fund_vault, ErrorCode::InvalidTokenVault);
         require!(has_fund_wsol, ErrorCode::InvalidOrderVault);
 
-        let spend_lamports = abs_deviation as u64;
-        require!(fund_vault_lamports >= spend_lamports, ErrorCode::InsufficientLiquidity);
+        let sol_available = (fund_vault_lamports as i128)
+            .checked_sub(sol_floor)
+            .ok_or(ErrorCode::MathOverflow)?;
+        require!(sol_available > 0, ErrorCode::InsufficientLiquidity);
+        let spend_lamports = std::cmp::min(abs_deviation, sol_available) as u64;
 
         if ctx.accounts.fund_wsol_vault.to_account_info().data_is_empty() {
             let cpi_ctx = CpiContext::new(
@@ -306,6 +278,7 @@ pub fn rebalance_strategy<'info>(
         )?;
         require!(token_delta >= min_expected, ErrorCode::InvalidTokenVault);
         require!(token_delta >= min_out, ErrorCode::InvalidTokenVault);
+        spend_lamports
     } else {
         require!(has_fund_vault, ErrorCode::InvalidTokenVault);
 
@@ -366,134 +339,179 @@ pub fn rebalance_strategy<'info>(
         )?;
         require!(sol_delta >= min_expected, ErrorCode::InvalidTokenVault);
         require!(sol_delta >= min_out, ErrorCode::InvalidTokenVault);
-    }
+        sol_delta
+    };
 
     strategy.last_rebalance_ts = now;
+    ctx.accounts.fund_state.mark_holdings_changed()?;
+    let tip_lamports = ctx.accounts.fund_state.keeper_tip(
+        notional_lamports,
+        ctx.accounts.config.max_keeper_tip_lamports,
+        1,
+        1,
+        true,
+    )?;
+    pay_keeper_tip(
+        fund_key,
+        &ctx.accounts.fund_vault.to_account_info(),
+        &ctx.accounts.executor.to_account_info(),
+        notional_lamports,
+        tip_lamports,
+    )?;
     Ok(())
 }
 
-struct PythPrice {
-    price: i64,
-    expo: i32,
+pub(crate) struct StrategyHolding {
+    pub(crate) mint: Pubkey,
+    pub(crate) weight_bps: u16,
+    pub(crate) vault: Pubkey,
+    pub(crate) amount: u64,
+    pub(crate) decimals: u8,
+    pub(crate) price: PythPrice,
+    pub(crate) value_lamports: i128,
 }
 
-fn load_pyth_price(price_info: &AccountInfo) -> Result<PythPrice> {
-    let feed = SolanaPriceAccount::account_info_to_feed(price_info)
-        .map_err(|_| ErrorCode::InvalidOracle)?;
-    let price = feed
-        .get_price_no_older_than(Clock::get()?.unix_timestamp, ORACLE_MAX_AGE_SECS)
-        .ok_or(ErrorCode::StaleOracle)?;
-    require!(price.price > 0, ErrorCode::InvalidOracle);
-    let price_u128 = price.price as u128;
-    let max_conf = price_u128
-        .checked_mul(MAX_CONF_BPS as u128)
-        .ok_or(ErrorCode::MathOverflow)?
-        .checked_div(10_000)
-        .ok_or(ErrorCode::MathOverflow)? as u64;
-    require!(price.conf <= max_conf, ErrorCode::InvalidOracleConfidence);
-    Ok(PythPrice {
-        price: price.price,
-        expo: price.expo,
-    })
+pub(crate) fn load_strategy_holdings<'info>(
+    strategy: &Strategy,
+    fund_key: &Pubkey,
+    pyth_program_id: &Pubkey,
+    validation_accounts: &'info [AccountInfo<'info>],
+    sol_price: &PythPrice,
+    now: i64,
+) -> Result<Vec<StrategyHolding>> {
+    let alloc_count = strategy.allocations.len();
+    require!(
+        validation_accounts.len() == 3 * alloc_count,
+        ErrorCode::InvalidRemainingAccounts
+    );
+
+    let mut holdings: Vec<StrategyHolding> = Vec::with_capacity(alloc_count);
+    for (idx, triplet) in validation_accounts.chunks(3).enumerate() {
+        holdings.push(load_strategy_holding(
+            strategy.allocations[idx].mint,
+            strategy.target_weight_bps(idx, now),
+            fund_key,
+            pyth_program_id,
+            triplet,
+            sol_price,
+        )?);
+    }
+    Ok(holdings)
 }
 
-fn apply_max_slippage(expected_out: u64, max_slippage_bps: u16) -> Result<u64> {
-    require!(max_slippage_bps <= 10_000, ErrorCode::InvalidFeeBps);
-    let expected = expected_out as u128;
-    let factor = 10_000u128
-        .checked_sub(max_slippage_bps as u128)
-        .ok_or(ErrorCode::MathOverflow)?;
-    let min_expected = expected
-        .checked_mul(factor)
-        .ok_or(ErrorCode::MathOverflow)?
-        .checked_div(10_000u128)
-        .ok_or(ErrorCode::MathOverflow)?;
-    Ok(min_expected as u64)
+pub(crate) fn load_strategy_holding<'info>(
+    mint: Pubkey,
+    weight_bps: u16,
+    fund_key: &Pubkey,
+    pyth_program_id: &Pubkey,
+    triplet: &'info [AccountInfo<'info>],
+    sol_price: &PythPrice,
+) -> Result<StrategyHolding> {
+    let wl_info = &triplet[0];
+    let vault_info = &triplet[1];
+    let price_info = &triplet[2];
+
+    let whitelist: Account<FundWhitelist> =
+        Account::try_from(wl_info).map_err(|_| ErrorCode::InvalidTokenVault)?;
+    require!(whitelist.enabled, ErrorCode::InvalidTokenVault);
+    require!(whitelist.fund == *fund_key, ErrorCode::InvalidTokenVault);
+    require!(whitelist.mint == mint, ErrorCode::InvalidTokenVault);
+    require!(whitelist.pyth_feed == *price_info.key, ErrorCode::InvalidOracle);
+    require!(price_info.owner == pyth_program_id, ErrorCode::InvalidOracle);
+
+    let expected_vault = anchor_spl::associated_token::get_associated_token_address(
+        fund_key,
+        &mint,
+    );
+    require!(*vault_info.key == expected_vault, ErrorCode::InvalidTokenVault);
+    let token_vault: Account<TokenAccount> =
+        Account::try_from(vault_info).map_err(|_| ErrorCode::InvalidTokenVault)?;
+    require!(token_vault.mint == mint, ErrorCode::InvalidTokenVault);
+
+    let token_price = load_pyth_price(price_info)?;
+    let token_value = expected_sol_out_from_token(
+        token_vault.amount,
+        whitelist.decimals,
+        token_price.price,
+        token_price.expo,
+        sol_price.price,
+        sol_price.expo,
+    )? as i128;
+
+    Ok(StrategyHolding {
+        mint,
+        weight_bps,
+        vault: expected_vault,
+        amount: token_vault.amount,
+        decimals: whitelist.decimals,
+        price: token_price,
+        value_lamports: token_value,
+    })
 }
 
-fn pow10_i128(exp: u32) -> Result<i128> {
-    let mut value: i128 = 1;
-    for _ in 0..exp {
-        value = value.checked_mul(10).ok_or(ErrorCode::MathOverflow)?;
+pub(crate) fn strategy_nav_lamports(
+    fund_vault_lamports: u64,
+    holdings: &[StrategyHolding],
+) -> Result<i128> {
+    let mut nav_lamports: i128 = fund_vault_lamports as i128;
+    for holding in holdings.iter() {
+        nav_lamports = nav_lamports
+            .checked_add(holding.value_lamports)
+            .ok_or(ErrorCode::MathOverflow)?;
     }
-    Ok(value)
+    Ok(nav_lamports)
 }
 
-fn expected_sol_out_from_token(
-    amount: u64,
-    decimals: u8,
-    token_price: i64,
-    token_expo: i32,
-    sol_price: i64,
-    sol_expo: i32,
-) -> Result<u64> {
-    require!(token_price > 0, ErrorCode::InvalidOracle);
-    require!(sol_price > 0, ErrorCode::InvalidOracle);
-    let mut numerator = (amount as i128)
-        .checked_mul(token_price as i128)
-        .ok_or(ErrorCode::MathOverflow)?
-        .checked_mul(LAMPORTS_PER_SOL_U64 as i128)
-        .ok_or(ErrorCode::MathOverflow)?;
-    let mut denominator = sol_price as i128;
-
-    let exp = token_expo
-        .checked_sub(decimals as i32)
-        .and_then(|v| v.checked_sub(sol_expo))
-        .ok_or(ErrorCode::MathOverflow)?;
-
-    if exp >= 0 {
-        let scale = pow10_i128(exp as u32)?;
-        numerator = numerator
-            .checked_mul(scale)
-            .ok_or(ErrorCode::MathOverflow)?;
-    } else {
-        let scale = pow10_i128((-exp) as u32)?;
-        denominator = denominator
-            .checked_mul(scale)
+fn sol_driven_trade_lamports(
+    holdings: &[StrategyHolding],
+    nav_lamports: i128,
+    deviation: i128,
+    sol_deviation: i128,
+    threshold: i128,
+) -> Result<i128> {
+    require!(abs_i128(sol_deviation)? > threshold, ErrorCode::RebalanceNotNeeded);
+    let mut extreme = 0i128;
+    for holding in holdings.iter() {
+        let holding_deviation = holding
+            .value_lamports
+            .checked_sub(target_value_for_weight(nav_lamports, holding.weight_bps)?)
             .ok_or(ErrorCode::MathOverflow)?;
+        if (sol_deviation > 0 && holding_deviation < extreme)
+            || (sol_deviation < 0 && holding_deviation > extreme)
+        {
+            extreme = holding_deviation;
+        }
     }
-    require!(denominator > 0, ErrorCode::MathOverflow);
-    Ok((numerator / denominator) as u64)
+    require!(
+        deviation != 0 && deviation == extreme,
+        ErrorCode::RebalanceNotNeeded
+    );
+    Ok(std::cmp::min(abs_i128(deviation)?, abs_i128(sol_deviation)?))
 }
 
-fn expected_token_out_from_sol(
-    sol_lamports: u64,
-    decimals: u8,
-    token_price: i64,
-    token_expo: i32,
-    sol_price: i64,
-    sol_expo: i32,
-) -> Result<u64> {
-    require!(token_price > 0, ErrorCode::InvalidOracle);
-    require!(sol_price > 0, ErrorCode::InvalidOracle);
-    let mut numerator = (sol_lamports as i128)
-        .checked_mul(sol_price as i128)
-        .ok_or(ErrorCode::MathOverflow)?
-        .checked_mul(pow10_i128(decimals as u32)?)
-        .ok_or(ErrorCode::MathOverflow)?;
-    let mut denominator = (token_price as i128)
-        .checked_mul(LAMPORTS_PER_SOL_U64 as i128)
+pub(crate) fn exceeds_threshold(
+    actual_value: i128,
+    nav_lamports: i128,
+    weight_bps: u16,
+    threshold_bps: u16,
+) -> Result<bool> {
+    let target_value = target_value_for_weight(nav_lamports, weight_bps)?;
+    let deviation = actual_value
+        .checked_sub(target_value)
         .ok_or(ErrorCode::MathOverflow)?;
+    let threshold = target_value_for_weight(nav_lamports, threshold_bps)?;
+    Ok(abs_i128(deviation)? > threshold)
+}
 
-    let exp = sol_expo
-        .checked_sub(token_expo)
-        .ok_or(ErrorCode::MathOverflow)?;
-    if exp >= 0 {
-        let scale = pow10_i128(exp as u32)?;
-        numerator = numerator
-            .checked_mul(scale)
-            .ok_or(ErrorCode::MathOverflow)?;
-    } else {
-        let scale = pow10_i128((-exp) as u32)?;
-        denominator = denominator
-            .checked_mul(scale)
-            .ok_or(ErrorCode::MathOverflow)?;
-    }
-    require!(denominator > 0, ErrorCode::MathOverflow);
-    Ok((numerator / denominator) as u64)
+pub(crate) fn target_value_for_weight(nav_lamports: i128, weight_bps: u16) -> Result<i128> {
+    Ok(nav_lamports
+        .checked_mul(weight_bps as i128)
+        .ok_or(ErrorCode::MathOverflow)?
+        .checked_div(10_000)
+        .ok_or(ErrorCode::MathOverflow)?)
 }
 
-fn abs_i128(value: i128) -> Result<i128> {
+pub(crate) fn abs_i128(value: i128) -> Result<i128> {
     if value >= 0 {
         Ok(value)
     } else {
@@ -503,7 +521,7 @@ fn abs_i128(value: i128) -> Result<i128> {
     }
 }
 
-fn read_token_amount(info: &AccountInfo) -> Result<u64> {
+pub(crate) fn read_token_amount(info: &AccountInfo) -> Result<u64> {
     let data = info.data.borrow();
     let mut data_slice: &[u8] = &data;
     let account = TokenAccount::try_deserialize(&mut data_slice)
@@ -513,6 +531,7 @@ fn read_token_amount(info: &AccountInfo) -> Result<u64> {
 
 #[derive(Accounts)]
 pub struct RebalanceStrategy<'info> {
+    #[account(mut)]
     pub executor: Signer<'info>,
     #[account(
         seeds = [b"config", config.config_id.to_le_bytes().as_ref()],
@@ -521,7 +540,7 @@ pub struct RebalanceStrategy<'info> {
     pub config: Account<'info, GlobalConfig>,
     #[account(
         mut,
-        seeds = [b"fund", config.key().as_ref(), fund_state.manager.as_ref(), fund_state.fund_id.to_le_bytes().as_ref()],
+        seeds = [b"fund", config.key().as_ref(), fund_state.creator.as_ref(), fund_state.fund_id.to_le_bytes().as_ref()],
         bump = fund_state.bump,
         has_one = config
     )]
@@ -538,6 +557,12 @@ pub struct RebalanceStrategy<'info> {
         bump = strategy.bump
     )]
     pub strategy: Account<'info, Strategy>,
+    /// CHECK: rebalance session PDA, must not exist
+    #[account(
+        seeds = [b"rebalance_session", fund_state.key().as_ref()],
+        bump
+    )]
+    pub session: UncheckedAccount<'info>,
     #[account(mut)]
     pub fund_token_vault: Account<'info, TokenAccount>,
     /// CHECK: created as ATA for fund_state + WSOL when needed
@@ -547,8 +572,13 @@ pub struct RebalanceStrategy<'info> {
     pub wsol_mint: Account<'info, Mint>,
     /// CHECK: Pyth price feed for SOL/USD
     pub sol_price_feed: AccountInfo<'info>,
-    /// CHECK: Jupiter program id
+    /// CHECK: swap program id, checked against the config registry
     pub swap_program: AccountInfo<'info>,
+    #[account(
+        seeds = [b"keeper", config.key().as_ref(), executor.key().as_ref()],
+        bump = keeper_record.bump
+    )]
+    pub keeper_record: Option<Account<'info, KeeperRecord>>,
     pub token_program: Program<'info, Token>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
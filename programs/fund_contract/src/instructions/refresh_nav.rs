@@ -0,0 +1,160 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::instructions::deposit::{compute_nav, NavBreakdown};
+use crate::state::fund::{FundState, FundVault};
+use crate::state::global_config::GlobalConfig;
+use crate::state::nav_history::NavHistory;
+use crate::state::nav_snapshot::{NavSnapshot, MAX_NAV_BATCH_ASSETS};
+
+pub fn refresh_nav<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RefreshNav<'info>>,
+) -> Result<()> {
+    let fund_state = &ctx.accounts.fund_state;
+    require!(
+        (fund_state.enabled_token_count as usize) <= MAX_NAV_BATCH_ASSETS,
+        ErrorCode::InvalidRemainingAccounts
+    );
+
+    let breakdown = compute_nav(
+        ctx.program_id,
+        fund_state.key(),
+        ctx.accounts.fund_vault.to_account_info().lamports(),
+        fund_state.escrowed_lamports,
+        ctx.accounts.config.sol_usd_pyth_feed,
+        ctx.accounts.config.pyth_program_id,
+        fund_state.enabled_token_count,
+        ctx.remaining_accounts,
+    )?;
+
+    let clock = Clock::get()?;
+    let nav_lamports = breakdown.nav_lamports;
+    let snapshot = &mut ctx.accounts.nav_snapshot;
+    write_nav_snapshot(snapshot, fund_state, breakdown, clock.slot, clock.unix_timestamp);
+    snapshot.bump = ctx.bumps.nav_snapshot;
+    if let Some(history) = ctx.accounts.nav_history.as_mut() {
+        history.record(clock.unix_timestamp, nav_lamports, fund_state.total_shares);
+    }
+
+    resize_nav_snapshot(
+        &ctx.accounts.nav_snapshot,
+        &ctx.accounts.caller,
+        &ctx.accounts.system_program,
+    )
+}
+
+pub(crate) fn write_nav_snapshot(
+    snapshot: &mut NavSnapshot,
+    fund_state: &Account<FundState>,
+    breakdown: NavBreakdown,
+    slot: u64,
+    updated_ts: i64,
+) {
+    snapshot.fund = fund_state.key();
+    snapshot.nav_lamports = breakdown.nav_lamports;
+    snapshot.sol_lamports = breakdown.sol_lamports;
+    snapshot.token_value_lamports = breakdown.token_value_lamports;
+    snapshot.total_shares = fund_state.total_shares;
+    snapshot.sol_price = breakdown.sol_price;
+    snapshot.sol_price_expo = breakdown.sol_price_expo;
+    snapshot.sol_price_publish_time = breakdown.sol_price_publish_time;
+    snapshot.holdings_nonce = fund_state.holdings_nonce;
+    snapshot.enabled_token_count = fund_state.enabled_token_count;
+    snapshot.slot = slot;
+    snapshot.updated_ts = updated_ts;
+    snapshot.assets = breakdown.assets;
+    snapshot.batch_cursor = 0;
+    snapshot.batch_assets = Vec::new();
+}
+
+pub(crate) fn snapshot_nav_lamports<'info>(
+    program_id: &Pubkey,
+    fund_key: Pubkey,
+    fund_state: &FundState,
+    sol_lamports: u64,
+    snapshot_info: &'info AccountInfo<'info>,
+) -> Result<u64> {
+    let (expected_snapshot, _) =
+        Pubkey::find_program_address(&[b"nav_snapshot", fund_key.as_ref()], program_id);
+    require!(
+        expected_snapshot == *snapshot_info.key,
+        ErrorCode::InvalidRemainingAccounts
+    );
+    let snapshot: Account<NavSnapshot> = Account::try_from(snapshot_info)?;
+    require!(snapshot.fund == fund_key, ErrorCode::InvalidRemainingAccounts);
+    require!(
+        snapshot.is_fresh(fund_state, Clock::get()?.slot),
+        ErrorCode::StaleNavSnapshot
+    );
+
+    let nav = sol_lamports
+        .checked_add(fund_state.escrowed_lamports)
+        .and_then(|v| v.checked_add(snapshot.token_value_lamports))
+        .ok_or(ErrorCode::MathOverflow)?;
+    require!(nav > 0, ErrorCode::InvalidNav);
+    Ok(nav)
+}
+
+pub(crate) fn resize_nav_snapshot<'info>(
+    snapshot: &Account<'info, NavSnapshot>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    let space = 8 + NavSnapshot::space(snapshot.assets.len() + snapshot.batch_assets.len());
+    let snapshot_info = snapshot.to_account_info();
+    if space <= snapshot_info.data_len() {
+        return Ok(());
+    }
+
+    let rent_lamports = Rent::get()?.minimum_balance(space);
+    let top_up = rent_lamports.saturating_sub(snapshot_info.lamports());
+    if top_up > 0 {
+        let cpi_ctx = CpiContext::new(
+            system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: payer.to_account_info(),
+                to: snapshot_info.clone(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_ctx, top_up)?;
+    }
+    snapshot_info.resize(space)?;
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RefreshNav<'info> {
+    #[account(mut)]
+    pub caller: Signer<'info>,
+    #[account(
+        seeds = [b"config", config.config_id.to_le_bytes().as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, GlobalConfig>,
+    #[account(
+        seeds = [b"fund", config.key().as_ref(), fund_state.creator.as_ref(), fund_state.fund_id.to_le_bytes().as_ref()],
+        bump = fund_state.bump,
+        has_one = config
+    )]
+    pub fund_state: Account<'info, FundState>,
+    #[account(
+        seeds = [b"vault", fund_state.key().as_ref()],
+        bump = fund_state.vault_bump
+    )]
+    pub fund_vault: Account<'info, FundVault>,
+    #[account(
+        init_if_needed,
+        payer = caller,
+        space = 8 + NavSnapshot::space(fund_state.enabled_token_count as usize),
+        seeds = [b"nav_snapshot", fund_state.key().as_ref()],
+        bump
+    )]
+    pub nav_snapshot: Account<'info, NavSnapshot>,
+    #[account(
+        mut,
+        seeds = [b"nav_history", fund_state.key().as_ref()],
+        bump = nav_history.bump
+    )]
+    pub nav_history: Option<Account<'info, NavHistory>>,
+    pub system_program: Program<'info, System>,
+}
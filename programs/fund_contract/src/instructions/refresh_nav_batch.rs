@@ -0,0 +1,166 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::instructions::deposit::{value_nav_entries, NavBreakdown};
+use crate::instructions::refresh_nav::{resize_nav_snapshot, write_nav_snapshot};
+use crate::oracle::load_pyth_price;
+use crate::state::fund::{FundState, FundVault};
+use crate::state::global_config::GlobalConfig;
+use crate::state::nav_history::NavHistory;
+use crate::state::nav_snapshot::{NavSnapshot, MAX_NAV_ASSETS, MAX_NAV_BATCH_ASSETS};
+use crate::state::whitelist::FundWhitelist;
+
+pub fn refresh_nav_batch<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RefreshNavBatch<'info>>,
+    asset_count: u16,
+) -> Result<()> {
+    let fund_state = &ctx.accounts.fund_state;
+    let enabled_token_count = fund_state.enabled_token_count;
+    require!(
+        (enabled_token_count as usize) <= MAX_NAV_ASSETS,
+        ErrorCode::InvalidRemainingAccounts
+    );
+    require!(
+        (asset_count as usize) <= MAX_NAV_BATCH_ASSETS,
+        ErrorCode::InvalidRemainingAccounts
+    );
+
+    let sol_price_info = &ctx.accounts.sol_price_feed;
+    require!(
+        sol_price_info.key == &ctx.accounts.config.sol_usd_pyth_feed,
+        ErrorCode::InvalidOracle
+    );
+    require!(
+        sol_price_info.owner == &ctx.accounts.config.pyth_program_id,
+        ErrorCode::InvalidOracle
+    );
+    let sol_price = load_pyth_price(sol_price_info)?;
+
+    let clock = Clock::get()?;
+    let snapshot = &mut ctx.accounts.nav_snapshot;
+    let mut restart = !snapshot.is_batch_fresh(fund_state, clock.slot);
+    if !restart && asset_count > 0 {
+        let first_info = ctx
+            .remaining_accounts
+            .first()
+            .ok_or(ErrorCode::InvalidRemainingAccounts)?;
+        let first: Account<FundWhitelist> = Account::try_from(first_info)?;
+        restart = first.mint.to_bytes() <= snapshot.batch_last_mint.to_bytes();
+    }
+    if restart {
+        snapshot.batch_cursor = 0;
+        snapshot.batch_last_mint = Pubkey::default();
+        snapshot.batch_token_value_lamports = 0;
+        snapshot.batch_holdings_nonce = fund_state.holdings_nonce;
+        snapshot.batch_enabled_token_count = enabled_token_count;
+        snapshot.batch_slot = clock.slot;
+        snapshot.batch_assets = Vec::with_capacity(enabled_token_count as usize);
+    }
+
+    let prev_mint = if snapshot.batch_cursor > 0 {
+        Some(snapshot.batch_last_mint)
+    } else {
+        None
+    };
+    let entries = value_nav_entries(
+        ctx.program_id,
+        fund_state.key(),
+        ctx.accounts.config.pyth_program_id,
+        &sol_price,
+        prev_mint,
+        asset_count as usize,
+        ctx.remaining_accounts,
+    )?;
+    let cursor = (snapshot.batch_cursor as usize)
+        .checked_add(asset_count as usize)
+        .ok_or(ErrorCode::MathOverflow)?;
+    require!(
+        cursor <= enabled_token_count as usize,
+        ErrorCode::InvalidRemainingAccounts
+    );
+    snapshot.batch_cursor = cursor as u16;
+    if let Some(last_mint) = entries.last_mint {
+        snapshot.batch_last_mint = last_mint;
+    }
+    snapshot.batch_token_value_lamports = snapshot
+        .batch_token_value_lamports
+        .checked_add(entries.token_value_lamports)
+        .ok_or(ErrorCode::MathOverflow)?;
+    snapshot.batch_assets.extend(entries.assets);
+    snapshot.bump = ctx.bumps.nav_snapshot;
+
+    if cursor == enabled_token_count as usize {
+        let sol_lamports = ctx
+            .accounts
+            .fund_vault
+            .to_account_info()
+            .lamports()
+            .checked_add(fund_state.escrowed_lamports)
+            .ok_or(ErrorCode::MathOverflow)?;
+        let token_value_lamports = snapshot.batch_token_value_lamports;
+        let nav_lamports = sol_lamports
+            .checked_add(token_value_lamports)
+            .ok_or(ErrorCode::MathOverflow)?;
+        require!(nav_lamports > 0, ErrorCode::InvalidNav);
+
+        let breakdown = NavBreakdown {
+            nav_lamports,
+            sol_lamports,
+            token_value_lamports,
+            sol_price: sol_price.price,
+            sol_price_expo: sol_price.expo,
+            sol_price_publish_time: sol_price.publish_time,
+            assets: std::mem::take(&mut snapshot.batch_assets),
+        };
+        let started_slot = snapshot.batch_slot;
+        write_nav_snapshot(snapshot, fund_state, breakdown, started_slot, clock.unix_timestamp);
+        if let Some(history) = ctx.accounts.nav_history.as_mut() {
+            history.record(clock.unix_timestamp, nav_lamports, fund_state.total_shares);
+        }
+    }
+
+    resize_nav_snapshot(
+        &ctx.accounts.nav_snapshot,
+        &ctx.accounts.caller,
+        &ctx.accounts.system_program,
+    )
+}
+
+#[derive(Accounts)]
+pub struct RefreshNavBatch<'info> {
+    #[account(mut)]
+    pub caller: Signer<'info>,
+    #[account(
+        seeds = [b"config", config.config_id.to_le_bytes().as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, GlobalConfig>,
+    #[account(
+        seeds = [b"fund", config.key().as_ref(), fund_state.creator.as_ref(), fund_state.fund_id.to_le_bytes().as_ref()],
+        bump = fund_state.bump,
+        has_one = config
+    )]
+    pub fund_state: Account<'info, FundState>,
+    #[account(
+        seeds = [b"vault", fund_state.key().as_ref()],
+        bump = fund_state.vault_bump
+    )]
+    pub fund_vault: Account<'info, FundVault>,
+    #[account(
+        init_if_needed,
+        payer = caller,
+        space = 8 + NavSnapshot::space(fund_state.enabled_token_count as usize),
+        seeds = [b"nav_snapshot", fund_state.key().as_ref()],
+        bump
+    )]
+    pub nav_snapshot: Account<'info, NavSnapshot>,
+    /// CHECK: Pyth price feed for SOL/USD
+    pub sol_price_feed: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"nav_history", fund_state.key().as_ref()],
+        bump = nav_history.bump
+    )]
+    pub nav_history: Option<Account<'info, NavHistory>>,
+    pub system_program: Program<'info, System>,
+}
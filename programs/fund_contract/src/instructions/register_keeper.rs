@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::state::global_config::GlobalConfig;
+use crate::state::keeper_record::{KeeperRecord, KEEPER_SCOPE_ALL};
+
+pub fn register_keeper(
+    ctx: Context<RegisterKeeper>,
+    keeper: Pubkey,
+    scopes: u8,
+    fund: Pubkey,
+) -> Result<()> {
+    require!(keeper != Pubkey::default(), ErrorCode::InvalidKeeper);
+    require!(scopes != 0, ErrorCode::InvalidKeeper);
+    require!(scopes & !KEEPER_SCOPE_ALL == 0, ErrorCode::InvalidKeeper);
+
+    let record = &mut ctx.accounts.keeper_record;
+    record.config = ctx.accounts.config.key();
+    record.keeper = keeper;
+    record.scopes = scopes;
+    record.fund = fund;
+    record.bump = ctx.bumps.keeper_record;
+    Ok(())
+}
+
+pub(crate) fn require_keeper(
+    config: &Account<GlobalConfig>,
+    keeper_record: Option<&Account<KeeperRecord>>,
+    executor: &Pubkey,
+    fund_key: &Pubkey,
+    scope: u8,
+) -> Result<()> {
+    require!(
+        is_keeper(config, keeper_record, executor, fund_key, scope),
+        ErrorCode::Unauthorized
+    );
+    Ok(())
+}
+
+pub(crate) fn is_keeper(
+    config: &Account<GlobalConfig>,
+    keeper_record: Option<&Account<KeeperRecord>>,
+    executor: &Pubkey,
+    fund_key: &Pubkey,
+    scope: u8,
+) -> bool {
+    if config.keeper == *executor {
+        return true;
+    }
+    keeper_record.is_some_and(|record| {
+        record.config == config.key()
+            && record.keeper == *executor
+            && record.allows(fund_key, scope)
+    })
+}
+
+#[derive(Accounts)]
+#[instruction(config_id: u64, keeper: Pubkey)]
+pub struct RegisterKeeper<'info> {
+    #[account(
+        seeds = [b"config", config_id.to_le_bytes().as_ref()],
+        bump = config.bump,
+        has_one = admin
+    )]
+    pub config: Account<'info, GlobalConfig>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + KeeperRecord::LEN,
+        seeds = [b"keeper", config.key().as_ref(), keeper.as_ref()],
+        bump
+    )]
+    pub keeper_record: Account<'info, KeeperRecord>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::state::global_config::GlobalConfig;
+use crate::state::keeper_record::KeeperRecord;
+
+pub fn remove_keeper(ctx: Context<RemoveKeeper>) -> Result<()> {
+    require!(
+        ctx.accounts.keeper_record.config == ctx.accounts.config.key(),
+        ErrorCode::Unauthorized
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(config_id: u64)]
+pub struct RemoveKeeper<'info> {
+    #[account(
+        seeds = [b"config", config_id.to_le_bytes().as_ref()],
+        bump = config.bump,
+        has_one = admin
+    )]
+    pub config: Account<'info, GlobalConfig>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"keeper", config.key().as_ref(), keeper_record.keeper.as_ref()],
+        bump = keeper_record.bump
+    )]
+    pub keeper_record: Account<'info, KeeperRecord>,
+}
@@ -2,9 +2,11 @@ use anchor_lang::prelude::*;
 use anchor_spl::token::{Mint, TokenAccount};
 
 use crate::errors::ErrorCode;
+use crate::instructions::set_fund_role::require_fund_role;
 use crate::state::fund::FundState;
+use crate::state::fund_roles::{FundRoles, ROLE_OPS};
 use crate::state::global_config::GlobalConfig;
-use crate::state::whitelist::GlobalWhitelist;
+use crate::state::whitelist::{FundWhitelist, GlobalWhitelist, ASSET_KIND_FUND_SHARES};
 
 const SCOPE_GLOBAL: u8 = 0;
 const SCOPE_FUND: u8 = 1;
@@ -61,7 +63,7 @@ fn remove_fund<'info>(
         &[
             b"fund",
             ctx.accounts.config.key().as_ref(),
-            fund_state.manager.as_ref(),
+            fund_state.creator.as_ref(),
             fund_state.fund_id.to_le_bytes().as_ref(),
         ],
         ctx.program_id,
@@ -70,10 +72,13 @@ fn remove_fund<'info>(
         expected_fund == *fund_state_info.key,
         ErrorCode::InvalidTokenVault
     );
-    require!(
-        fund_state.manager == ctx.accounts.authority.key(),
-        ErrorCode::Unauthorized
-    );
+    require_fund_role(
+        *fund_state_info.key,
+        &fund_state,
+        ctx.accounts.fund_roles.as_ref(),
+        &ctx.accounts.authority.key(),
+        ROLE_OPS,
+    )?;
     require!(fund_state.fund_id == fund_id, ErrorCode::InvalidTokenVault);
 
     let (expected_whitelist, _bump) = Pubkey::find_program_address(
@@ -99,6 +104,30 @@ fn remove_fund<'info>(
     let vault: Account<TokenAccount> = Account::try_from(fund_token_vault_info)?;
     require!(vault.mint == ctx.accounts.mint.key(), ErrorCode::InvalidTokenVault);
     require!(vault.amount == 0, ErrorCode::TokenVaultNotEmpty);
+    let fund_whitelist: Account<FundWhitelist> = Account::try_from(fund_whitelist_info)?;
+    require!(fund_whitelist.escrowed_amount == 0, ErrorCode::TokenVaultNotEmpty);
+    if fund_whitelist.kind == ASSET_KIND_FUND_SHARES {
+        require!(remaining.len() >= 4, ErrorCode::InvalidRemainingAccounts);
+        let underlying_info = &remaining[3];
+        require!(underlying_info.is_writable, ErrorCode::InvalidRemainingAccounts);
+        require!(
+            fund_whitelist.underlying_fund == *underlying_info.key,
+            ErrorCode::InvalidTokenVault
+        );
+        let mut underlying = load_fund_state(underlying_info, ctx.program_id)?;
+        underlying.holder_count = underlying
+            .holder_count
+            .checked_sub(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+        let mut data = underlying_info.try_borrow_mut_data()?;
+        let mut writer: &mut [u8] = &mut data;
+        underlying.try_serialize(&mut writer)?;
+
+        fund_state.held_fund_count = fund_state
+            .held_fund_count
+            .checked_sub(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+    }
     close_program_account(
         fund_whitelist_info,
         &ctx.accounts.authority.to_account_info(),
@@ -146,5 +175,6 @@ pub struct RemoveToken<'info> {
     pub mint: Account<'info, Mint>,
     #[account(mut)]
     pub global_whitelist: Account<'info, GlobalWhitelist>,
+    pub fund_roles: Option<Account<'info, FundRoles>>,
     pub system_program: Program<'info, System>,
 }
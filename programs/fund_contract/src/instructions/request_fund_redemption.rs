@@ -0,0 +1,195 @@
+use anchor_lang::prelude::*;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+
+use crate::errors::ErrorCode;
+use crate::instructions::set_fund_role::require_fund_role;
+use crate::instructions::invest_in_fund::{
+    invoke_fund_program, move_lamports, sweep_proxy, validate_fund_share_whitelist,
+};
+use crate::program::FundContract;
+use crate::state::fund::{FundState, FundVault};
+use crate::state::fund_roles::{FundRoles, ROLE_TRADER};
+use crate::state::global_config::GlobalConfig;
+use crate::state::whitelist::FundWhitelist;
+use crate::state::withdraw_request::WithdrawRequest;
+
+pub fn request_fund_redemption<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RequestFundRedemption<'info>>,
+    shares: u64,
+) -> Result<()> {
+    require!(shares > 0, ErrorCode::InvalidWithdrawal);
+    require_fund_role(
+        ctx.accounts.fund_state.key(),
+        &ctx.accounts.fund_state,
+        ctx.accounts.fund_roles.as_ref(),
+        &ctx.accounts.manager.key(),
+        ROLE_TRADER,
+    )?;
+    validate_fund_share_whitelist(
+        &ctx.accounts.fund_whitelist,
+        ctx.accounts.fund_state.key(),
+        ctx.accounts.underlying_fund.key(),
+    )?;
+    require!(
+        ctx.accounts.fund_whitelist.escrowed_amount == 0,
+        ErrorCode::FundRedemptionPending
+    );
+    require!(
+        ctx.accounts.fund_token_vault.amount >= shares,
+        ErrorCode::InsufficientShares
+    );
+
+    let config_key = ctx.accounts.config.key();
+    let creator_key = ctx.accounts.fund_state.creator;
+    let fund_id_bytes = ctx.accounts.fund_state.fund_id.to_le_bytes();
+    let fund_seeds: &[&[u8]] = &[
+        b"fund",
+        config_key.as_ref(),
+        creator_key.as_ref(),
+        fund_id_bytes.as_ref(),
+        &[ctx.accounts.fund_state.bump],
+    ];
+    let fund_seeds_set = [fund_seeds];
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        token::Transfer {
+            from: ctx.accounts.fund_token_vault.to_account_info(),
+            to: ctx.accounts.proxy_share_account.to_account_info(),
+            authority: ctx.accounts.fund_state.to_account_info(),
+        },
+        &fund_seeds_set,
+    );
+    token::transfer(transfer_ctx, shares)?;
+    ctx.accounts.fund_whitelist.track_escrow(0, shares)?;
+
+    let fund_key = ctx.accounts.fund_state.key();
+    let underlying_key = ctx.accounts.underlying_fund.key();
+    let proxy_seeds: &[&[u8]] = &[
+        b"fund_proxy",
+        fund_key.as_ref(),
+        underlying_key.as_ref(),
+        &[ctx.bumps.proxy],
+    ];
+    let proxy_seeds_set = [proxy_seeds];
+    sweep_proxy(
+        &ctx.accounts.proxy.to_account_info(),
+        &ctx.accounts.fund_vault.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        &proxy_seeds_set,
+    )?;
+
+    let request_rent = Rent::get()?.minimum_balance(8 + WithdrawRequest::LEN);
+    move_lamports(
+        &ctx.accounts.fund_vault.to_account_info(),
+        &ctx.accounts.proxy.to_account_info(),
+        request_rent,
+    )?;
+    ctx.accounts.fund_state.track_escrow(0, request_rent)?;
+    let request_accounts = crate::accounts::RequestWithdraw {
+        investor: ctx.accounts.proxy.key(),
+        config: ctx.accounts.config.key(),
+        fund_state: ctx.accounts.underlying_fund.key(),
+        share_mint: ctx.accounts.share_mint.key(),
+        investor_share_account: ctx.accounts.proxy_share_account.key(),
+        withdraw_request: ctx.accounts.withdraw_request.key(),
+        system_program: ctx.accounts.system_program.key(),
+        rent: ctx.accounts.rent.key(),
+    };
+    let mut account_infos = vec![
+        ctx.accounts.proxy.to_account_info(),
+        ctx.accounts.config.to_account_info(),
+        ctx.accounts.underlying_fund.to_account_info(),
+        ctx.accounts.share_mint.to_account_info(),
+        ctx.accounts.proxy_share_account.to_account_info(),
+        ctx.accounts.withdraw_request.to_account_info(),
+        ctx.accounts.system_program.to_account_info(),
+        ctx.accounts.rent.to_account_info(),
+        ctx.accounts.fund_program.to_account_info(),
+    ];
+    invoke_fund_program(
+        request_accounts.to_account_metas(None),
+        crate::instruction::RequestWithdraw { shares }.data(),
+        &mut account_infos,
+        &[],
+        &proxy_seeds_set,
+    )?;
+    require!(ctx.accounts.proxy.lamports() == 0, ErrorCode::ProxyBalanceNotZero);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RequestFundRedemption<'info> {
+    #[account(mut)]
+    pub manager: Signer<'info>,
+    #[account(
+        seeds = [b"config", config.config_id.to_le_bytes().as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, GlobalConfig>,
+    #[account(
+        mut,
+        seeds = [b"fund", config.key().as_ref(), fund_state.creator.as_ref(), fund_state.fund_id.to_le_bytes().as_ref()],
+        bump = fund_state.bump,
+        has_one = config
+    )]
+    pub fund_state: Account<'info, FundState>,
+    #[account(
+        mut,
+        seeds = [b"vault", fund_state.key().as_ref()],
+        bump = fund_state.vault_bump
+    )]
+    pub fund_vault: Account<'info, FundVault>,
+    #[account(
+        mut,
+        seeds = [b"whitelist", fund_state.key().as_ref(), share_mint.key().as_ref()],
+        bump = fund_whitelist.bump
+    )]
+    pub fund_whitelist: Account<'info, FundWhitelist>,
+    #[account(
+        mut,
+        associated_token::mint = share_mint,
+        associated_token::authority = fund_state
+    )]
+    pub fund_token_vault: Account<'info, TokenAccount>,
+    #[account(
+        seeds = [b"fund", config.key().as_ref(), underlying_fund.creator.as_ref(), underlying_fund.fund_id.to_le_bytes().as_ref()],
+        bump = underlying_fund.bump,
+        has_one = config
+    )]
+    pub underlying_fund: Account<'info, FundState>,
+    #[account(
+        seeds = [b"shares", underlying_fund.key().as_ref()],
+        bump = underlying_fund.share_mint_bump
+    )]
+    pub share_mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [b"fund_proxy", fund_state.key().as_ref(), underlying_fund.key().as_ref()],
+        bump
+    )]
+    pub proxy: SystemAccount<'info>,
+    #[account(
+        mut,
+        associated_token::mint = share_mint,
+        associated_token::authority = proxy
+    )]
+    pub proxy_share_account: Account<'info, TokenAccount>,
+    /// CHECK: created by the request_withdraw CPI.
+    #[account(
+        mut,
+        seeds = [b"withdraw", underlying_fund.key().as_ref(), proxy.key().as_ref()],
+        bump
+    )]
+    pub withdraw_request: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"fund_roles", fund_state.key().as_ref()],
+        bump = fund_roles.bump
+    )]
+    pub fund_roles: Option<Account<'info, FundRoles>>,
+    pub fund_program: Program<'info, FundContract>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+use crate::instructions::set_fund_role::require_fund_role;
+use crate::state::fund::FundState;
+use crate::state::fund_roles::{FundRoles, ROLE_RISK_OFFICER};
+
+pub fn set_fund_paused(ctx: Context<SetFundPaused>, paused: bool) -> Result<()> {
+    require_fund_role(
+        ctx.accounts.fund_state.key(),
+        &ctx.accounts.fund_state,
+        ctx.accounts.fund_roles.as_ref(),
+        &ctx.accounts.authority.key(),
+        ROLE_RISK_OFFICER,
+    )?;
+    ctx.accounts.fund_state.paused = paused;
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetFundPaused<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"fund", fund_state.config.as_ref(), fund_state.creator.as_ref(), fund_state.fund_id.to_le_bytes().as_ref()],
+        bump = fund_state.bump,
+    )]
+    pub fund_state: Account<'info, FundState>,
+    #[account(
+        seeds = [b"fund_roles", fund_state.key().as_ref()],
+        bump = fund_roles.bump
+    )]
+    pub fund_roles: Option<Account<'info, FundRoles>>,
+}
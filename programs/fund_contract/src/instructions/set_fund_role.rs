@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::state::fund::FundState;
+use crate::state::fund_roles::{FundRoleMember, FundRoles, MAX_FUND_ROLE_MEMBERS, ROLE_ALL};
+
+pub fn set_fund_role(ctx: Context<SetFundRole>, member: Pubkey, roles: u8) -> Result<()> {
+    require!(
+        ctx.accounts.fund_state.manager == ctx.accounts.manager.key(),
+        ErrorCode::Unauthorized
+    );
+    require!(roles & !ROLE_ALL == 0, ErrorCode::InvalidRole);
+    require!(
+        member != ctx.accounts.fund_state.manager,
+        ErrorCode::InvalidRole
+    );
+
+    let fund_roles = &mut ctx.accounts.fund_roles;
+    fund_roles.fund = ctx.accounts.fund_state.key();
+    fund_roles.bump = ctx.bumps.fund_roles;
+
+    match fund_roles.members.iter().position(|entry| entry.key == member) {
+        Some(idx) => fund_roles.members[idx].roles = roles,
+        None if roles != 0 => {
+            require!(
+                fund_roles.members.len() < MAX_FUND_ROLE_MEMBERS,
+                ErrorCode::TooManyRoleMembers
+            );
+            fund_roles.members.push(FundRoleMember { key: member, roles });
+        }
+        None => {}
+    }
+    fund_roles.members.retain(|entry| entry.roles != 0);
+
+    Ok(())
+}
+
+pub(crate) fn require_fund_role(
+    fund_key: Pubkey,
+    fund_state: &FundState,
+    fund_roles: Option<&Account<FundRoles>>,
+    signer: &Pubkey,
+    roles: u8,
+) -> Result<()> {
+    if fund_state.manager == *signer {
+        return Ok(());
+    }
+    let fund_roles = fund_roles.ok_or(ErrorCode::Unauthorized)?;
+    require!(fund_roles.fund == fund_key, ErrorCode::Unauthorized);
+    require!(
+        fund_roles.has_any_role(signer, roles),
+        ErrorCode::Unauthorized
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetFundRole<'info> {
+    #[account(mut)]
+    pub manager: Signer<'info>,
+    #[account(
+        seeds = [b"fund", fund_state.config.as_ref(), fund_state.creator.as_ref(), fund_state.fund_id.to_le_bytes().as_ref()],
+        bump = fund_state.bump,
+    )]
+    pub fund_state: Account<'info, FundState>,
+    #[account(
+        init_if_needed,
+        payer = manager,
+        space = 8 + FundRoles::LEN,
+        seeds = [b"fund_roles", fund_state.key().as_ref()],
+        bump
+    )]
+    pub fund_roles: Account<'info, FundRoles>,
+    pub system_program: Program<'info, System>,
+}
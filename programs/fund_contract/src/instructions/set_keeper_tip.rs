@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::events::KeeperTipPaid;
+use crate::instructions::invest_in_fund::move_lamports;
+use crate::state::fund::FundState;
+use crate::state::global_config::GlobalConfig;
+
+pub fn set_keeper_tip(
+    ctx: Context<SetKeeperTip>,
+    keeper_tip_lamports: u64,
+    keeper_tip_bps: u16,
+) -> Result<()> {
+    require!(
+        ctx.accounts.fund_state.manager == ctx.accounts.manager.key(),
+        ErrorCode::Unauthorized
+    );
+    require!(keeper_tip_bps <= 10_000, ErrorCode::InvalidFeeBps);
+    require!(
+        keeper_tip_lamports <= ctx.accounts.config.max_keeper_tip_lamports,
+        ErrorCode::InvalidKeeperTip
+    );
+
+    let fund_state = &mut ctx.accounts.fund_state;
+    fund_state.keeper_tip_lamports = keeper_tip_lamports;
+    fund_state.keeper_tip_bps = keeper_tip_bps;
+    Ok(())
+}
+
+pub(crate) fn pay_keeper_tip<'info>(
+    fund_key: Pubkey,
+    fund_vault: &AccountInfo<'info>,
+    executor: &AccountInfo<'info>,
+    notional_lamports: u64,
+    tip_lamports: u64,
+) -> Result<u64> {
+    let rent_min = Rent::get()?.minimum_balance(fund_vault.data_len());
+    let available = fund_vault.lamports().saturating_sub(rent_min);
+    let tip = tip_lamports.min(available);
+    if tip == 0 {
+        return Ok(0);
+    }
+    move_lamports(fund_vault, executor, tip)?;
+    emit!(KeeperTipPaid {
+        fund: fund_key,
+        keeper: executor.key(),
+        notional_lamports,
+        tip_lamports: tip,
+    });
+    Ok(tip)
+}
+
+#[derive(Accounts)]
+pub struct SetKeeperTip<'info> {
+    pub manager: Signer<'info>,
+    #[account(
+        seeds = [b"config", config.config_id.to_le_bytes().as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, GlobalConfig>,
+    #[account(
+        mut,
+        seeds = [b"fund", config.key().as_ref(), fund_state.creator.as_ref(), fund_state.fund_id.to_le_bytes().as_ref()],
+        bump = fund_state.bump,
+        has_one = config
+    )]
+    pub fund_state: Account<'info, FundState>,
+}
@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+
+use crate::state::global_config::GlobalConfig;
+
+pub fn set_max_keeper_tip(
+    ctx: Context<SetMaxKeeperTip>,
+    max_keeper_tip_lamports: u64,
+) -> Result<()> {
+    ctx.accounts.config.max_keeper_tip_lamports = max_keeper_tip_lamports;
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(config_id: u64)]
+pub struct SetMaxKeeperTip<'info> {
+    #[account(
+        mut,
+        seeds = [b"config", config_id.to_le_bytes().as_ref()],
+        bump = config.bump,
+        has_one = admin
+    )]
+    pub config: Account<'info, GlobalConfig>,
+    pub admin: Signer<'info>,
+}
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::state::fund::FundState;
+use crate::state::nav_snapshot::NAV_SNAPSHOT_MAX_AGE_SLOTS;
+
+pub fn set_nav_snapshot_policy(
+    ctx: Context<SetNavSnapshotPolicy>,
+    required: bool,
+    max_age_slots: u64,
+) -> Result<()> {
+    require!(
+        ctx.accounts.fund_state.manager == ctx.accounts.manager.key(),
+        ErrorCode::Unauthorized
+    );
+    require!(
+        max_age_slots > 0 && max_age_slots <= NAV_SNAPSHOT_MAX_AGE_SLOTS,
+        ErrorCode::InvalidNavSnapshotAge
+    );
+
+    let fund_state = &mut ctx.accounts.fund_state;
+    fund_state.nav_snapshot_required = required;
+    fund_state.nav_snapshot_max_age_slots = max_age_slots;
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetNavSnapshotPolicy<'info> {
+    pub manager: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"fund", fund_state.config.as_ref(), fund_state.creator.as_ref(), fund_state.fund_id.to_le_bytes().as_ref()],
+        bump = fund_state.bump
+    )]
+    pub fund_state: Account<'info, FundState>,
+}
@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::events::PriceImprovementShared;
+use crate::instructions::invest_in_fund::move_lamports;
+use crate::instructions::register_keeper::is_keeper;
+use crate::state::fund::FundState;
+use crate::state::global_config::GlobalConfig;
+use crate::state::keeper_record::{KeeperRecord, KEEPER_SCOPE_ORDERS};
+
+pub fn set_permissionless_execution(
+    ctx: Context<SetPermissionlessExecution>,
+    enabled: bool,
+    executor_share_bps: u16,
+) -> Result<()> {
+    require!(
+        ctx.accounts.fund_state.manager == ctx.accounts.manager.key(),
+        ErrorCode::Unauthorized
+    );
+    require!(executor_share_bps <= 10_000, ErrorCode::InvalidFeeBps);
+
+    let fund_state = &mut ctx.accounts.fund_state;
+    fund_state.permissionless_execution = enabled;
+    fund_state.executor_share_bps = executor_share_bps;
+    Ok(())
+}
+
+pub(crate) fn require_order_executor(
+    config: &Account<GlobalConfig>,
+    keeper_record: Option<&Account<KeeperRecord>>,
+    fund_state: &FundState,
+    executor: &Pubkey,
+    fund_key: &Pubkey,
+) -> Result<bool> {
+    let registered = is_keeper(config, keeper_record, executor, fund_key, KEEPER_SCOPE_ORDERS);
+    require!(
+        registered || fund_state.permissionless_execution,
+        ErrorCode::Unauthorized
+    );
+    Ok(registered)
+}
+
+pub(crate) fn pay_price_improvement<'info>(
+    fund_key: Pubkey,
+    fund_vault: &AccountInfo<'info>,
+    executor: &AccountInfo<'info>,
+    expected_out: u64,
+    filled_out: u64,
+    surplus_lamports: u64,
+    share_lamports: u64,
+) -> Result<u64> {
+    let rent_min = Rent::get()?.minimum_balance(fund_vault.data_len());
+    let available = fund_vault.lamports().saturating_sub(rent_min);
+    let share = share_lamports.min(available);
+    if share == 0 {
+        return Ok(0);
+    }
+    move_lamports(fund_vault, executor, share)?;
+    emit!(PriceImprovementShared {
+        fund: fund_key,
+        executor: executor.key(),
+        expected_out,
+        filled_out,
+        surplus_lamports,
+        executor_lamports: share,
+    });
+    Ok(share)
+}
+
+#[derive(Accounts)]
+pub struct SetPermissionlessExecution<'info> {
+    pub manager: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"fund", fund_state.config.as_ref(), fund_state.creator.as_ref(), fund_state.fund_id.to_le_bytes().as_ref()],
+        bump = fund_state.bump
+    )]
+    pub fund_state: Account<'info, FundState>,
+}
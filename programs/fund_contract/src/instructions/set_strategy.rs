@@ -16,6 +16,10 @@ pub fn set_strategy<'info>(
     allocations: Vec<StrategyAllocationInput>,
     rebalance_threshold_bps: u16,
     rebalance_cooldown_secs: i64,
+    sol_weight_bps: u16,
+    liquidity_reserve_bps: u16,
+    glide_duration_secs: i64,
+    effective_ts: i64,
 ) -> Result<()> {
     require!(
         ctx.accounts.fund_state.manager == ctx.accounts.manager.key(),
@@ -25,27 +29,11 @@ pub fn set_strategy<'info>(
         ctx.accounts.fund_state.fund_type == FUND_TYPE_STRATEGY,
         ErrorCode::InvalidFundType
     );
-    require!(!allocations.is_empty(), ErrorCode::InvalidStrategy);
-    require!(allocations.len() <= MAX_STRATEGY_TOKENS, ErrorCode::InvalidStrategy);
     require!(rebalance_threshold_bps <= 10_000, ErrorCode::InvalidFeeBps);
     require!(rebalance_cooldown_secs > 0, ErrorCode::InvalidStrategy);
-    require!(
-        ctx.accounts.fund_state.enabled_token_count as usize == allocations.len(),
-        ErrorCode::InvalidStrategy
-    );
-
-    let mut sum: u32 = 0;
-    let mut seen: Vec<Pubkey> = Vec::with_capacity(allocations.len());
-    for alloc in allocations.iter() {
-        require!(alloc.weight_bps > 0, ErrorCode::InvalidStrategy);
-        sum = sum
-            .checked_add(alloc.weight_bps as u32)
-            .ok_or(ErrorCode::MathOverflow)?;
-        require!(!seen.contains(&alloc.mint), ErrorCode::InvalidStrategy);
-        seen.push(alloc.mint);
-    }
-    require!(sum == 10_000, ErrorCode::InvalidStrategy);
-
+    require!(sol_weight_bps <= 10_000, ErrorCode::InvalidFeeBps);
+    require!(liquidity_reserve_bps <= 10_000, ErrorCode::InvalidFeeBps);
+    require!(glide_duration_secs >= 0, ErrorCode::InvalidStrategy);
     require!(
         ctx.remaining_accounts.len() == allocations.len(),
         ErrorCode::InvalidRemainingAccounts
@@ -60,21 +48,136 @@ pub fn set_strategy<'info>(
         require!(whitelist.mint == alloc.mint, ErrorCode::InvalidTokenVault);
     }
 
+    let mut entries: Vec<StrategyAllocationInput> = ctx
+        .accounts
+        .strategy
+        .draft_allocations
+        .iter()
+        .map(|draft| StrategyAllocationInput {
+            mint: draft.mint,
+            weight_bps: draft.weight_bps,
+        })
+        .collect();
+    entries.extend(allocations);
+    require!(!entries.is_empty(), ErrorCode::InvalidStrategy);
+    require!(entries.len() <= MAX_STRATEGY_TOKENS, ErrorCode::InvalidStrategy);
+    require!(
+        ctx.accounts.fund_state.enabled_token_count as usize == entries.len(),
+        ErrorCode::InvalidStrategy
+    );
+
+    let is_new = ctx.accounts.strategy.allocations.is_empty();
+    let mut sum: u32 = sol_weight_bps as u32;
+    let mut seen: Vec<Pubkey> = Vec::with_capacity(entries.len());
+    for alloc in entries.iter() {
+        require!(alloc.weight_bps > 0 || !is_new, ErrorCode::InvalidStrategy);
+        sum = sum
+            .checked_add(alloc.weight_bps as u32)
+            .ok_or(ErrorCode::MathOverflow)?;
+        require!(!seen.contains(&alloc.mint), ErrorCode::InvalidStrategy);
+        seen.push(alloc.mint);
+    }
+    require!(sum == 10_000, ErrorCode::InvalidStrategy);
+
+    let now = Clock::get()?.unix_timestamp;
+    let withdraw_timelock_secs = ctx.accounts.fund_state.withdraw_timelock_secs;
     let config = &mut ctx.accounts.strategy;
-    config.fund = ctx.accounts.fund_state.key();
-    config.allocation_count = allocations.len() as u8;
-    config.allocations = [StrategyAllocation::default(); MAX_STRATEGY_TOKENS];
-    for (idx, alloc) in allocations.iter().enumerate() {
-        config.allocations[idx] = StrategyAllocation {
-            mint: alloc.mint,
-            weight_bps: alloc.weight_bps,
-        };
+    config.draft_allocations = Vec::new();
+
+    if is_new {
+        config.fund = ctx.accounts.fund_state.key();
+        config.allocations = entries
+            .iter()
+            .map(|alloc| StrategyAllocation {
+                mint: alloc.mint,
+                weight_bps: alloc.weight_bps,
+                start_weight_bps: alloc.weight_bps,
+            })
+            .collect();
+        config.sol_weight_bps = sol_weight_bps;
+        config.start_sol_weight_bps = sol_weight_bps;
+        config.liquidity_reserve_bps = liquidity_reserve_bps;
+        config.rebalance_threshold_bps = rebalance_threshold_bps;
+        config.rebalance_cooldown_secs = rebalance_cooldown_secs;
+        config.glide_start_ts = now;
+        config.glide_end_ts = now;
+        config.last_rebalance_ts = now;
+        config.bump = ctx.bumps.strategy;
+    } else {
+        let earliest_effective_ts = now
+            .checked_add(withdraw_timelock_secs)
+            .ok_or(ErrorCode::MathOverflow)?;
+        require!(effective_ts >= earliest_effective_ts, ErrorCode::StrategyTimelock);
+
+        config.activate_pending(now)?;
+
+        for alloc in entries.iter() {
+            if config.allocations.iter().any(|a| a.mint == alloc.mint) {
+                continue;
+            }
+            require!(
+                config.allocations.len() < MAX_STRATEGY_TOKENS,
+                ErrorCode::InvalidStrategy
+            );
+            config.allocations.push(StrategyAllocation {
+                mint: alloc.mint,
+                weight_bps: 0,
+                start_weight_bps: 0,
+            });
+        }
+
+        config.pending_allocations = entries
+            .iter()
+            .map(|alloc| StrategyAllocation {
+                mint: alloc.mint,
+                weight_bps: alloc.weight_bps,
+                start_weight_bps: 0,
+            })
+            .collect();
+        config.pending_sol_weight_bps = sol_weight_bps;
+        config.pending_liquidity_reserve_bps = liquidity_reserve_bps;
+        config.pending_rebalance_threshold_bps = rebalance_threshold_bps;
+        config.pending_rebalance_cooldown_secs = rebalance_cooldown_secs;
+        config.pending_glide_duration_secs = glide_duration_secs;
+        config.pending_effective_ts = effective_ts;
+        config.has_pending_update = true;
     }
-    config.rebalance_threshold_bps = rebalance_threshold_bps;
-    config.rebalance_cooldown_secs = rebalance_cooldown_secs;
-    config.last_rebalance_ts = Clock::get()?.unix_timestamp;
-    config.bump = ctx.bumps.strategy;
 
+    resize_strategy(
+        &ctx.accounts.strategy,
+        &ctx.accounts.manager,
+        &ctx.accounts.system_program,
+    )
+}
+
+pub(crate) fn resize_strategy<'info>(
+    strategy: &Account<'info, Strategy>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    let space = 8 + Strategy::space(
+        strategy.allocations.len(),
+        strategy.pending_allocations.len(),
+        strategy.draft_allocations.len(),
+    );
+    let strategy_info = strategy.to_account_info();
+    if space <= strategy_info.data_len() {
+        return Ok(());
+    }
+
+    let rent_lamports = Rent::get()?.minimum_balance(space);
+    let top_up = rent_lamports.saturating_sub(strategy_info.lamports());
+    if top_up > 0 {
+        let cpi_ctx = CpiContext::new(
+            system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: payer.to_account_info(),
+                to: strategy_info.clone(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_ctx, top_up)?;
+    }
+    strategy_info.resize(space)?;
     Ok(())
 }
 
@@ -84,14 +187,14 @@ pub struct SetStrategy<'info> {
     pub manager: Signer<'info>,
     #[account(
         mut,
-        seeds = [b"fund", fund_state.config.as_ref(), fund_state.manager.as_ref(), fund_state.fund_id.to_le_bytes().as_ref()],
+        seeds = [b"fund", fund_state.config.as_ref(), fund_state.creator.as_ref(), fund_state.fund_id.to_le_bytes().as_ref()],
         bump = fund_state.bump,
     )]
     pub fund_state: Account<'info, FundState>,
     #[account(
-        init,
+        init_if_needed,
         payer = manager,
-        space = 8 + Strategy::LEN,
+        space = 8 + Strategy::space(0, 0, 0),
         seeds = [b"strategy", fund_state.key().as_ref()],
         bump
     )]
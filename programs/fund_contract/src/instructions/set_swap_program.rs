@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::state::global_config::{GlobalConfig, MAX_SWAP_PROGRAMS};
+
+pub fn set_swap_program(
+    ctx: Context<SetSwapProgram>,
+    swap_program: Pubkey,
+    allowed: bool,
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let position = config
+        .swap_programs
+        .iter()
+        .position(|program| *program == swap_program);
+    match (position, allowed) {
+        (Some(idx), false) => {
+            config.swap_programs.remove(idx);
+        }
+        (None, true) => {
+            require!(
+                swap_program != Pubkey::default() && swap_program != crate::ID,
+                ErrorCode::InvalidSwapProgram
+            );
+            require!(
+                config.swap_programs.len() < MAX_SWAP_PROGRAMS,
+                ErrorCode::TooManySwapPrograms
+            );
+            config.swap_programs.push(swap_program);
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(config_id: u64)]
+pub struct SetSwapProgram<'info> {
+    #[account(
+        mut,
+        seeds = [b"config", config_id.to_le_bytes().as_ref()],
+        bump = config.bump,
+        has_one = admin
+    )]
+    pub config: Account<'info, GlobalConfig>,
+    pub admin: Signer<'info>,
+}
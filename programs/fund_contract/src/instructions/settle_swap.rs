@@ -2,7 +2,9 @@ use anchor_lang::prelude::*;
 use anchor_spl::token::TokenAccount;
 
 use crate::errors::ErrorCode;
+use crate::instructions::set_fund_role::require_fund_role;
 use crate::state::fund::{FundState, FundVault, FUND_TYPE_TRADING};
+use crate::state::fund_roles::{FundRoles, ROLE_TRADER};
 use crate::state::global_config::GlobalConfig;
 use crate::state::trading::Trading;
 use crate::state::whitelist::FundWhitelist;
@@ -15,10 +17,14 @@ pub fn settle_swap<'info>(
         ctx.accounts.fund_state.fund_type == FUND_TYPE_TRADING,
         ErrorCode::InvalidFundType
     );
-    require!(
-        ctx.accounts.fund_state.manager == ctx.accounts.manager.key(),
-        ErrorCode::Unauthorized
-    );
+    require!(!ctx.accounts.fund_state.paused, ErrorCode::FundPaused);
+    require_fund_role(
+        ctx.accounts.fund_state.key(),
+        &ctx.accounts.fund_state,
+        ctx.accounts.fund_roles.as_ref(),
+        &ctx.accounts.manager.key(),
+        ROLE_TRADER,
+    )?;
     require!(
         ctx.accounts.trading.output_mint == ctx.accounts.output_whitelist.mint,
         ErrorCode::InvalidTokenVault
@@ -74,6 +80,7 @@ pub fn settle_swap<'info>(
     ctx.accounts.trading.snapshot_sol = 0;
     ctx.accounts.trading.snapshot_output = 0;
     ctx.accounts.trading.output_mint = Pubkey::default();
+    ctx.accounts.fund_state.mark_holdings_changed()?;
 
     Ok(())
 }
@@ -89,7 +96,7 @@ pub struct SettleSwap<'info> {
     pub config: Account<'info, GlobalConfig>,
     #[account(
         mut,
-        seeds = [b"fund", config.key().as_ref(), fund_state.manager.as_ref(), fund_state.fund_id.to_le_bytes().as_ref()],
+        seeds = [b"fund", config.key().as_ref(), fund_state.creator.as_ref(), fund_state.fund_id.to_le_bytes().as_ref()],
         bump = fund_state.bump,
         has_one = config
     )]
@@ -110,4 +117,9 @@ pub struct SettleSwap<'info> {
     pub output_whitelist: Account<'info, FundWhitelist>,
     #[account(mut)]
     pub output_token_vault: Account<'info, TokenAccount>,
+    #[account(
+        seeds = [b"fund_roles", fund_state.key().as_ref()],
+        bump = fund_roles.bump
+    )]
+    pub fund_roles: Option<Account<'info, FundRoles>>,
 }
@@ -3,16 +3,22 @@ use anchor_spl::token::{self, CloseAccount, Token, TokenAccount};
 use anchor_spl::token::spl_token::native_mint;
 
 use crate::errors::ErrorCode;
+use crate::instructions::register_keeper::require_keeper;
 use crate::state::fund::{FundState, FundVault};
 use crate::state::global_config::GlobalConfig;
+use crate::state::keeper_record::{KeeperRecord, KEEPER_SCOPE_SWEEP};
 
 pub fn sweep_wsol<'info>(
     ctx: Context<'_, '_, 'info, 'info, SweepWsol<'info>>,
 ) -> Result<()> {
-    require!(
-        ctx.accounts.executor.key() == ctx.accounts.config.keeper,
-        ErrorCode::Unauthorized
-    );
+    require_keeper(
+        &ctx.accounts.config,
+        ctx.accounts.keeper_record.as_ref(),
+        &ctx.accounts.executor.key(),
+        &ctx.accounts.fund_state.key(),
+        KEEPER_SCOPE_SWEEP,
+    )?;
+    require!(!ctx.accounts.fund_state.paused, ErrorCode::FundPaused);
 
     let fund_key = ctx.accounts.fund_state.key();
     let expected_wsol = anchor_spl::associated_token::get_associated_token_address(
@@ -51,32 +57,46 @@ pub fn sweep_wsol<'info>(
     let signer_seeds: &[&[u8]] = &[
         b"fund",
         config_key.as_ref(),
-        ctx.accounts.fund_state.manager.as_ref(),
+        ctx.accounts.fund_state.creator.as_ref(),
         fund_id_bytes.as_ref(),
         &[ctx.accounts.fund_state.bump],
     ];
     let signer_seeds_set = [signer_seeds];
 
+    unwrap_wsol(
+        &ctx.accounts.token_program.to_account_info(),
+        &ctx.accounts.fund_wsol_vault.to_account_info(),
+        &ctx.accounts.fund_vault.to_account_info(),
+        &ctx.accounts.fund_state.to_account_info(),
+        &signer_seeds_set,
+    )
+}
+
+pub(crate) fn unwrap_wsol<'info>(
+    token_program: &AccountInfo<'info>,
+    wsol_vault: &AccountInfo<'info>,
+    fund_vault: &AccountInfo<'info>,
+    fund_state: &AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
     let sync_ctx = CpiContext::new(
-        ctx.accounts.token_program.to_account_info(),
+        token_program.clone(),
         token::SyncNative {
-            account: ctx.accounts.fund_wsol_vault.to_account_info(),
+            account: wsol_vault.clone(),
         },
     );
     token::sync_native(sync_ctx)?;
 
     let close_ctx = CpiContext::new_with_signer(
-        ctx.accounts.token_program.to_account_info(),
+        token_program.clone(),
         CloseAccount {
-            account: ctx.accounts.fund_wsol_vault.to_account_info(),
-            destination: ctx.accounts.fund_vault.to_account_info(),
-            authority: ctx.accounts.fund_state.to_account_info(),
+            account: wsol_vault.clone(),
+            destination: fund_vault.clone(),
+            authority: fund_state.clone(),
         },
-        &signer_seeds_set,
+        signer_seeds,
     );
-    token::close_account(close_ctx)?;
-
-    Ok(())
+    token::close_account(close_ctx)
 }
 
 #[derive(Accounts)]
@@ -88,7 +108,7 @@ pub struct SweepWsol<'info> {
     )]
     pub config: Account<'info, GlobalConfig>,
     #[account(
-        seeds = [b"fund", config.key().as_ref(), fund_state.manager.as_ref(), fund_state.fund_id.to_le_bytes().as_ref()],
+        seeds = [b"fund", config.key().as_ref(), fund_state.creator.as_ref(), fund_state.fund_id.to_le_bytes().as_ref()],
         bump = fund_state.bump,
         has_one = config
     )]
@@ -102,5 +122,10 @@ pub struct SweepWsol<'info> {
     /// CHECK: validated as ATA for fund_state + WSOL
     #[account(mut)]
     pub fund_wsol_vault: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"keeper", config.key().as_ref(), executor.key().as_ref()],
+        bump = keeper_record.bump
+    )]
+    pub keeper_record: Option<Account<'info, KeeperRecord>>,
     pub token_program: Program<'info, Token>,
 }
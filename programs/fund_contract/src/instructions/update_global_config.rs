@@ -1,7 +1,7 @@
 use anchor_lang::prelude::*;
 
 use crate::errors::ErrorCode;
-use crate::state::global_config::GlobalConfig;
+use crate::state::global_config::{GlobalConfig, SensitiveConfig};
 
 pub fn update_global_config(
     ctx: Context<UpdateGlobalConfig>,
@@ -16,6 +16,7 @@ pub fn update_global_config(
     min_manager_deposit_lamports: u64,
     min_withdraw_timelock_secs: i64,
     max_withdraw_timelock_secs: i64,
+    config_timelock_secs: i64,
 ) -> Result<()> {
     require!(deposit_fee_bps <= 10_000, ErrorCode::InvalidFeeBps);
     require!(withdraw_fee_bps <= 10_000, ErrorCode::InvalidFeeBps);
@@ -27,16 +28,34 @@ pub fn update_global_config(
         max_withdraw_timelock_secs >= min_withdraw_timelock_secs,
         ErrorCode::InvalidTimelock
     );
+    require!(config_timelock_secs >= 0, ErrorCode::InvalidTimelock);
 
+    let now = Clock::get()?.unix_timestamp;
     let config = &mut ctx.accounts.config;
+    config.activate_pending(now);
+
+    let update = SensitiveConfig {
+        sol_usd_pyth_feed,
+        pyth_program_id,
+        deposit_fee_bps,
+        withdraw_fee_bps,
+        trade_fee_bps,
+        max_manager_fee_bps,
+        max_slippage_bps,
+        config_timelock_secs,
+    };
+    if config.is_sensitive_update(&update) {
+        let effective_ts = now
+            .checked_add(config.config_timelock_secs)
+            .ok_or(ErrorCode::MathOverflow)?;
+        config.stage_update(&update, effective_ts);
+        config.activate_pending(now);
+    } else {
+        config.apply_update(&update);
+        config.clear_pending();
+    }
+
     config.fee_treasury = ctx.accounts.fee_treasury.key();
-    config.sol_usd_pyth_feed = sol_usd_pyth_feed;
-    config.pyth_program_id = pyth_program_id;
-    config.deposit_fee_bps = deposit_fee_bps;
-    config.withdraw_fee_bps = withdraw_fee_bps;
-    config.trade_fee_bps = trade_fee_bps;
-    config.max_manager_fee_bps = max_manager_fee_bps;
-    config.max_slippage_bps = max_slippage_bps;
     config.min_manager_deposit_lamports = min_manager_deposit_lamports;
     config.min_withdraw_timelock_secs = min_withdraw_timelock_secs;
     config.max_withdraw_timelock_secs = max_withdraw_timelock_secs;
@@ -0,0 +1,189 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::spl_token::native_mint;
+
+use crate::errors::ErrorCode;
+use crate::instructions::rebalance_strategy::{
+    exceeds_threshold, load_strategy_holding, read_token_amount,
+};
+use crate::instructions::register_keeper::require_keeper;
+use crate::oracle::load_pyth_price;
+use crate::state::fund::{FundState, FundVault};
+use crate::state::global_config::GlobalConfig;
+use crate::state::keeper_record::{KeeperRecord, KEEPER_SCOPE_REBALANCE};
+use crate::state::rebalance_session::{
+    RebalanceSession, REBALANCE_SESSION_MAX_SECS, SESSION_PHASE_CLOSING,
+    SESSION_PHASE_COMPLETE, SESSION_PHASE_OPENING, SESSION_PHASE_TRADING,
+};
+use crate::state::strategy::Strategy;
+
+pub fn value_rebalance_batch<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ValueRebalanceBatch<'info>>,
+) -> Result<()> {
+    require_keeper(
+        &ctx.accounts.config,
+        ctx.accounts.keeper_record.as_ref(),
+        &ctx.accounts.executor.key(),
+        &ctx.accounts.fund_state.key(),
+        KEEPER_SCOPE_REBALANCE,
+    )?;
+    require!(!ctx.accounts.fund_state.paused, ErrorCode::FundPaused);
+
+    let now = Clock::get()?.unix_timestamp;
+    let session = &mut ctx.accounts.session;
+    let session_deadline = session
+        .opened_ts
+        .checked_add(REBALANCE_SESSION_MAX_SECS)
+        .ok_or(ErrorCode::MathOverflow)?;
+    require!(now <= session_deadline, ErrorCode::RebalanceSessionExpired);
+    require!(
+        session.phase != SESSION_PHASE_COMPLETE,
+        ErrorCode::InvalidSessionPhase
+    );
+    if session.phase == SESSION_PHASE_TRADING {
+        session.phase = SESSION_PHASE_CLOSING;
+        session.cursor = 0;
+    }
+
+    require!(
+        ctx.remaining_accounts.len() % 3 == 0,
+        ErrorCode::InvalidRemainingAccounts
+    );
+    let start = session.cursor as usize;
+    let end = start
+        .checked_add(ctx.remaining_accounts.len() / 3)
+        .ok_or(ErrorCode::MathOverflow)?;
+    require!(end <= session.entries.len(), ErrorCode::InvalidRemainingAccounts);
+
+    let sol_price_info = &ctx.accounts.sol_price_feed;
+    require!(
+        sol_price_info.key == &ctx.accounts.config.sol_usd_pyth_feed,
+        ErrorCode::InvalidOracle
+    );
+    require!(
+        sol_price_info.owner == &ctx.accounts.config.pyth_program_id,
+        ErrorCode::InvalidOracle
+    );
+    let sol_price = load_pyth_price(sol_price_info)?;
+
+    let fund_key = ctx.accounts.fund_state.key();
+    for (offset, triplet) in ctx.remaining_accounts.chunks(3).enumerate() {
+        let entry = &mut session.entries[start + offset];
+        let holding = load_strategy_holding(
+            entry.mint,
+            entry.weight_bps,
+            &fund_key,
+            &ctx.accounts.config.pyth_program_id,
+            triplet,
+            &sol_price,
+        )?;
+        entry.value_lamports =
+            u64::try_from(holding.value_lamports).map_err(|_| ErrorCode::MathOverflow)?;
+    }
+    session.cursor = end as u16;
+    if end < session.entries.len() {
+        return Ok(());
+    }
+
+    let expected_wsol_vault =
+        anchor_spl::associated_token::get_associated_token_address(&fund_key, &native_mint::ID);
+    require!(
+        expected_wsol_vault == ctx.accounts.fund_wsol_vault.key(),
+        ErrorCode::InvalidOrderVault
+    );
+    let wsol_amount = if ctx.accounts.fund_wsol_vault.to_account_info().data_is_empty() {
+        0
+    } else {
+        read_token_amount(&ctx.accounts.fund_wsol_vault.to_account_info())?
+    };
+    let sol_lamports = ctx
+        .accounts
+        .fund_vault
+        .to_account_info()
+        .lamports()
+        .checked_add(wsol_amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let mut nav_lamports: i128 = sol_lamports as i128;
+    for entry in session.entries.iter() {
+        nav_lamports = nav_lamports
+            .checked_add(entry.value_lamports as i128)
+            .ok_or(ErrorCode::MathOverflow)?;
+    }
+    require!(nav_lamports > 0, ErrorCode::InvalidNav);
+
+    let threshold_bps = ctx.accounts.strategy.rebalance_threshold_bps;
+    let mut out_of_band = exceeds_threshold(
+        sol_lamports as i128,
+        nav_lamports,
+        session.sol_weight_bps,
+        threshold_bps,
+    )?;
+    for entry in session.entries.iter() {
+        if exceeds_threshold(
+            entry.value_lamports as i128,
+            nav_lamports,
+            entry.weight_bps,
+            threshold_bps,
+        )? {
+            out_of_band = true;
+        }
+    }
+
+    if session.phase == SESSION_PHASE_OPENING {
+        require!(out_of_band, ErrorCode::RebalanceNotNeeded);
+        session.nav_lamports =
+            u64::try_from(nav_lamports).map_err(|_| ErrorCode::MathOverflow)?;
+        session.phase = SESSION_PHASE_TRADING;
+        return Ok(());
+    }
+
+    if out_of_band {
+        session.phase = SESSION_PHASE_TRADING;
+        session.cursor = 0;
+        return Ok(());
+    }
+    session.phase = SESSION_PHASE_COMPLETE;
+    ctx.accounts.strategy.last_rebalance_ts = now;
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ValueRebalanceBatch<'info> {
+    pub executor: Signer<'info>,
+    #[account(
+        seeds = [b"config", config.config_id.to_le_bytes().as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, GlobalConfig>,
+    #[account(
+        seeds = [b"fund", config.key().as_ref(), fund_state.creator.as_ref(), fund_state.fund_id.to_le_bytes().as_ref()],
+        bump = fund_state.bump,
+        has_one = config
+    )]
+    pub fund_state: Account<'info, FundState>,
+    #[account(
+        seeds = [b"vault", fund_state.key().as_ref()],
+        bump = fund_state.vault_bump
+    )]
+    pub fund_vault: Account<'info, FundVault>,
+    #[account(
+        mut,
+        seeds = [b"strategy", fund_state.key().as_ref()],
+        bump = strategy.bump
+    )]
+    pub strategy: Account<'info, Strategy>,
+    #[account(
+        mut,
+        seeds = [b"rebalance_session", fund_state.key().as_ref()],
+        bump = session.bump
+    )]
+    pub session: Account<'info, RebalanceSession>,
+    /// CHECK: ATA for fund_state + WSOL, counted as SOL
+    pub fund_wsol_vault: UncheckedAccount<'info>,
+    /// CHECK: Pyth price feed for SOL/USD
+    pub sol_price_feed: AccountInfo<'info>,
+    #[account(
+        seeds = [b"keeper", config.key().as_ref(), executor.key().as_ref()],
+        bump = keeper_record.bump
+    )]
+    pub keeper_record: Option<Account<'info, KeeperRecord>>,
+}
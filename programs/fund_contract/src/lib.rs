@@ -1,7 +1,11 @@
+#![allow(clippy::too_many_arguments)]
+
 use anchor_lang::prelude::*;
 
 pub mod errors;
+pub mod events;
 pub mod instructions;
+pub mod oracle;
 pub mod state;
 
 use instructions::*;
@@ -26,6 +30,7 @@ pub mod fund_contract {
         min_manager_deposit_lamports: u64,
         min_withdraw_timelock_secs: i64,
         max_withdraw_timelock_secs: i64,
+        config_timelock_secs: i64,
     ) -> Result<()> {
         instructions::initialize_global_config::initialize_global_config(
             ctx,
@@ -41,6 +46,7 @@ pub mod fund_contract {
             min_manager_deposit_lamports,
             min_withdraw_timelock_secs,
             max_withdraw_timelock_secs,
+            config_timelock_secs,
         )
     }
 
@@ -57,6 +63,7 @@ pub mod fund_contract {
         min_manager_deposit_lamports: u64,
         min_withdraw_timelock_secs: i64,
         max_withdraw_timelock_secs: i64,
+        config_timelock_secs: i64,
     ) -> Result<()> {
         instructions::update_global_config::update_global_config(
             ctx,
@@ -71,6 +78,7 @@ pub mod fund_contract {
             min_manager_deposit_lamports,
             min_withdraw_timelock_secs,
             max_withdraw_timelock_secs,
+            config_timelock_secs,
         )
     }
 
@@ -91,6 +99,89 @@ pub mod fund_contract {
         instructions::revoke_keeper::revoke_keeper(ctx)
     }
 
+    pub fn propose_admin(
+        ctx: Context<ProposeAdmin>,
+        config_id: u64,
+        new_admin: Pubkey,
+    ) -> Result<()> {
+        let _ = config_id;
+        instructions::propose_admin::propose_admin(ctx, new_admin)
+    }
+
+    pub fn accept_admin(
+        ctx: Context<AcceptAdmin>,
+        config_id: u64,
+    ) -> Result<()> {
+        let _ = config_id;
+        instructions::accept_admin::accept_admin(ctx)
+    }
+
+    pub fn apply_config_update(
+        ctx: Context<ApplyConfigUpdate>,
+        config_id: u64,
+    ) -> Result<()> {
+        let _ = config_id;
+        instructions::apply_config_update::apply_config_update(ctx)
+    }
+
+    pub fn register_keeper(
+        ctx: Context<RegisterKeeper>,
+        config_id: u64,
+        keeper: Pubkey,
+        scopes: u8,
+        fund: Pubkey,
+    ) -> Result<()> {
+        let _ = config_id;
+        instructions::register_keeper::register_keeper(ctx, keeper, scopes, fund)
+    }
+
+    pub fn remove_keeper(
+        ctx: Context<RemoveKeeper>,
+        config_id: u64,
+    ) -> Result<()> {
+        let _ = config_id;
+        instructions::remove_keeper::remove_keeper(ctx)
+    }
+
+    pub fn set_max_keeper_tip(
+        ctx: Context<SetMaxKeeperTip>,
+        config_id: u64,
+        max_keeper_tip_lamports: u64,
+    ) -> Result<()> {
+        let _ = config_id;
+        instructions::set_max_keeper_tip::set_max_keeper_tip(ctx, max_keeper_tip_lamports)
+    }
+
+    pub fn set_keeper_tip(
+        ctx: Context<SetKeeperTip>,
+        keeper_tip_lamports: u64,
+        keeper_tip_bps: u16,
+    ) -> Result<()> {
+        instructions::set_keeper_tip::set_keeper_tip(ctx, keeper_tip_lamports, keeper_tip_bps)
+    }
+
+    pub fn set_permissionless_execution(
+        ctx: Context<SetPermissionlessExecution>,
+        enabled: bool,
+        executor_share_bps: u16,
+    ) -> Result<()> {
+        instructions::set_permissionless_execution::set_permissionless_execution(
+            ctx,
+            enabled,
+            executor_share_bps,
+        )
+    }
+
+    pub fn set_swap_program(
+        ctx: Context<SetSwapProgram>,
+        config_id: u64,
+        swap_program: Pubkey,
+        allowed: bool,
+    ) -> Result<()> {
+        let _ = config_id;
+        instructions::set_swap_program::set_swap_program(ctx, swap_program, allowed)
+    }
+
     pub fn initialize_fund(
         ctx: Context<InitializeFund>,
         fund_id: u64,
@@ -155,15 +246,30 @@ pub mod fund_contract {
         allocations: Vec<StrategyAllocationInput>,
         rebalance_threshold_bps: u16,
         rebalance_cooldown_secs: i64,
+        sol_weight_bps: u16,
+        liquidity_reserve_bps: u16,
+        glide_duration_secs: i64,
+        effective_ts: i64,
     ) -> Result<()> {
         instructions::set_strategy::set_strategy(
             ctx,
             allocations,
             rebalance_threshold_bps,
             rebalance_cooldown_secs,
+            sol_weight_bps,
+            liquidity_reserve_bps,
+            glide_duration_secs,
+            effective_ts,
         )
     }
 
+    pub fn append_strategy_allocations<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AppendStrategyAllocations<'info>>,
+        allocations: Vec<StrategyAllocationInput>,
+    ) -> Result<()> {
+        instructions::append_strategy_allocations::append_strategy_allocations(ctx, allocations)
+    }
+
     pub fn request_withdraw<'info>(
         ctx: Context<'_, '_, 'info, 'info, RequestWithdraw<'info>>,
         shares: u64,
@@ -205,6 +311,9 @@ pub mod fund_contract {
         limit_price: i64,
         price_expo: i32,
         expiry_ts: i64,
+        order_type: u8,
+        stop_price: i64,
+        trail_bps: u16,
     ) -> Result<()> {
         instructions::create_limit_order::create_limit_order(
             ctx,
@@ -214,6 +323,9 @@ pub mod fund_contract {
             limit_price,
             price_expo,
             expiry_ts,
+            order_type,
+            stop_price,
+            trail_bps,
         )
     }
 
@@ -225,6 +337,11 @@ pub mod fund_contract {
         interval_secs: i64,
         min_out: u64,
         expiry_ts: i64,
+        max_slippage_bps: u16,
+        band_lower_price: i64,
+        band_upper_price: i64,
+        price_expo: i32,
+        mode: u8,
     ) -> Result<()> {
         instructions::create_dca_order::create_dca_order(
             ctx,
@@ -234,16 +351,27 @@ pub mod fund_contract {
             interval_secs,
             min_out,
             expiry_ts,
+            max_slippage_bps,
+            band_lower_price,
+            band_upper_price,
+            price_expo,
+            mode,
         )
     }
 
     pub fn execute_limit_order<'info>(
         ctx: Context<'_, '_, 'info, 'info, ExecuteLimitOrder<'info>>,
         order_id: u64,
+        fill_amount: u64,
         swap_data: Vec<u8>,
     ) -> Result<()> {
         let _ = order_id;
-        instructions::execute_limit_order::execute_limit_order(ctx, swap_data)
+        instructions::execute_limit_order::execute_limit_order(ctx, fill_amount, swap_data)
+    }
+
+    pub fn poke_trailing_stop(ctx: Context<PokeTrailingStop>, order_id: u64) -> Result<()> {
+        let _ = order_id;
+        instructions::poke_trailing_stop::poke_trailing_stop(ctx)
     }
 
     pub fn execute_dca_order<'info>(
@@ -271,6 +399,157 @@ pub mod fund_contract {
         instructions::cancel_dca_order::cancel_dca_order(ctx)
     }
 
+    pub fn amend_limit_order<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AmendLimitOrder<'info>>,
+        order_id: u64,
+        amount_in: u64,
+        min_out: u64,
+        limit_price: i64,
+        stop_price: i64,
+        trail_bps: u16,
+        expiry_ts: i64,
+    ) -> Result<()> {
+        let _ = order_id;
+        instructions::amend_limit_order::amend_limit_order(
+            ctx,
+            amount_in,
+            min_out,
+            limit_price,
+            stop_price,
+            trail_bps,
+            expiry_ts,
+        )
+    }
+
+    pub fn amend_dca_order<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AmendDcaOrder<'info>>,
+        order_id: u64,
+        total_amount: u64,
+        slice_amount: u64,
+        interval_secs: i64,
+        min_out: u64,
+        expiry_ts: i64,
+        band_lower_price: i64,
+        band_upper_price: i64,
+    ) -> Result<()> {
+        let _ = order_id;
+        instructions::amend_dca_order::amend_dca_order(
+            ctx,
+            total_amount,
+            slice_amount,
+            interval_secs,
+            min_out,
+            expiry_ts,
+            band_lower_price,
+            band_upper_price,
+        )
+    }
+
+    pub fn expire_limit_order<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExpireLimitOrder<'info>>,
+        order_id: u64,
+    ) -> Result<()> {
+        let _ = order_id;
+        instructions::expire_limit_order::expire_limit_order(ctx)
+    }
+
+    pub fn expire_dca_order<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExpireDcaOrder<'info>>,
+        order_id: u64,
+    ) -> Result<()> {
+        let _ = order_id;
+        instructions::expire_dca_order::expire_dca_order(ctx)
+    }
+
+    pub fn refresh_nav<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RefreshNav<'info>>,
+    ) -> Result<()> {
+        instructions::refresh_nav::refresh_nav(ctx)
+    }
+
+    pub fn refresh_nav_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RefreshNavBatch<'info>>,
+        asset_count: u16,
+    ) -> Result<()> {
+        instructions::refresh_nav_batch::refresh_nav_batch(ctx, asset_count)
+    }
+
+    pub fn set_nav_snapshot_policy(
+        ctx: Context<SetNavSnapshotPolicy>,
+        required: bool,
+        max_age_slots: u64,
+    ) -> Result<()> {
+        instructions::set_nav_snapshot_policy::set_nav_snapshot_policy(
+            ctx,
+            required,
+            max_age_slots,
+        )
+    }
+
+    pub fn configure_nav_history(
+        ctx: Context<ConfigureNavHistory>,
+        interval_secs: i64,
+    ) -> Result<()> {
+        instructions::configure_nav_history::configure_nav_history(ctx, interval_secs)
+    }
+
+    pub fn get_nav<'info>(
+        ctx: Context<'_, '_, 'info, 'info, GetNav<'info>>,
+    ) -> Result<NavView> {
+        instructions::get_nav::get_nav(ctx)
+    }
+
+    pub fn add_fund_shares(ctx: Context<AddFundShares>) -> Result<()> {
+        instructions::add_fund_shares::add_fund_shares(ctx)
+    }
+
+    pub fn invest_in_fund<'info>(
+        ctx: Context<'_, '_, 'info, 'info, InvestInFund<'info>>,
+        amount_lamports: u64,
+        min_shares: u64,
+    ) -> Result<()> {
+        instructions::invest_in_fund::invest_in_fund(ctx, amount_lamports, min_shares)
+    }
+
+    pub fn request_fund_redemption<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RequestFundRedemption<'info>>,
+        shares: u64,
+    ) -> Result<()> {
+        instructions::request_fund_redemption::request_fund_redemption(ctx, shares)
+    }
+
+    pub fn complete_fund_redemption<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CompleteFundRedemption<'info>>,
+    ) -> Result<()> {
+        instructions::complete_fund_redemption::complete_fund_redemption(ctx)
+    }
+
+    pub fn cancel_fund_redemption<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CancelFundRedemption<'info>>,
+    ) -> Result<()> {
+        instructions::cancel_fund_redemption::cancel_fund_redemption(ctx)
+    }
+
+    pub fn set_fund_role(ctx: Context<SetFundRole>, member: Pubkey, roles: u8) -> Result<()> {
+        instructions::set_fund_role::set_fund_role(ctx, member, roles)
+    }
+
+    pub fn set_fund_paused(ctx: Context<SetFundPaused>, paused: bool) -> Result<()> {
+        instructions::set_fund_paused::set_fund_paused(ctx, paused)
+    }
+
+    pub fn propose_manager(
+        ctx: Context<ProposeManager>,
+        new_manager: Pubkey,
+        effective_ts: i64,
+    ) -> Result<()> {
+        instructions::propose_manager::propose_manager(ctx, new_manager, effective_ts)
+    }
+
+    pub fn accept_manager(ctx: Context<AcceptManager>) -> Result<()> {
+        instructions::accept_manager::accept_manager(ctx)
+    }
+
     pub fn rebalance_strategy<'info>(
         ctx: Context<'_, '_, 'info, 'info, RebalanceStrategy<'info>>,
         target_mint: Pubkey,
@@ -290,4 +569,40 @@ pub mod fund_contract {
     ) -> Result<()> {
         instructions::sweep_wsol::sweep_wsol(ctx)
     }
+
+    pub fn open_rebalance_session<'info>(
+        ctx: Context<'_, '_, 'info, 'info, OpenRebalanceSession<'info>>,
+    ) -> Result<()> {
+        instructions::open_rebalance_session::open_rebalance_session(ctx)
+    }
+
+    pub fn execute_rebalance_leg<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteRebalanceLeg<'info>>,
+        input_mint: Pubkey,
+        output_mint: Pubkey,
+        amount_in: u64,
+        min_out: u64,
+        swap_data: Vec<u8>,
+    ) -> Result<()> {
+        instructions::execute_rebalance_leg::execute_rebalance_leg(
+            ctx,
+            input_mint,
+            output_mint,
+            amount_in,
+            min_out,
+            swap_data,
+        )
+    }
+
+    pub fn value_rebalance_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ValueRebalanceBatch<'info>>,
+    ) -> Result<()> {
+        instructions::value_rebalance_batch::value_rebalance_batch(ctx)
+    }
+
+    pub fn close_rebalance_session<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CloseRebalanceSession<'info>>,
+    ) -> Result<()> {
+        instructions::close_rebalance_session::close_rebalance_session(ctx)
+    }
 }
@@ -0,0 +1,239 @@
+use anchor_lang::prelude::*;
+use pyth_sdk_solana::state::SolanaPriceAccount;
+
+use crate::errors::ErrorCode;
+
+pub(crate) const ORACLE_MAX_AGE_SECS: u64 = 60;
+pub(crate) const MAX_CONF_BPS: u64 = 200;
+pub(crate) const LAMPORTS_PER_SOL_U64: u64 = 1_000_000_000;
+
+#[derive(Clone, Copy)]
+pub(crate) struct PythPrice {
+    pub(crate) price: i64,
+    pub(crate) expo: i32,
+    pub(crate) publish_time: i64,
+    pub(crate) conf: u64,
+}
+
+pub(crate) fn load_pyth_price(price_info: &AccountInfo) -> Result<PythPrice> {
+    let feed = SolanaPriceAccount::account_info_to_feed(price_info)
+        .map_err(|_| ErrorCode::InvalidOracle)?;
+    let price = feed
+        .get_price_no_older_than(Clock::get()?.unix_timestamp, ORACLE_MAX_AGE_SECS)
+        .ok_or(ErrorCode::StaleOracle)?;
+    require!(price.price > 0, ErrorCode::InvalidOracle);
+    let price_u128 = price.price as u128;
+    let max_conf = price_u128
+        .checked_mul(MAX_CONF_BPS as u128)
+        .ok_or(ErrorCode::MathOverflow)?
+        .checked_div(10_000)
+        .ok_or(ErrorCode::MathOverflow)? as u64;
+    require!(price.conf <= max_conf, ErrorCode::InvalidOracleConfidence);
+    Ok(PythPrice {
+        price: price.price,
+        expo: price.expo,
+        publish_time: price.publish_time,
+        conf: price.conf,
+    })
+}
+
+pub(crate) fn confidence_bps(price: &PythPrice) -> Result<u64> {
+    require!(price.price > 0, ErrorCode::InvalidOracle);
+    let bps = (price.conf as u128)
+        .checked_mul(10_000)
+        .ok_or(ErrorCode::MathOverflow)?
+        .checked_add(price.price as u128 - 1)
+        .ok_or(ErrorCode::MathOverflow)?
+        .checked_div(price.price as u128)
+        .ok_or(ErrorCode::MathOverflow)?;
+    Ok(bps as u64)
+}
+
+pub(crate) fn apply_confidence_premium(expected_out: u64, conf_bps: u64) -> Result<u64> {
+    let factor = 10_000u128
+        .checked_add(conf_bps as u128)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let floor = (expected_out as u128)
+        .checked_mul(factor)
+        .ok_or(ErrorCode::MathOverflow)?
+        .checked_div(10_000)
+        .ok_or(ErrorCode::MathOverflow)?;
+    Ok(u64::try_from(floor).unwrap_or(u64::MAX))
+}
+
+pub(crate) fn scale_price(price: i64, expo: i32, target_expo: i32) -> Result<i128> {
+    let mut value = price as i128;
+    if expo == target_expo {
+        return Ok(value);
+    }
+    if expo > target_expo {
+        let scale = pow10_i128((expo - target_expo) as u32)?;
+        value = value.checked_mul(scale).ok_or(ErrorCode::MathOverflow)?;
+    } else {
+        let scale = pow10_i128((target_expo - expo) as u32)?;
+        value = value.checked_div(scale).ok_or(ErrorCode::MathOverflow)?;
+    }
+    Ok(value)
+}
+
+pub(crate) fn cross_price(
+    base_price: i64,
+    base_expo: i32,
+    quote_price: i64,
+    quote_expo: i32,
+    target_expo: i32,
+) -> Result<i128> {
+    require!(quote_price > 0, ErrorCode::InvalidOracle);
+    let mut numerator = base_price as i128;
+    let mut denominator = quote_price as i128;
+    let exp = base_expo
+        .checked_sub(quote_expo)
+        .and_then(|v| v.checked_sub(target_expo))
+        .ok_or(ErrorCode::MathOverflow)?;
+    if exp >= 0 {
+        let scale = pow10_i128(exp as u32)?;
+        numerator = numerator
+            .checked_mul(scale)
+            .ok_or(ErrorCode::MathOverflow)?;
+    } else {
+        let scale = pow10_i128((-exp) as u32)?;
+        denominator = denominator
+            .checked_mul(scale)
+            .ok_or(ErrorCode::MathOverflow)?;
+    }
+    Ok(numerator / denominator)
+}
+
+pub(crate) fn pow10_i128(exp: u32) -> Result<i128> {
+    let mut value: i128 = 1;
+    for _ in 0..exp {
+        value = value.checked_mul(10).ok_or(ErrorCode::MathOverflow)?;
+    }
+    Ok(value)
+}
+
+pub(crate) fn apply_max_slippage(expected_out: u64, max_slippage_bps: u16) -> Result<u64> {
+    require!(max_slippage_bps <= 10_000, ErrorCode::InvalidFeeBps);
+    let expected = expected_out as u128;
+    let factor = 10_000u128
+        .checked_sub(max_slippage_bps as u128)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let min_expected = expected
+        .checked_mul(factor)
+        .ok_or(ErrorCode::MathOverflow)?
+        .checked_div(10_000u128)
+        .ok_or(ErrorCode::MathOverflow)?;
+    Ok(min_expected as u64)
+}
+
+pub(crate) fn expected_sol_out_from_token(
+    amount: u64,
+    decimals: u8,
+    token_price: i64,
+    token_expo: i32,
+    sol_price: i64,
+    sol_expo: i32,
+) -> Result<u64> {
+    require!(token_price > 0, ErrorCode::InvalidOracle);
+    require!(sol_price > 0, ErrorCode::InvalidOracle);
+    let mut numerator = (amount as i128)
+        .checked_mul(token_price as i128)
+        .ok_or(ErrorCode::MathOverflow)?
+        .checked_mul(LAMPORTS_PER_SOL_U64 as i128)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let mut denominator = sol_price as i128;
+
+    let exp = token_expo
+        .checked_sub(decimals as i32)
+        .and_then(|v| v.checked_sub(sol_expo))
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    if exp >= 0 {
+        let scale = pow10_i128(exp as u32)?;
+        numerator = numerator
+            .checked_mul(scale)
+            .ok_or(ErrorCode::MathOverflow)?;
+    } else {
+        let scale = pow10_i128((-exp) as u32)?;
+        denominator = denominator
+            .checked_mul(scale)
+            .ok_or(ErrorCode::MathOverflow)?;
+    }
+    require!(denominator > 0, ErrorCode::MathOverflow);
+    Ok((numerator / denominator) as u64)
+}
+
+pub(crate) fn expected_token_out_from_sol(
+    sol_lamports: u64,
+    decimals: u8,
+    token_price: i64,
+    token_expo: i32,
+    sol_price: i64,
+    sol_expo: i32,
+) -> Result<u64> {
+    require!(token_price > 0, ErrorCode::InvalidOracle);
+    require!(sol_price > 0, ErrorCode::InvalidOracle);
+    let mut numerator = (sol_lamports as i128)
+        .checked_mul(sol_price as i128)
+        .ok_or(ErrorCode::MathOverflow)?
+        .checked_mul(pow10_i128(decimals as u32)?)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let mut denominator = (token_price as i128)
+        .checked_mul(LAMPORTS_PER_SOL_U64 as i128)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    let exp = sol_expo
+        .checked_sub(token_expo)
+        .ok_or(ErrorCode::MathOverflow)?;
+    if exp >= 0 {
+        let scale = pow10_i128(exp as u32)?;
+        numerator = numerator
+            .checked_mul(scale)
+            .ok_or(ErrorCode::MathOverflow)?;
+    } else {
+        let scale = pow10_i128((-exp) as u32)?;
+        denominator = denominator
+            .checked_mul(scale)
+            .ok_or(ErrorCode::MathOverflow)?;
+    }
+    require!(denominator > 0, ErrorCode::MathOverflow);
+    Ok((numerator / denominator) as u64)
+}
+
+pub(crate) fn expected_token_out_from_token(
+    amount: u64,
+    in_decimals: u8,
+    in_price: i64,
+    in_expo: i32,
+    out_decimals: u8,
+    out_price: i64,
+    out_expo: i32,
+) -> Result<u64> {
+    require!(in_price > 0, ErrorCode::InvalidOracle);
+    require!(out_price > 0, ErrorCode::InvalidOracle);
+    let mut numerator = (amount as i128)
+        .checked_mul(in_price as i128)
+        .ok_or(ErrorCode::MathOverflow)?
+        .checked_mul(pow10_i128(out_decimals as u32)?)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let mut denominator = (out_price as i128)
+        .checked_mul(pow10_i128(in_decimals as u32)?)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    let exp = in_expo
+        .checked_sub(out_expo)
+        .ok_or(ErrorCode::MathOverflow)?;
+    if exp >= 0 {
+        let scale = pow10_i128(exp as u32)?;
+        numerator = numerator
+            .checked_mul(scale)
+            .ok_or(ErrorCode::MathOverflow)?;
+    } else {
+        let scale = pow10_i128((-exp) as u32)?;
+        denominator = denominator
+            .checked_mul(scale)
+            .ok_or(ErrorCode::MathOverflow)?;
+    }
+    require!(denominator > 0, ErrorCode::MathOverflow);
+    Ok((numerator / denominator) as u64)
+}
@@ -1,24 +1,38 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token::spl_token::native_mint;
 
 pub const DCA_STATUS_OPEN: u8 = 0;
 pub const DCA_STATUS_EXECUTED: u8 = 1;
 pub const DCA_STATUS_CANCELLED: u8 = 2;
+pub const DCA_STATUS_EXPIRED: u8 = 3;
 
 pub const DCA_SIDE_BUY: u8 = 0;
 pub const DCA_SIDE_SELL: u8 = 1;
 
+pub const DCA_MODE_FIXED: u8 = 0;
+pub const DCA_MODE_VALUE_AVERAGE: u8 = 1;
+
 #[account]
 pub struct DcaOrder {
     pub fund: Pubkey,
     pub side: u8,
     pub mint: Pubkey,
+    pub quote_mint: Pubkey,
     pub total_amount: u64,
     pub slice_amount: u64,
     pub remaining_amount: u64,
     pub interval_secs: i64,
     pub next_exec_ts: i64,
     pub min_out: u64,
+    pub max_slippage_bps: u16,
+    pub band_lower_price: i64,
+    pub band_upper_price: i64,
+    pub price_expo: i32,
+    pub mode: u8,
+    pub period_count: u32,
+    pub acquired_amount: u64,
     pub price_feed: Pubkey,
+    pub quote_price_feed: Pubkey,
     pub pyth_program_id: Pubkey,
     pub expiry_ts: i64,
     pub status: u8,
@@ -26,5 +40,19 @@ pub struct DcaOrder {
 }
 
 impl DcaOrder {
-    pub const LEN: usize = 187;
+    pub const LEN: usize = 286;
+
+    pub fn escrows_sol(&self) -> bool {
+        self.side == DCA_SIDE_BUY && self.quote_mint == Pubkey::default()
+    }
+
+    pub fn escrow_mint(&self) -> Pubkey {
+        if self.side == DCA_SIDE_SELL {
+            self.mint
+        } else if self.quote_mint == Pubkey::default() {
+            native_mint::ID
+        } else {
+            self.quote_mint
+        }
+    }
 }
@@ -1,6 +1,9 @@
 use anchor_lang::prelude::*;
 
+use crate::errors::ErrorCode;
+
 #[account]
+#[derive(Default)]
 pub struct FundState {
     pub config: Pubkey,
     pub manager: Pubkey,
@@ -16,6 +19,20 @@ pub struct FundState {
     pub active_limit_count: u16,
     pub active_dca_count: u16,
     pub next_order_id: u64,
+    pub escrowed_lamports: u64,
+    pub holdings_nonce: u64,
+    pub held_fund_count: u16,
+    pub holder_count: u16,
+    pub paused: bool,
+    pub creator: Pubkey,
+    pub pending_manager: Pubkey,
+    pub pending_manager_effective_ts: i64,
+    pub keeper_tip_lamports: u64,
+    pub keeper_tip_bps: u16,
+    pub permissionless_execution: bool,
+    pub executor_share_bps: u16,
+    pub nav_snapshot_required: bool,
+    pub nav_snapshot_max_age_slots: u64,
     pub bump: u8,
     pub share_mint_bump: u8,
     pub vault_bump: u8,
@@ -23,7 +40,71 @@ pub struct FundState {
 
 impl FundState {
     pub const LEN: usize =
-        32 + 32 + 8 + 1 + 32 + 32 + 8 + 2 + 8 + 8 + 2 + 2 + 2 + 8 + 1 + 1 + 1;
+        32 + 32 + 8 + 1 + 32 + 32 + 8 + 2 + 8 + 8 + 2 + 2 + 2 + 8 + 8 + 8 + 2 + 2 + 1 + 32 + 32 + 8 + 8 + 2 + 1 + 2 + 1 + 8 + 1 + 1 + 1;
+
+    pub fn track_escrow(&mut self, current: u64, target: u64) -> Result<()> {
+        self.escrowed_lamports = self
+            .escrowed_lamports
+            .checked_sub(current)
+            .and_then(|v| v.checked_add(target))
+            .ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    pub fn keeper_tip(
+        &self,
+        notional_lamports: u64,
+        max_tip_lamports: u64,
+        fill_amount: u64,
+        total_amount: u64,
+        registered_keeper: bool,
+    ) -> Result<u64> {
+        let fixed_tip = if registered_keeper {
+            pro_rata(self.keeper_tip_lamports, fill_amount, total_amount)?
+        } else {
+            0
+        };
+        let max_tip = pro_rata(max_tip_lamports, fill_amount, total_amount)?;
+        let bps_tip = (notional_lamports as u128)
+            .checked_mul(self.keeper_tip_bps as u128)
+            .ok_or(ErrorCode::MathOverflow)?
+            .checked_div(10_000)
+            .ok_or(ErrorCode::MathOverflow)?;
+        let tip = bps_tip
+            .checked_add(fixed_tip as u128)
+            .ok_or(ErrorCode::MathOverflow)?;
+        Ok(tip.min(max_tip as u128) as u64)
+    }
+
+    pub fn executor_share(&self, surplus_lamports: u64, max_share_lamports: u64) -> Result<u64> {
+        if !self.permissionless_execution {
+            return Ok(0);
+        }
+        let share = (surplus_lamports as u128)
+            .checked_mul(self.executor_share_bps as u128)
+            .ok_or(ErrorCode::MathOverflow)?
+            .checked_div(10_000)
+            .ok_or(ErrorCode::MathOverflow)?;
+        Ok((share as u64).min(max_share_lamports))
+    }
+
+    pub fn mark_holdings_changed(&mut self) -> Result<()> {
+        self.holdings_nonce = self
+            .holdings_nonce
+            .checked_add(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+}
+
+pub(crate) fn pro_rata(amount: u64, part: u64, whole: u64) -> Result<u64> {
+    require!(whole > 0 && part <= whole, ErrorCode::InvalidFillAmount);
+    let scaled = (amount as u128)
+        .checked_mul(part as u128)
+        .ok_or(ErrorCode::MathOverflow)?
+        .checked_div(whole as u128)
+        .ok_or(ErrorCode::MathOverflow)?;
+    Ok(scaled as u64)
 }
 
 #[account]
@@ -31,3 +112,70 @@ pub struct FundVault {}
 
 pub const FUND_TYPE_TRADING: u8 = 0;
 pub const FUND_TYPE_STRATEGY: u8 = 1;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tips_for_fills(fund: &FundState, notional: u64, max_tip: u64, amount_in: u64, fills: u64) -> u64 {
+        let fill = (amount_in / fills).max(1);
+        let mut remaining = amount_in;
+        let mut paid = 0u64;
+        while remaining > 0 {
+            let fill_amount = fill.min(remaining);
+            let fill_notional = ((notional as u128) * (fill_amount as u128) / (amount_in as u128)) as u64;
+            paid += fund
+                .keeper_tip(fill_notional, max_tip, fill_amount, amount_in, true)
+                .unwrap();
+            remaining -= fill_amount;
+        }
+        paid
+    }
+
+    #[test]
+    fn partial_fills_never_tip_more_than_one_full_fill() {
+        let fund = FundState {
+            keeper_tip_lamports: 5_000,
+            keeper_tip_bps: 10,
+            ..Default::default()
+        };
+        let amount_in = 1_000_003;
+        let notional = 2_000_000_000;
+        for max_tip in [0, 3_000, 50_000, 10_000_000] {
+            let full = fund.keeper_tip(notional, max_tip, amount_in, amount_in, true).unwrap();
+            for fills in [1, 2, 7, 100, 10_000] {
+                assert!(tips_for_fills(&fund, notional, max_tip, amount_in, fills) <= full);
+            }
+        }
+    }
+
+    #[test]
+    fn executor_payouts_stay_within_the_per_order_cap() {
+        let fund = FundState {
+            keeper_tip_lamports: 5_000,
+            keeper_tip_bps: 10,
+            permissionless_execution: true,
+            executor_share_bps: 5_000,
+            ..Default::default()
+        };
+        let amount_in = 1_000_000;
+        let notional = 2_000_000_000;
+        let max_tip = 50_000;
+        assert_eq!(fund.keeper_tip(0, max_tip, amount_in, amount_in, false).unwrap(), 0);
+        for fills in [1, 3, 100] {
+            let fill_amount = amount_in / fills;
+            let mut paid = 0u64;
+            for _ in 0..fills {
+                let tip = fund
+                    .keeper_tip(notional / fills, max_tip, fill_amount, amount_in, false)
+                    .unwrap();
+                let cap = pro_rata(max_tip, fill_amount, amount_in).unwrap();
+                let share = fund
+                    .executor_share(u64::MAX / 2, cap.saturating_sub(tip))
+                    .unwrap();
+                paid += tip + share;
+            }
+            assert!(paid <= max_tip);
+        }
+    }
+}
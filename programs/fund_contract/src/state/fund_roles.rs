@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+
+pub const MAX_FUND_ROLE_MEMBERS: usize = 16;
+
+pub const ROLE_TRADER: u8 = 1;
+pub const ROLE_RISK_OFFICER: u8 = 2;
+pub const ROLE_OPS: u8 = 4;
+pub const ROLE_ALL: u8 = ROLE_TRADER | ROLE_RISK_OFFICER | ROLE_OPS;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct FundRoleMember {
+    pub key: Pubkey,
+    pub roles: u8,
+}
+
+impl FundRoleMember {
+    pub const LEN: usize = 32 + 1;
+}
+
+#[account]
+pub struct FundRoles {
+    pub fund: Pubkey,
+    pub members: Vec<FundRoleMember>,
+    pub bump: u8,
+}
+
+impl FundRoles {
+    pub const LEN: usize = 32 + 4 + FundRoleMember::LEN * MAX_FUND_ROLE_MEMBERS + 1;
+
+    pub fn has_any_role(&self, key: &Pubkey, roles: u8) -> bool {
+        self.members
+            .iter()
+            .any(|member| member.key == *key && member.roles & roles != 0)
+    }
+}
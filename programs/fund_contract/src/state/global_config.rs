@@ -1,5 +1,8 @@
 use anchor_lang::prelude::*;
 
+pub const MAX_SWAP_PROGRAMS: usize = 8;
+pub const JUPITER_V6_PROGRAM_ID: Pubkey = pubkey!("JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4");
+
 #[account]
 pub struct GlobalConfig {
     pub config_id: u64,
@@ -16,10 +19,134 @@ pub struct GlobalConfig {
     pub min_manager_deposit_lamports: u64,
     pub min_withdraw_timelock_secs: i64,
     pub max_withdraw_timelock_secs: i64,
+    pub pending_admin: Pubkey,
+    pub config_timelock_secs: i64,
+    pub pending_sol_usd_pyth_feed: Pubkey,
+    pub pending_pyth_program_id: Pubkey,
+    pub pending_deposit_fee_bps: u16,
+    pub pending_withdraw_fee_bps: u16,
+    pub pending_trade_fee_bps: u16,
+    pub pending_max_manager_fee_bps: u16,
+    pub pending_max_slippage_bps: u16,
+    pub pending_config_timelock_secs: i64,
+    pub pending_effective_ts: i64,
+    pub has_pending_update: bool,
+    pub max_keeper_tip_lamports: u64,
+    pub swap_programs: Vec<Pubkey>,
     pub bump: u8,
 }
 
 impl GlobalConfig {
-    pub const LEN: usize =
-        8 + 32 + 32 + 32 + 32 + 32 + 2 + 2 + 2 + 2 + 2 + 8 + 8 + 8 + 1;
+    pub const LEN: usize = 8
+        + 32
+        + 32
+        + 32
+        + 32
+        + 32
+        + 2
+        + 2
+        + 2
+        + 2
+        + 2
+        + 8
+        + 8
+        + 8
+        + 32
+        + 8
+        + 32
+        + 32
+        + 2
+        + 2
+        + 2
+        + 2
+        + 2
+        + 8
+        + 8
+        + 1
+        + 8
+        + 4
+        + 32 * MAX_SWAP_PROGRAMS
+        + 1;
+
+    pub fn is_swap_program_allowed(&self, program_id: &Pubkey) -> bool {
+        self.swap_programs.contains(program_id)
+    }
+
+    pub fn is_sensitive_update(&self, update: &SensitiveConfig) -> bool {
+        update.sol_usd_pyth_feed != self.sol_usd_pyth_feed
+            || update.pyth_program_id != self.pyth_program_id
+            || update.deposit_fee_bps > self.deposit_fee_bps
+            || update.withdraw_fee_bps > self.withdraw_fee_bps
+            || update.trade_fee_bps > self.trade_fee_bps
+            || update.max_manager_fee_bps > self.max_manager_fee_bps
+            || update.max_slippage_bps > self.max_slippage_bps
+            || update.config_timelock_secs < self.config_timelock_secs
+    }
+
+    pub fn stage_update(&mut self, update: &SensitiveConfig, effective_ts: i64) {
+        self.pending_sol_usd_pyth_feed = update.sol_usd_pyth_feed;
+        self.pending_pyth_program_id = update.pyth_program_id;
+        self.pending_deposit_fee_bps = update.deposit_fee_bps;
+        self.pending_withdraw_fee_bps = update.withdraw_fee_bps;
+        self.pending_trade_fee_bps = update.trade_fee_bps;
+        self.pending_max_manager_fee_bps = update.max_manager_fee_bps;
+        self.pending_max_slippage_bps = update.max_slippage_bps;
+        self.pending_config_timelock_secs = update.config_timelock_secs;
+        self.pending_effective_ts = effective_ts;
+        self.has_pending_update = true;
+    }
+
+    pub fn apply_update(&mut self, update: &SensitiveConfig) {
+        self.sol_usd_pyth_feed = update.sol_usd_pyth_feed;
+        self.pyth_program_id = update.pyth_program_id;
+        self.deposit_fee_bps = update.deposit_fee_bps;
+        self.withdraw_fee_bps = update.withdraw_fee_bps;
+        self.trade_fee_bps = update.trade_fee_bps;
+        self.max_manager_fee_bps = update.max_manager_fee_bps;
+        self.max_slippage_bps = update.max_slippage_bps;
+        self.config_timelock_secs = update.config_timelock_secs;
+    }
+
+    pub fn clear_pending(&mut self) {
+        self.pending_sol_usd_pyth_feed = Pubkey::default();
+        self.pending_pyth_program_id = Pubkey::default();
+        self.pending_deposit_fee_bps = 0;
+        self.pending_withdraw_fee_bps = 0;
+        self.pending_trade_fee_bps = 0;
+        self.pending_max_manager_fee_bps = 0;
+        self.pending_max_slippage_bps = 0;
+        self.pending_config_timelock_secs = 0;
+        self.pending_effective_ts = 0;
+        self.has_pending_update = false;
+    }
+
+    pub fn activate_pending(&mut self, now: i64) -> bool {
+        if !self.has_pending_update || now < self.pending_effective_ts {
+            return false;
+        }
+        let pending = SensitiveConfig {
+            sol_usd_pyth_feed: self.pending_sol_usd_pyth_feed,
+            pyth_program_id: self.pending_pyth_program_id,
+            deposit_fee_bps: self.pending_deposit_fee_bps,
+            withdraw_fee_bps: self.pending_withdraw_fee_bps,
+            trade_fee_bps: self.pending_trade_fee_bps,
+            max_manager_fee_bps: self.pending_max_manager_fee_bps,
+            max_slippage_bps: self.pending_max_slippage_bps,
+            config_timelock_secs: self.pending_config_timelock_secs,
+        };
+        self.apply_update(&pending);
+        self.clear_pending();
+        true
+    }
+}
+
+pub struct SensitiveConfig {
+    pub sol_usd_pyth_feed: Pubkey,
+    pub pyth_program_id: Pubkey,
+    pub deposit_fee_bps: u16,
+    pub withdraw_fee_bps: u16,
+    pub trade_fee_bps: u16,
+    pub max_manager_fee_bps: u16,
+    pub max_slippage_bps: u16,
+    pub config_timelock_secs: i64,
 }
@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+pub const KEEPER_SCOPE_ORDERS: u8 = 1;
+pub const KEEPER_SCOPE_REBALANCE: u8 = 2;
+pub const KEEPER_SCOPE_SWEEP: u8 = 4;
+pub const KEEPER_SCOPE_NAV: u8 = 8;
+pub const KEEPER_SCOPE_ALL: u8 =
+    KEEPER_SCOPE_ORDERS | KEEPER_SCOPE_REBALANCE | KEEPER_SCOPE_SWEEP | KEEPER_SCOPE_NAV;
+
+#[account]
+pub struct KeeperRecord {
+    pub config: Pubkey,
+    pub keeper: Pubkey,
+    pub scopes: u8,
+    pub fund: Pubkey,
+    pub bump: u8,
+}
+
+impl KeeperRecord {
+    pub const LEN: usize = 32 + 32 + 1 + 32 + 1;
+
+    pub fn allows(&self, fund: &Pubkey, scope: u8) -> bool {
+        self.scopes & scope != 0 && (self.fund == Pubkey::default() || self.fund == *fund)
+    }
+}
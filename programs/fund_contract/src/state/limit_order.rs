@@ -1,29 +1,86 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token::spl_token::native_mint;
 
 pub const ORDER_STATUS_OPEN: u8 = 0;
 pub const ORDER_STATUS_EXECUTED: u8 = 1;
 pub const ORDER_STATUS_CANCELLED: u8 = 2;
+pub const ORDER_STATUS_EXPIRED: u8 = 3;
 
 pub const SIDE_BUY: u8 = 0;
 pub const SIDE_SELL: u8 = 1;
 
+pub const ORDER_TYPE_LIMIT: u8 = 0;
+pub const ORDER_TYPE_STOP: u8 = 1;
+pub const ORDER_TYPE_BRACKET: u8 = 2;
+pub const ORDER_TYPE_TRAILING_STOP: u8 = 3;
+
+pub const ORDER_LEG_NONE: u8 = 0;
+pub const ORDER_LEG_LIMIT: u8 = 1;
+pub const ORDER_LEG_STOP: u8 = 2;
+
+pub const MIN_FILL_BPS: u64 = 100;
+
 #[account]
 pub struct LimitOrder {
     pub fund: Pubkey,
     pub side: u8,
     pub mint: Pubkey,
+    pub quote_mint: Pubkey,
     pub amount_in: u64,
     pub min_out: u64,
+    pub order_type: u8,
     pub limit_price: i64,
+    pub stop_price: i64,
+    pub trail_bps: u16,
+    pub peak_price: i64,
     pub price_expo: i32,
     pub price_feed: Pubkey,
+    pub quote_price_feed: Pubkey,
     pub pyth_program_id: Pubkey,
     pub created_ts: i64,
     pub expiry_ts: i64,
     pub status: u8,
+    pub filled_in: u64,
+    pub filled_out: u64,
+    pub active_leg: u8,
     pub bump: u8,
 }
 
 impl LimitOrder {
-    pub const LEN: usize = 32 + 1 + 32 + 8 + 8 + 8 + 4 + 32 + 32 + 8 + 8 + 1 + 1;
+    pub const LEN: usize = 32
+        + 1
+        + 32
+        + 32
+        + 8
+        + 8
+        + 1
+        + 8
+        + 8
+        + 2
+        + 8
+        + 4
+        + 32
+        + 32
+        + 32
+        + 8
+        + 8
+        + 1
+        + 8
+        + 8
+        + 1
+        + 1;
+
+    pub fn escrows_sol(&self) -> bool {
+        self.side == SIDE_BUY && self.quote_mint == Pubkey::default()
+    }
+
+    pub fn escrow_mint(&self) -> Pubkey {
+        if self.side == SIDE_SELL {
+            self.mint
+        } else if self.quote_mint == Pubkey::default() {
+            native_mint::ID
+        } else {
+            self.quote_mint
+        }
+    }
 }
@@ -6,3 +6,8 @@ pub mod dca_order;
 pub mod whitelist;
 pub mod withdraw_request;
 pub mod strategy;
+pub mod rebalance_session;
+pub mod nav_snapshot;
+pub mod nav_history;
+pub mod fund_roles;
+pub mod keeper_record;
@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+
+pub const NAV_HISTORY_CAPACITY: usize = 128;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct NavSample {
+    pub ts: i64,
+    pub nav_lamports: u64,
+    pub total_shares: u64,
+}
+
+impl NavSample {
+    pub const LEN: usize = 8 + 8 + 8;
+}
+
+#[account]
+pub struct NavHistory {
+    pub fund: Pubkey,
+    pub interval_secs: i64,
+    pub last_sample_ts: i64,
+    pub head: u16,
+    pub samples: Vec<NavSample>,
+    pub bump: u8,
+}
+
+impl NavHistory {
+    pub const LEN: usize = 32 + 8 + 8 + 2 + 4 + NavSample::LEN * NAV_HISTORY_CAPACITY + 1;
+
+    pub fn record(&mut self, ts: i64, nav_lamports: u64, total_shares: u64) {
+        if !self.samples.is_empty() && ts < self.last_sample_ts.saturating_add(self.interval_secs) {
+            return;
+        }
+        let sample = NavSample {
+            ts,
+            nav_lamports,
+            total_shares,
+        };
+        let head = self.head as usize;
+        if self.samples.len() < NAV_HISTORY_CAPACITY {
+            self.samples.push(sample);
+        } else {
+            self.samples[head] = sample;
+        }
+        self.head = ((head + 1) % NAV_HISTORY_CAPACITY) as u16;
+        self.last_sample_ts = ts;
+    }
+}
@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+
+use crate::state::fund::FundState;
+
+pub const MAX_NAV_ASSETS: usize = 50;
+pub const MAX_NAV_BATCH_ASSETS: usize = 12;
+pub const NAV_SNAPSHOT_MAX_AGE_SLOTS: u64 = 150;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct NavAssetValue {
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub price: i64,
+    pub price_expo: i32,
+    pub price_publish_time: i64,
+    pub value_lamports: u64,
+}
+
+impl NavAssetValue {
+    pub const LEN: usize = 32 + 8 + 8 + 4 + 8 + 8;
+}
+
+#[account]
+pub struct NavSnapshot {
+    pub fund: Pubkey,
+    pub nav_lamports: u64,
+    pub sol_lamports: u64,
+    pub token_value_lamports: u64,
+    pub total_shares: u64,
+    pub sol_price: i64,
+    pub sol_price_expo: i32,
+    pub sol_price_publish_time: i64,
+    pub holdings_nonce: u64,
+    pub enabled_token_count: u16,
+    pub slot: u64,
+    pub updated_ts: i64,
+    pub assets: Vec<NavAssetValue>,
+    pub batch_cursor: u16,
+    pub batch_last_mint: Pubkey,
+    pub batch_token_value_lamports: u64,
+    pub batch_holdings_nonce: u64,
+    pub batch_enabled_token_count: u16,
+    pub batch_slot: u64,
+    pub batch_assets: Vec<NavAssetValue>,
+    pub bump: u8,
+}
+
+impl NavSnapshot {
+    pub const BASE_LEN: usize =
+        32 + 8 + 8 + 8 + 8 + 8 + 4 + 8 + 8 + 2 + 8 + 8 + 4 + 2 + 32 + 8 + 8 + 2 + 8 + 4 + 1;
+
+    pub fn space(asset_count: usize) -> usize {
+        Self::BASE_LEN + NavAssetValue::LEN * asset_count
+    }
+
+    pub fn is_fresh(&self, fund: &FundState, slot: u64) -> bool {
+        self.holdings_nonce == fund.holdings_nonce
+            && self.enabled_token_count == fund.enabled_token_count
+            && slot.saturating_sub(self.slot) <= fund.nav_snapshot_max_age_slots
+    }
+
+    pub fn is_batch_fresh(&self, fund: &FundState, slot: u64) -> bool {
+        self.batch_cursor > 0
+            && self.batch_holdings_nonce == fund.holdings_nonce
+            && self.batch_enabled_token_count == fund.enabled_token_count
+            && slot.saturating_sub(self.batch_slot) <= fund.nav_snapshot_max_age_slots
+    }
+}
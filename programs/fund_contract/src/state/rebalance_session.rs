@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+pub const REBALANCE_SESSION_MAX_SECS: i64 = 300;
+
+pub const SESSION_PHASE_OPENING: u8 = 0;
+pub const SESSION_PHASE_TRADING: u8 = 1;
+pub const SESSION_PHASE_CLOSING: u8 = 2;
+pub const SESSION_PHASE_COMPLETE: u8 = 3;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct RebalanceEntry {
+    pub mint: Pubkey,
+    pub weight_bps: u16,
+    pub value_lamports: u64,
+}
+
+impl RebalanceEntry {
+    pub const LEN: usize = 32 + 2 + 8;
+}
+
+#[account]
+pub struct RebalanceSession {
+    pub fund: Pubkey,
+    pub opener: Pubkey,
+    pub nav_lamports: u64,
+    pub opened_ts: i64,
+    pub leg_count: u16,
+    pub notional_lamports: u64,
+    pub phase: u8,
+    pub cursor: u16,
+    pub sol_weight_bps: u16,
+    pub entries: Vec<RebalanceEntry>,
+    pub wsol_created: bool,
+    pub bump: u8,
+}
+
+impl RebalanceSession {
+    pub const BASE_LEN: usize = 32 + 32 + 8 + 8 + 2 + 8 + 1 + 2 + 2 + 4 + 1 + 1;
+
+    pub fn space(entry_count: usize) -> usize {
+        Self::BASE_LEN + RebalanceEntry::LEN * entry_count
+    }
+}
@@ -1,18 +1,40 @@
 use anchor_lang::prelude::*;
 
-pub const MAX_STRATEGY_TOKENS: usize = 8;
+use crate::errors::ErrorCode;
+use crate::state::nav_snapshot::MAX_NAV_ASSETS;
+
+pub const MAX_STRATEGY_TOKENS: usize = MAX_NAV_ASSETS;
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
 pub struct StrategyAllocation {
     pub mint: Pubkey,
     pub weight_bps: u16,
+    pub start_weight_bps: u16,
+}
+
+impl StrategyAllocation {
+    pub const LEN: usize = 32 + 2 + 2;
 }
 
 #[account]
+#[derive(Default)]
 pub struct Strategy {
     pub fund: Pubkey,
-    pub allocation_count: u8,
-    pub allocations: [StrategyAllocation; MAX_STRATEGY_TOKENS],
+    pub allocations: Vec<StrategyAllocation>,
+    pub sol_weight_bps: u16,
+    pub start_sol_weight_bps: u16,
+    pub liquidity_reserve_bps: u16,
+    pub glide_start_ts: i64,
+    pub glide_end_ts: i64,
+    pub pending_allocations: Vec<StrategyAllocation>,
+    pub pending_sol_weight_bps: u16,
+    pub pending_liquidity_reserve_bps: u16,
+    pub pending_rebalance_threshold_bps: u16,
+    pub pending_rebalance_cooldown_secs: i64,
+    pub pending_glide_duration_secs: i64,
+    pub pending_effective_ts: i64,
+    pub has_pending_update: bool,
+    pub draft_allocations: Vec<StrategyAllocation>,
     pub rebalance_threshold_bps: u16,
     pub rebalance_cooldown_secs: i64,
     pub last_rebalance_ts: i64,
@@ -20,5 +42,170 @@ pub struct Strategy {
 }
 
 impl Strategy {
-    pub const LEN: usize = 32 + 1 + (32 + 2) * MAX_STRATEGY_TOKENS + 2 + 8 + 8 + 1;
+    pub const BASE_LEN: usize = 32
+        + 4
+        + 2
+        + 2
+        + 2
+        + 8
+        + 8
+        + 4
+        + 2
+        + 2
+        + 2
+        + 8
+        + 8
+        + 8
+        + 1
+        + 4
+        + 2
+        + 8
+        + 8
+        + 1;
+
+    pub fn space(active: usize, pending: usize, draft: usize) -> usize {
+        Self::BASE_LEN + StrategyAllocation::LEN * (active + pending + draft)
+    }
+
+    pub fn target_weight_bps(&self, idx: usize, now: i64) -> u16 {
+        let alloc = &self.allocations[idx];
+        let weight_bps = self.glide_weight(alloc.start_weight_bps, alloc.weight_bps, now);
+        let sol_weight_bps = self.mandate_sol_weight_bps(now);
+        if self.liquidity_reserve_bps <= sol_weight_bps {
+            return weight_bps;
+        }
+        let token_share = 10_000 - sol_weight_bps as u32;
+        let reserved_share = 10_000 - self.liquidity_reserve_bps as u32;
+        (weight_bps as u32 * reserved_share / token_share) as u16
+    }
+
+    pub fn target_sol_weight_bps(&self, now: i64) -> u16 {
+        std::cmp::max(self.mandate_sol_weight_bps(now), self.liquidity_reserve_bps)
+    }
+
+    fn mandate_weight_for_mint(&self, mint: &Pubkey, now: i64) -> u16 {
+        self.allocations
+            .iter()
+            .find(|alloc| alloc.mint == *mint)
+            .map(|alloc| self.glide_weight(alloc.start_weight_bps, alloc.weight_bps, now))
+            .unwrap_or(0)
+    }
+
+    fn mandate_sol_weight_bps(&self, now: i64) -> u16 {
+        self.glide_weight(self.start_sol_weight_bps, self.sol_weight_bps, now)
+    }
+
+    fn glide_weight(&self, start_bps: u16, end_bps: u16, now: i64) -> u16 {
+        if now >= self.glide_end_ts || self.glide_end_ts <= self.glide_start_ts {
+            return end_bps;
+        }
+        if now <= self.glide_start_ts {
+            return start_bps;
+        }
+        let elapsed = (now - self.glide_start_ts) as i128;
+        let duration = (self.glide_end_ts - self.glide_start_ts) as i128;
+        let delta = end_bps as i128 - start_bps as i128;
+        (start_bps as i128 + delta * elapsed / duration) as u16
+    }
+
+    pub fn activate_pending(&mut self, now: i64) -> Result<bool> {
+        if !self.has_pending_update || now < self.pending_effective_ts {
+            return Ok(false);
+        }
+        let effective_ts = self.pending_effective_ts;
+        let next_allocations: Vec<StrategyAllocation> = self
+            .pending_allocations
+            .iter()
+            .map(|pending| StrategyAllocation {
+                mint: pending.mint,
+                weight_bps: pending.weight_bps,
+                start_weight_bps: self.mandate_weight_for_mint(&pending.mint, effective_ts),
+            })
+            .collect();
+        self.start_sol_weight_bps = self.mandate_sol_weight_bps(effective_ts);
+        self.glide_start_ts = effective_ts;
+        self.glide_end_ts = effective_ts
+            .checked_add(self.pending_glide_duration_secs)
+            .ok_or(ErrorCode::MathOverflow)?;
+        self.allocations = next_allocations;
+        self.sol_weight_bps = self.pending_sol_weight_bps;
+        self.liquidity_reserve_bps = self.pending_liquidity_reserve_bps;
+        self.rebalance_threshold_bps = self.pending_rebalance_threshold_bps;
+        self.rebalance_cooldown_secs = self.pending_rebalance_cooldown_secs;
+
+        self.pending_allocations = Vec::new();
+        self.pending_sol_weight_bps = 0;
+        self.pending_liquidity_reserve_bps = 0;
+        self.pending_rebalance_threshold_bps = 0;
+        self.pending_rebalance_cooldown_secs = 0;
+        self.pending_glide_duration_secs = 0;
+        self.pending_effective_ts = 0;
+        self.has_pending_update = false;
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::nav_snapshot::NavSnapshot;
+    use anchor_lang::solana_program::entrypoint::MAX_PERMITTED_DATA_INCREASE;
+
+    fn build_strategy(weights: &[u16], sol_weight_bps: u16, liquidity_reserve_bps: u16) -> Strategy {
+        Strategy {
+            allocations: weights
+                .iter()
+                .map(|&weight_bps| StrategyAllocation {
+                    mint: Pubkey::new_unique(),
+                    weight_bps,
+                    start_weight_bps: weight_bps,
+                })
+                .collect(),
+            sol_weight_bps,
+            start_sol_weight_bps: sol_weight_bps,
+            liquidity_reserve_bps,
+            ..Default::default()
+        }
+    }
+
+    fn total_target_bps(strategy: &Strategy) -> u32 {
+        let tokens: u32 = (0..strategy.allocations.len())
+            .map(|idx| strategy.target_weight_bps(idx, 0) as u32)
+            .sum();
+        tokens + strategy.target_sol_weight_bps(0) as u32
+    }
+
+    #[test]
+    fn reserve_within_sol_weight_leaves_targets_unchanged() {
+        let strategy = build_strategy(&[4_000, 3_000], 3_000, 1_000);
+        assert_eq!(strategy.target_sol_weight_bps(0), 3_000);
+        assert_eq!(strategy.target_weight_bps(0, 0), 4_000);
+        assert_eq!(strategy.target_weight_bps(1, 0), 3_000);
+    }
+
+    #[test]
+    fn reserve_above_sol_weight_is_held_back_from_token_targets() {
+        let strategy = build_strategy(&[6_000, 4_000], 0, 1_000);
+        assert_eq!(strategy.target_sol_weight_bps(0), 1_000);
+        assert_eq!(strategy.target_weight_bps(0, 0), 5_400);
+        assert_eq!(strategy.target_weight_bps(1, 0), 3_600);
+        assert_eq!(total_target_bps(&strategy), 10_000);
+
+        let strategy = build_strategy(&[5_000, 3_000], 2_000, 5_000);
+        assert_eq!(strategy.target_sol_weight_bps(0), 5_000);
+        assert!(total_target_bps(&strategy) <= 10_000);
+        assert_eq!(strategy.target_weight_bps(0, 0), 3_125);
+    }
+
+    #[test]
+    fn max_strategy_fits_accounts_and_targets() {
+        let strategy = build_strategy(&[190; MAX_STRATEGY_TOKENS], 500, 1_000);
+        assert_eq!(strategy.target_sol_weight_bps(0), 1_000);
+        assert!(total_target_bps(&strategy) <= 10_000);
+        assert!(
+            8 + Strategy::space(MAX_STRATEGY_TOKENS, MAX_STRATEGY_TOKENS, MAX_STRATEGY_TOKENS)
+                <= MAX_PERMITTED_DATA_INCREASE
+        );
+        assert!(8 + NavSnapshot::space(MAX_STRATEGY_TOKENS) <= MAX_PERMITTED_DATA_INCREASE);
+    }
 }
@@ -1,5 +1,10 @@
 use anchor_lang::prelude::*;
 
+use crate::errors::ErrorCode;
+
+pub const ASSET_KIND_TOKEN: u8 = 0;
+pub const ASSET_KIND_FUND_SHARES: u8 = 1;
+
 #[account]
 pub struct FundWhitelist {
     pub fund: Pubkey,
@@ -7,11 +12,23 @@ pub struct FundWhitelist {
     pub decimals: u8,
     pub pyth_feed: Pubkey,
     pub enabled: bool,
+    pub escrowed_amount: u64,
+    pub kind: u8,
+    pub underlying_fund: Pubkey,
     pub bump: u8,
 }
 
 impl FundWhitelist {
-    pub const LEN: usize = 32 + 32 + 1 + 32 + 1 + 1;
+    pub const LEN: usize = 32 + 32 + 1 + 32 + 1 + 8 + 1 + 32 + 1;
+
+    pub fn track_escrow(&mut self, current: u64, target: u64) -> Result<()> {
+        self.escrowed_amount = self
+            .escrowed_amount
+            .checked_sub(current)
+            .and_then(|v| v.checked_add(target))
+            .ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
 }
 
 #[account]
@@ -121,8 +121,21 @@ export const decodeFundWhitelist = (data: Buffer) => {
   const decimals = data[72];
   const pythFeed = new anchor.web3.PublicKey(data.slice(73, 105));
   const enabled = data[105] !== 0;
-  const bump = data[106];
-  return { fund, mint, decimals, pythFeed, enabled, bump };
+  const escrowedAmount = data.readBigUInt64LE(106);
+  const kind = data[114];
+  const underlyingFund = new anchor.web3.PublicKey(data.slice(115, 147));
+  const bump = data[147];
+  return {
+    fund,
+    mint,
+    decimals,
+    pythFeed,
+    enabled,
+    escrowedAmount,
+    kind,
+    underlyingFund,
+    bump,
+  };
 };
 
 export const expectError = async (promise: Promise<string>, code: string) => {
@@ -183,7 +196,8 @@ export const ensureGlobalConfig = async (ctx: TestContext) => {
       configAccount.maxSlippageBps !== 100 ||
       !configAccount.minManagerDepositLamports.eq(expectedMinManagerDeposit) ||
       !configAccount.minWithdrawTimelockSecs.eq(expectedMinWithdrawTimelock) ||
-      !configAccount.maxWithdrawTimelockSecs.eq(expectedMaxWithdrawTimelock);
+      !configAccount.maxWithdrawTimelockSecs.eq(expectedMaxWithdrawTimelock) ||
+      configAccount.hasPendingUpdate;
     if (needsUpdate) {
       await ctx.program.methods
         .updateGlobalConfig(
@@ -198,6 +212,7 @@ export const ensureGlobalConfig = async (ctx: TestContext) => {
           expectedMinManagerDeposit,
           expectedMinWithdrawTimelock,
           expectedMaxWithdrawTimelock,
+          new anchor.BN(0),
         )
         .accounts({
           config: ctx.configPda,
@@ -231,6 +246,7 @@ export const ensureGlobalConfig = async (ctx: TestContext) => {
       new anchor.BN(anchor.web3.LAMPORTS_PER_SOL / 10),
       new anchor.BN(0),
       new anchor.BN(31_536_000),
+      new anchor.BN(0),
     )
     .accounts({
       config: ctx.configPda,
@@ -66,6 +66,7 @@ describe("global-config", () => {
         newMinManagerDepositLamports,
         newMinWithdrawTimelockSecs,
         newMaxWithdrawTimelockSecs,
+        new anchor.BN(0),
       )
       .accounts({
         config: ctx.configPda,
@@ -117,6 +118,7 @@ describe("global-config", () => {
           new anchor.BN(1),
           new anchor.BN(0),
           new anchor.BN(1),
+          new anchor.BN(0),
         )
         .accounts({
           config: badConfigPda,
@@ -148,6 +150,7 @@ describe("global-config", () => {
           new anchor.BN(1),
           new anchor.BN(0),
           new anchor.BN(1),
+          new anchor.BN(0),
         )
         .accounts({
           config: ctx.configPda,
@@ -222,6 +225,7 @@ describe("global-config", () => {
         new anchor.BN(anchor.web3.LAMPORTS_PER_SOL / 10),
         new anchor.BN(0),
         new anchor.BN(86_400),
+        new anchor.BN(0),
       )
       .accounts({
         config: configPda,
@@ -264,4 +268,232 @@ describe("global-config", () => {
       "ConstraintHasOne",
     );
   });
+
+  it("Stages sensitive config changes behind the config timelock", async () => {
+    const ctx = await getContext();
+
+    const configId = new anchor.BN(Date.now() + 2);
+    const configSeed = configId.toArrayLike(Buffer, "le", 8);
+    const [configPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("config"), configSeed],
+      ctx.program.programId,
+    );
+    const feeTreasury = anchor.web3.Keypair.generate();
+    const solPythFeed = anchor.web3.Keypair.generate().publicKey;
+    const pythProgramId = anchor.web3.Keypair.generate().publicKey;
+    const configTimelockSecs = new anchor.BN(3600);
+
+    await ctx.program.methods
+      .initializeGlobalConfig(
+        configId,
+        ctx.keeper,
+        solPythFeed,
+        pythProgramId,
+        50,
+        25,
+        10,
+        3000,
+        100,
+        new anchor.BN(anchor.web3.LAMPORTS_PER_SOL / 10),
+        new anchor.BN(0),
+        new anchor.BN(86_400),
+        configTimelockSecs,
+      )
+      .accounts({
+        config: configPda,
+        admin: ctx.provider.wallet.publicKey,
+        feeTreasury: feeTreasury.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const update = (depositFeeBps: number, timelockSecs: anchor.BN) =>
+      ctx.program.methods
+        .updateGlobalConfig(
+          configId,
+          solPythFeed,
+          pythProgramId,
+          depositFeeBps,
+          25,
+          10,
+          3000,
+          100,
+          new anchor.BN(anchor.web3.LAMPORTS_PER_SOL / 5),
+          new anchor.BN(0),
+          new anchor.BN(86_400),
+          timelockSecs,
+        )
+        .accounts({
+          config: configPda,
+          admin: ctx.provider.wallet.publicKey,
+          feeTreasury: feeTreasury.publicKey,
+        })
+        .rpc();
+
+    await update(75, configTimelockSecs);
+    const staged = await ctx.program.account.globalConfig.fetch(configPda);
+    expect(staged.depositFeeBps).to.equal(50);
+    expect(staged.hasPendingUpdate).to.equal(true);
+    expect(staged.pendingDepositFeeBps).to.equal(75);
+    expect(staged.minManagerDepositLamports.toNumber()).to.equal(
+      Math.floor(anchor.web3.LAMPORTS_PER_SOL / 5),
+    );
+
+    await expectError(
+      ctx.program.methods
+        .applyConfigUpdate(configId)
+        .accounts({ config: configPda })
+        .rpc(),
+      "ConfigUpdateTimelock",
+    );
+
+    await update(40, new anchor.BN(0));
+    const shortened = await ctx.program.account.globalConfig.fetch(configPda);
+    expect(shortened.depositFeeBps).to.equal(50);
+    expect(shortened.pendingConfigTimelockSecs.toNumber()).to.equal(0);
+
+    await update(40, configTimelockSecs);
+    const lowered = await ctx.program.account.globalConfig.fetch(configPda);
+    expect(lowered.depositFeeBps).to.equal(40);
+    expect(lowered.hasPendingUpdate).to.equal(false);
+
+    await expectError(
+      ctx.program.methods
+        .applyConfigUpdate(configId)
+        .accounts({ config: configPda })
+        .rpc(),
+      "NoPendingConfigUpdate",
+    );
+  });
+
+  it("Hands the admin role over in two steps", async () => {
+    const ctx = await getContext();
+
+    const configId = new anchor.BN(Date.now() + 3);
+    const configSeed = configId.toArrayLike(Buffer, "le", 8);
+    const [configPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("config"), configSeed],
+      ctx.program.programId,
+    );
+    const feeTreasury = anchor.web3.Keypair.generate();
+    const newAdmin = anchor.web3.Keypair.generate();
+    const rogue = anchor.web3.Keypair.generate();
+
+    await ctx.program.methods
+      .initializeGlobalConfig(
+        configId,
+        ctx.keeper,
+        ctx.solPythFeed,
+        ctx.pythProgramId,
+        50,
+        25,
+        10,
+        3000,
+        100,
+        new anchor.BN(anchor.web3.LAMPORTS_PER_SOL / 10),
+        new anchor.BN(0),
+        new anchor.BN(86_400),
+        new anchor.BN(0),
+      )
+      .accounts({
+        config: configPda,
+        admin: ctx.provider.wallet.publicKey,
+        feeTreasury: feeTreasury.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const accept = (signer: anchor.web3.Keypair) =>
+      ctx.program.methods
+        .acceptAdmin(configId)
+        .accounts({ config: configPda, newAdmin: signer.publicKey })
+        .signers([signer])
+        .rpc();
+
+    await expectError(accept(newAdmin), "NoPendingAdmin");
+    await expectError(
+      ctx.program.methods
+        .proposeAdmin(configId, rogue.publicKey)
+        .accounts({ config: configPda, admin: rogue.publicKey })
+        .signers([rogue])
+        .rpc(),
+      "ConstraintHasOne",
+    );
+
+    await ctx.program.methods
+      .proposeAdmin(configId, newAdmin.publicKey)
+      .accounts({ config: configPda, admin: ctx.provider.wallet.publicKey })
+      .rpc();
+    await expectError(accept(rogue), "Unauthorized");
+    await accept(newAdmin);
+
+    const configAccount = await ctx.program.account.globalConfig.fetch(
+      configPda,
+    );
+    expect(configAccount.admin.toBase58()).to.equal(
+      newAdmin.publicKey.toBase58(),
+    );
+    expect(configAccount.pendingAdmin.toBase58()).to.equal(
+      anchor.web3.PublicKey.default.toBase58(),
+    );
+
+    await expectError(
+      ctx.program.methods
+        .revokeKeeper(configId)
+        .accounts({
+          config: configPda,
+          admin: ctx.provider.wallet.publicKey,
+        })
+        .rpc(),
+      "ConstraintHasOne",
+    );
+  });
+
+  it("Maintains the swap program registry", async () => {
+    const ctx = await getContext();
+    await ensureGlobalConfig(ctx);
+
+    const jupiter = new anchor.web3.PublicKey(
+      "JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4",
+    );
+    const router = anchor.web3.Keypair.generate().publicKey;
+    const rogue = anchor.web3.Keypair.generate();
+    const setSwapProgram = (program: anchor.web3.PublicKey, allowed: boolean) =>
+      ctx.program.methods
+        .setSwapProgram(ctx.configId, program, allowed)
+        .accounts({
+          config: ctx.configPda,
+          admin: ctx.provider.wallet.publicKey,
+        })
+        .rpc();
+    const registry = async () =>
+      (
+        await ctx.program.account.globalConfig.fetch(ctx.configPda)
+      ).swapPrograms.map((program: anchor.web3.PublicKey) =>
+        program.toBase58(),
+      );
+
+    expect(await registry()).to.include(jupiter.toBase58());
+    await expectError(
+      setSwapProgram(anchor.web3.PublicKey.default, true),
+      "InvalidSwapProgram",
+    );
+    await expectError(
+      ctx.program.methods
+        .setSwapProgram(ctx.configId, router, true)
+        .accounts({ config: ctx.configPda, admin: rogue.publicKey })
+        .signers([rogue])
+        .rpc(),
+      "ConstraintHasOne",
+    );
+
+    await setSwapProgram(router, true);
+    await setSwapProgram(router, true);
+    const added = await registry();
+    expect(added.filter((key) => key === router.toBase58())).to.have.length(1);
+
+    await setSwapProgram(router, false);
+    expect(await registry()).to.not.include(router.toBase58());
+    expect(await registry()).to.include(jupiter.toBase58());
+  });
 });
@@ -31,6 +31,7 @@ describe("add-token", () => {
       token.tokenPythFeed.toBase58(),
     );
     expect(whitelistAccount.enabled).to.equal(true);
+    expect(whitelistAccount.kind).to.equal(0);
 
     await removeFundToken(ctx, token);
   });
@@ -248,7 +248,7 @@ describe("deposit", () => {
     await removeFundToken(ctx, tokenB);
   });
 
-  it("Rejects deposit when open limit orders are not provided", async () => {
+  it("Deposits without order accounts while a limit order is open", async () => {
     const ctx = await getContext();
     await ensureGlobalConfig(ctx);
     await ensureFund(ctx);
@@ -295,6 +295,9 @@ describe("deposit", () => {
         new anchor.BN(1),
         0,
         new anchor.BN(0),
+        0,
+        new anchor.BN(0),
+        0,
       )
       .accounts({
         manager: ctx.provider.wallet.publicKey,
@@ -318,6 +321,13 @@ describe("deposit", () => {
       })
       .rpc();
 
+    const fundStateAfterOrder = await ctx.program.account.fundState.fetch(
+      ctx.fundPda,
+    );
+    expect(fundStateAfterOrder.escrowedLamports.toNumber()).to.equal(
+      fundState.escrowedLamports.toNumber() + 10_000,
+    );
+
     const investorShareAccount =
       await anchor.utils.token.associatedAddress({
         mint: ctx.shareMintPda,
@@ -325,32 +335,36 @@ describe("deposit", () => {
       });
     const amountLamports = new anchor.BN(anchor.web3.LAMPORTS_PER_SOL / 4);
 
-    await expectError(
-      ctx.program.methods
-        .deposit(amountLamports)
-        .accounts({
-          investor: ctx.investor.publicKey,
-          config: ctx.configPda,
-          fundState: ctx.fundPda,
-          fundVault: ctx.vaultPda,
-          shareMint: ctx.shareMintPda,
-          investorShareAccount,
-          feeTreasury: ctx.feeTreasury.publicKey,
-          systemProgram: anchor.web3.SystemProgram.programId,
-          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
-          associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
-          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
-        })
-        .remainingAccounts([
-          { pubkey: ctx.solPythFeed, isWritable: false, isSigner: false },
-          { pubkey: token.fundWhitelistPda, isWritable: false, isSigner: false },
-          { pubkey: token.fundTokenVault, isWritable: false, isSigner: false },
-          { pubkey: token.tokenPythFeed, isWritable: false, isSigner: false },
-        ])
-        .signers([ctx.investor])
-        .rpc(),
-      "InvalidRemainingAccounts",
-    );
+    const sharesBefore = (
+      await ctx.program.account.fundState.fetch(ctx.fundPda)
+    ).totalShares;
+    await ctx.program.methods
+      .deposit(amountLamports)
+      .accounts({
+        investor: ctx.investor.publicKey,
+        config: ctx.configPda,
+        fundState: ctx.fundPda,
+        fundVault: ctx.vaultPda,
+        shareMint: ctx.shareMintPda,
+        investorShareAccount,
+        feeTreasury: ctx.feeTreasury.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .remainingAccounts([
+        { pubkey: ctx.solPythFeed, isWritable: false, isSigner: false },
+        { pubkey: token.fundWhitelistPda, isWritable: false, isSigner: false },
+        { pubkey: token.fundTokenVault, isWritable: false, isSigner: false },
+        { pubkey: token.tokenPythFeed, isWritable: false, isSigner: false },
+      ])
+      .signers([ctx.investor])
+      .rpc();
+    const sharesAfter = (
+      await ctx.program.account.fundState.fetch(ctx.fundPda)
+    ).totalShares;
+    expect(sharesAfter.gt(sharesBefore)).to.equal(true);
 
     await ctx.program.methods
       .cancelLimitOrder(orderId)
@@ -508,6 +522,7 @@ describe("deposit", () => {
         configBefore.minManagerDepositLamports,
         configBefore.minWithdrawTimelockSecs,
         configBefore.maxWithdrawTimelockSecs,
+        configBefore.configTimelockSecs,
       )
       .accounts({
         config: ctx.configPda,
@@ -556,6 +571,7 @@ describe("deposit", () => {
         configBefore.minManagerDepositLamports,
         configBefore.minWithdrawTimelockSecs,
         configBefore.maxWithdrawTimelockSecs,
+        configBefore.configTimelockSecs,
       )
       .accounts({
         config: ctx.configPda,
@@ -564,4 +580,403 @@ describe("deposit", () => {
       })
       .rpc();
   });
+
+  it("Deposits against a fresh NAV snapshot", async () => {
+    const ctx = await getContext();
+    await ensureGlobalConfig(ctx);
+    await ensureFund(ctx);
+    await airdropIfNeeded(
+      ctx.provider,
+      ctx.investor.publicKey,
+      2 * anchor.web3.LAMPORTS_PER_SOL,
+    );
+    await airdropIfNeeded(ctx.provider, ctx.solPythFeed, 1);
+
+    const token = await addFundToken(ctx);
+    await airdropIfNeeded(ctx.provider, token.tokenPythFeed, 1);
+
+    const navSnapshotPda = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("nav_snapshot"), ctx.fundPda.toBuffer()],
+      ctx.program.programId,
+    )[0];
+
+    await ctx.program.methods
+      .refreshNav()
+      .accounts({
+        caller: ctx.provider.wallet.publicKey,
+        config: ctx.configPda,
+        fundState: ctx.fundPda,
+        fundVault: ctx.vaultPda,
+        navSnapshot: navSnapshotPda,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .remainingAccounts([
+        { pubkey: ctx.solPythFeed, isWritable: false, isSigner: false },
+        { pubkey: token.fundWhitelistPda, isWritable: false, isSigner: false },
+        { pubkey: token.fundTokenVault, isWritable: false, isSigner: false },
+        { pubkey: token.tokenPythFeed, isWritable: false, isSigner: false },
+      ])
+      .rpc();
+
+    const fundState = await ctx.program.account.fundState.fetch(ctx.fundPda);
+    const snapshot = await ctx.program.account.navSnapshot.fetch(navSnapshotPda);
+    const fundVaultBalance = await ctx.provider.connection.getBalance(
+      ctx.vaultPda,
+    );
+    expect(snapshot.fund.toBase58()).to.equal(ctx.fundPda.toBase58());
+    expect(snapshot.assets.length).to.equal(fundState.enabledTokenCount);
+    expect(snapshot.solLamports.toNumber()).to.equal(
+      fundVaultBalance + fundState.escrowedLamports.toNumber(),
+    );
+    expect(snapshot.navLamports.toNumber()).to.equal(
+      snapshot.solLamports.toNumber() + snapshot.tokenValueLamports.toNumber(),
+    );
+    expect(snapshot.totalShares.toString()).to.equal(
+      fundState.totalShares.toString(),
+    );
+
+    const investorShareAccount =
+      await anchor.utils.token.associatedAddress({
+        mint: ctx.shareMintPda,
+        owner: ctx.investor.publicKey,
+      });
+    const depositAccounts = {
+      investor: ctx.investor.publicKey,
+      config: ctx.configPda,
+      fundState: ctx.fundPda,
+      fundVault: ctx.vaultPda,
+      shareMint: ctx.shareMintPda,
+      investorShareAccount,
+      feeTreasury: ctx.feeTreasury.publicKey,
+      systemProgram: anchor.web3.SystemProgram.programId,
+      tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+      rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+    };
+
+    const setSnapshotPolicy = (required: boolean, maxAgeSlots: number) =>
+      ctx.program.methods
+        .setNavSnapshotPolicy(required, new anchor.BN(maxAgeSlots))
+        .accounts({
+          manager: ctx.provider.wallet.publicKey,
+          fundState: ctx.fundPda,
+        })
+        .rpc();
+    await expectError(setSnapshotPolicy(true, 0), "InvalidNavSnapshotAge");
+    await expectError(setSnapshotPolicy(true, 151), "InvalidNavSnapshotAge");
+
+    await expectError(
+      ctx.program.methods
+        .deposit(new anchor.BN(anchor.web3.LAMPORTS_PER_SOL / 4))
+        .accounts(depositAccounts)
+        .remainingAccounts([
+          { pubkey: navSnapshotPda, isWritable: false, isSigner: false },
+        ])
+        .signers([ctx.investor])
+        .rpc(),
+      "InvalidOracle",
+    );
+
+    await setSnapshotPolicy(true, 150);
+    expect(
+      (await ctx.program.account.fundState.fetch(ctx.fundPda))
+        .navSnapshotRequired,
+    ).to.equal(true);
+    await ctx.program.methods
+      .deposit(new anchor.BN(anchor.web3.LAMPORTS_PER_SOL / 4))
+      .accounts(depositAccounts)
+      .remainingAccounts([
+        { pubkey: navSnapshotPda, isWritable: false, isSigner: false },
+      ])
+      .signers([ctx.investor])
+      .rpc();
+
+    const fundStateAfter = await ctx.program.account.fundState.fetch(
+      ctx.fundPda,
+    );
+    expect(fundStateAfter.totalShares.gt(fundState.totalShares)).to.equal(true);
+
+    const second = await addFundToken(ctx);
+    await expectError(
+      ctx.program.methods
+        .deposit(new anchor.BN(anchor.web3.LAMPORTS_PER_SOL / 4))
+        .accounts(depositAccounts)
+        .remainingAccounts([
+          { pubkey: navSnapshotPda, isWritable: false, isSigner: false },
+        ])
+        .signers([ctx.investor])
+        .rpc(),
+      "StaleNavSnapshot",
+    );
+
+    await setSnapshotPolicy(false, 150);
+    await removeFundToken(ctx, second);
+    await removeFundToken(ctx, token);
+  });
+
+  it("Refreshes NAV in batches across several calls", async () => {
+    const ctx = await getContext();
+    await ensureGlobalConfig(ctx);
+    await ensureFund(ctx);
+    await airdropIfNeeded(
+      ctx.provider,
+      ctx.investor.publicKey,
+      2 * anchor.web3.LAMPORTS_PER_SOL,
+    );
+    await airdropIfNeeded(ctx.provider, ctx.solPythFeed, 1);
+
+    const tokens = [];
+    for (let i = 0; i < 3; i++) {
+      const token = await addFundToken(ctx);
+      await airdropIfNeeded(ctx.provider, token.tokenPythFeed, 1);
+      tokens.push(token);
+    }
+    tokens.sort((a, b) => Buffer.compare(a.mint.toBuffer(), b.mint.toBuffer()));
+
+    const navSnapshotPda = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("nav_snapshot"), ctx.fundPda.toBuffer()],
+      ctx.program.programId,
+    )[0];
+    const refreshBatch = (batch: typeof tokens, assetCount = batch.length) =>
+      ctx.program.methods
+        .refreshNavBatch(assetCount)
+        .accounts({
+          caller: ctx.provider.wallet.publicKey,
+          config: ctx.configPda,
+          fundState: ctx.fundPda,
+          fundVault: ctx.vaultPda,
+          navSnapshot: navSnapshotPda,
+          solPriceFeed: ctx.solPythFeed,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .remainingAccounts(
+          batch.flatMap((token) => [
+            { pubkey: token.fundWhitelistPda, isWritable: false, isSigner: false },
+            { pubkey: token.fundTokenVault, isWritable: false, isSigner: false },
+            { pubkey: token.tokenPythFeed, isWritable: false, isSigner: false },
+          ]),
+        )
+        .rpc();
+
+    await expectError(refreshBatch(tokens, 13), "InvalidRemainingAccounts");
+
+    const published = await ctx.program.account.navSnapshot.fetchNullable(
+      navSnapshotPda,
+    );
+    await refreshBatch(tokens.slice(0, 2));
+    const partial = await ctx.program.account.navSnapshot.fetch(navSnapshotPda);
+    expect(partial.batchCursor).to.equal(2);
+    expect(partial.batchAssets.length).to.equal(2);
+    if (published) {
+      expect(partial.assets.length).to.equal(published.assets.length);
+      expect(partial.navLamports.toString()).to.equal(
+        published.navLamports.toString(),
+      );
+    }
+    expect(partial.batchLastMint.toBase58()).to.equal(
+      tokens[1].mint.toBase58(),
+    );
+
+    await expectError(
+      refreshBatch([tokens[2], tokens[2]]),
+      "InvalidWhitelistOrder",
+    );
+
+    await refreshBatch(tokens.slice(0, 1));
+    const restarted = await ctx.program.account.navSnapshot.fetch(
+      navSnapshotPda,
+    );
+    expect(restarted.batchCursor).to.equal(1);
+
+    await refreshBatch(tokens.slice(1));
+    const fundState = await ctx.program.account.fundState.fetch(ctx.fundPda);
+    const snapshot = await ctx.program.account.navSnapshot.fetch(navSnapshotPda);
+    expect(snapshot.batchCursor).to.equal(0);
+    expect(snapshot.batchAssets.length).to.equal(0);
+    expect(snapshot.assets.length).to.equal(3);
+    expect(snapshot.assets.map((asset) => asset.mint.toBase58())).to.deep.equal(
+      tokens.map((token) => token.mint.toBase58()),
+    );
+    expect(snapshot.slot.toString()).to.equal(restarted.batchSlot.toString());
+    expect(snapshot.holdingsNonce.toString()).to.equal(
+      fundState.holdingsNonce.toString(),
+    );
+    expect(snapshot.navLamports.toNumber()).to.equal(
+      snapshot.solLamports.toNumber() + snapshot.tokenValueLamports.toNumber(),
+    );
+
+    const policyAccounts = {
+      manager: ctx.provider.wallet.publicKey,
+      fundState: ctx.fundPda,
+    };
+    await ctx.program.methods
+      .setNavSnapshotPolicy(true, new anchor.BN(150))
+      .accounts(policyAccounts)
+      .rpc();
+    const investorShareAccount =
+      await anchor.utils.token.associatedAddress({
+        mint: ctx.shareMintPda,
+        owner: ctx.investor.publicKey,
+      });
+    await ctx.program.methods
+      .deposit(new anchor.BN(anchor.web3.LAMPORTS_PER_SOL / 4))
+      .accounts({
+        investor: ctx.investor.publicKey,
+        config: ctx.configPda,
+        fundState: ctx.fundPda,
+        fundVault: ctx.vaultPda,
+        shareMint: ctx.shareMintPda,
+        investorShareAccount,
+        feeTreasury: ctx.feeTreasury.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .remainingAccounts([
+        { pubkey: navSnapshotPda, isWritable: false, isSigner: false },
+      ])
+      .signers([ctx.investor])
+      .rpc();
+    await ctx.program.methods
+      .setNavSnapshotPolicy(false, new anchor.BN(150))
+      .accounts(policyAccounts)
+      .rpc();
+
+    for (const token of tokens) {
+      await removeFundToken(ctx, token);
+    }
+  });
+
+  it("Records NAV history samples at most once per interval", async () => {
+    const ctx = await getContext();
+    await ensureGlobalConfig(ctx);
+    await ensureFund(ctx);
+    await airdropIfNeeded(
+      ctx.provider,
+      ctx.investor.publicKey,
+      2 * anchor.web3.LAMPORTS_PER_SOL,
+    );
+
+    const navHistoryPda = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("nav_history"), ctx.fundPda.toBuffer()],
+      ctx.program.programId,
+    )[0];
+    const navSnapshotPda = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("nav_snapshot"), ctx.fundPda.toBuffer()],
+      ctx.program.programId,
+    )[0];
+    const historyAccounts = {
+      manager: ctx.provider.wallet.publicKey,
+      fundState: ctx.fundPda,
+      navHistory: navHistoryPda,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    };
+
+    await expectError(
+      ctx.program.methods
+        .configureNavHistory(new anchor.BN(0))
+        .accounts(historyAccounts)
+        .rpc(),
+      "InvalidNavHistoryInterval",
+    );
+
+    await ctx.program.methods
+      .configureNavHistory(new anchor.BN(3600))
+      .accounts(historyAccounts)
+      .rpc();
+    const historyBefore = await ctx.program.account.navHistory.fetch(
+      navHistoryPda,
+    );
+    expect(historyBefore.intervalSecs.toNumber()).to.equal(3600);
+    expect(historyBefore.samples.length).to.equal(0);
+
+    await ctx.program.methods
+      .refreshNav()
+      .accounts({
+        caller: ctx.provider.wallet.publicKey,
+        config: ctx.configPda,
+        fundState: ctx.fundPda,
+        fundVault: ctx.vaultPda,
+        navSnapshot: navSnapshotPda,
+        navHistory: navHistoryPda,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const snapshot = await ctx.program.account.navSnapshot.fetch(navSnapshotPda);
+    const history = await ctx.program.account.navHistory.fetch(navHistoryPda);
+    expect(history.samples.length).to.equal(1);
+    expect(history.head).to.equal(1);
+    expect(history.samples[0].navLamports.toString()).to.equal(
+      snapshot.navLamports.toString(),
+    );
+    expect(history.samples[0].totalShares.toString()).to.equal(
+      snapshot.totalShares.toString(),
+    );
+
+    const investorShareAccount =
+      await anchor.utils.token.associatedAddress({
+        mint: ctx.shareMintPda,
+        owner: ctx.investor.publicKey,
+      });
+    await ctx.program.methods
+      .deposit(new anchor.BN(anchor.web3.LAMPORTS_PER_SOL / 4))
+      .accounts({
+        investor: ctx.investor.publicKey,
+        config: ctx.configPda,
+        fundState: ctx.fundPda,
+        fundVault: ctx.vaultPda,
+        shareMint: ctx.shareMintPda,
+        investorShareAccount,
+        feeTreasury: ctx.feeTreasury.publicKey,
+        navHistory: navHistoryPda,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .signers([ctx.investor])
+      .rpc();
+
+    const historyAfter = await ctx.program.account.navHistory.fetch(
+      navHistoryPda,
+    );
+    expect(historyAfter.samples.length).to.equal(1);
+    expect(historyAfter.lastSampleTs.toString()).to.equal(
+      history.lastSampleTs.toString(),
+    );
+  });
+
+  it("Returns NAV and share price from get_nav", async () => {
+    const ctx = await getContext();
+    await ensureGlobalConfig(ctx);
+    await ensureFund(ctx);
+
+    const view = await ctx.program.methods
+      .getNav()
+      .accounts({
+        config: ctx.configPda,
+        fundState: ctx.fundPda,
+        fundVault: ctx.vaultPda,
+      })
+      .view();
+
+    const fundState = await ctx.program.account.fundState.fetch(ctx.fundPda);
+    const fundVaultBalance = await ctx.provider.connection.getBalance(
+      ctx.vaultPda,
+    );
+    const nav = fundVaultBalance + fundState.escrowedLamports.toNumber();
+    expect(view.fund.toBase58()).to.equal(ctx.fundPda.toBase58());
+    expect(view.navLamports.toNumber()).to.equal(nav);
+    expect(view.totalShares.toString()).to.equal(
+      fundState.totalShares.toString(),
+    );
+    expect(view.sharePriceLamports.toString()).to.equal(
+      new anchor.BN(nav)
+        .mul(new anchor.BN(1_000_000_000))
+        .div(fundState.totalShares)
+        .toString(),
+    );
+  });
 });
@@ -18,6 +18,10 @@ const JUPITER_PROGRAM_ID = new anchor.web3.PublicKey(
 
 const SIDE_BUY = 0;
 const SIDE_SELL = 1;
+const ORDER_TYPE_LIMIT = 0;
+const ORDER_TYPE_STOP = 1;
+const ORDER_TYPE_BRACKET = 2;
+const ORDER_TYPE_TRAILING_STOP = 3;
 const WSOL_MINT = new anchor.web3.PublicKey(
   "So11111111111111111111111111111111111111112"
 );
@@ -90,7 +94,10 @@ describe("limit-orders", () => {
         new anchor.BN(1),
         new anchor.BN(1),
         0,
-        new anchor.BN(0)
+        new anchor.BN(0),
+        ORDER_TYPE_LIMIT,
+        new anchor.BN(0),
+        0
       )
       .accounts({
         manager: ctx.provider.wallet.publicKey,
@@ -115,6 +122,7 @@ describe("limit-orders", () => {
     const orderAccount = await ctx.program.account.limitOrder.fetch(orderPda);
     expect(orderAccount.status).to.equal(0);
     expect(orderAccount.side).to.equal(SIDE_BUY);
+    expect(orderAccount.filledIn.toNumber()).to.equal(0);
 
     const orderSolBalance = await ctx.provider.connection.getBalance(
       orderSolVault
@@ -124,6 +132,12 @@ describe("limit-orders", () => {
     );
     expect(orderSolBalance).to.be.greaterThan(0);
     expect(fundVaultBefore - fundVaultAfter).to.equal(100_000);
+    const fundStateAfter = await ctx.program.account.fundState.fetch(
+      ctx.fundPda
+    );
+    expect(
+      fundStateAfter.escrowedLamports.sub(fundState.escrowedLamports).toNumber()
+    ).to.equal(100_000);
 
     await ctx.program.methods
       .cancelLimitOrder(orderId)
@@ -149,6 +163,10 @@ describe("limit-orders", () => {
     );
     expect(orderInfo).to.equal(null);
     expect(solVaultInfo).to.equal(null);
+    const fundVaultFinal = await ctx.provider.connection.getBalance(
+      ctx.vaultPda
+    );
+    expect(fundVaultFinal).to.equal(fundVaultBefore);
   });
 
   it("Rejects buy order with non-WSOL vault", async () => {
@@ -179,7 +197,10 @@ describe("limit-orders", () => {
           new anchor.BN(1),
           new anchor.BN(1),
           0,
-          new anchor.BN(0)
+          new anchor.BN(0),
+          ORDER_TYPE_LIMIT,
+          new anchor.BN(0),
+          0
         )
         .accounts({
           manager: ctx.provider.wallet.publicKey,
@@ -251,7 +272,10 @@ describe("limit-orders", () => {
         new anchor.BN(1),
         new anchor.BN(1),
         0,
-        new anchor.BN(0)
+        new anchor.BN(0),
+        ORDER_TYPE_LIMIT,
+        new anchor.BN(0),
+        0
       )
       .accounts({
         manager: ctx.provider.wallet.publicKey,
@@ -302,14 +326,23 @@ describe("limit-orders", () => {
     expect(orderInfo).to.equal(null);
   });
 
-  it("Rejects execute when oracle account is invalid", async () => {
+  it("Creates and cancels a sell bracket order", async () => {
     const ctx = await getContext();
     await ensureGlobalConfig(ctx);
     await ensureFund(ctx);
 
     const token = await addFundToken(ctx);
 
-    await airdropIfNeeded(ctx.provider, token.tokenPythFeed, 1);
+    const mintIx = createMintToInstruction(
+      token.mint,
+      token.fundTokenVault,
+      ctx.provider.wallet.publicKey,
+      1_000
+    );
+    await ctx.provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(mintIx),
+      []
+    );
 
     const fundState = await ctx.program.account.fundState.fetch(ctx.fundPda);
     const orderId = new anchor.BN(fundState.nextOrderId.toString());
@@ -318,24 +351,32 @@ describe("limit-orders", () => {
       orderId,
       ctx.program.programId
     );
+    const orderSolVault = deriveOrderSolVault(orderPda, ctx.program.programId);
     const orderVaultAuth = deriveOrderVaultAuth(
       orderPda,
       ctx.program.programId
     );
     const orderTokenVault = await anchor.utils.token.associatedAddress({
-      mint: WSOL_MINT,
+      mint: token.mint,
       owner: orderVaultAuth,
     });
-    const orderSolVault = deriveOrderSolVault(orderPda, ctx.program.programId);
+
+    const fundTokenBefore =
+      await ctx.provider.connection.getTokenAccountBalance(
+        token.fundTokenVault
+      );
 
     await ctx.program.methods
       .createLimitOrder(
-        SIDE_BUY,
-        new anchor.BN(50_000),
-        new anchor.BN(1),
+        SIDE_SELL,
+        new anchor.BN(500),
         new anchor.BN(1),
+        new anchor.BN(200),
         0,
-        new anchor.BN(0)
+        new anchor.BN(0),
+        ORDER_TYPE_BRACKET,
+        new anchor.BN(100),
+        0
       )
       .accounts({
         manager: ctx.provider.wallet.publicKey,
@@ -357,44 +398,56 @@ describe("limit-orders", () => {
       })
       .rpc();
 
-    await expectError(
-      ctx.program.methods
-        .executeLimitOrder(orderId, Buffer.from([1]))
-        .accounts({
-          executor: ctx.provider.wallet.publicKey,
-          config: ctx.configPda,
-          fundState: ctx.fundPda,
-          fundVault: ctx.vaultPda,
-          whitelist: token.fundWhitelistPda,
-          fundTokenVault: token.fundTokenVault,
-          order: orderPda,
-          orderSolVault,
-          orderVaultAuth,
-          orderTokenVault,
-          priceFeed: token.tokenPythFeed,
-          solPriceFeed: ctx.solPythFeed,
-          swapProgram: JUPITER_PROGRAM_ID,
-          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
-          systemProgram: anchor.web3.SystemProgram.programId,
-        })
-        .rpc(),
-      "InvalidOracle"
+    const orderAccount = await ctx.program.account.limitOrder.fetch(orderPda);
+    expect(orderAccount.orderType).to.equal(ORDER_TYPE_BRACKET);
+    expect(orderAccount.stopPrice.toNumber()).to.equal(100);
+    expect(orderAccount.activeLeg).to.equal(0);
+
+    const fundTokenAfter = await ctx.provider.connection.getTokenAccountBalance(
+      token.fundTokenVault
     );
+    expect(
+      Number(fundTokenBefore.value.amount) - Number(fundTokenAfter.value.amount)
+    ).to.equal(500);
 
-    await removeFundToken(ctx, token);
+    await ctx.program.methods
+      .cancelLimitOrder(orderId)
+      .accounts({
+        manager: ctx.provider.wallet.publicKey,
+        config: ctx.configPda,
+        fundState: ctx.fundPda,
+        fundVault: ctx.vaultPda,
+        whitelist: token.fundWhitelistPda,
+        fundTokenVault: token.fundTokenVault,
+        order: orderPda,
+        orderSolVault,
+        orderVaultAuth,
+        orderTokenVault,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const orderInfo = await ctx.provider.connection.getAccountInfo(orderPda);
+    expect(orderInfo).to.equal(null);
   });
 
-  it("Rejects create limit order by non-manager", async () => {
+  it("Creates and cancels a sell trailing stop order", async () => {
     const ctx = await getContext();
     await ensureGlobalConfig(ctx);
     await ensureFund(ctx);
 
     const token = await addFundToken(ctx);
-    const rogue = anchor.web3.Keypair.generate();
-    await airdropIfNeeded(
-      ctx.provider,
-      rogue.publicKey,
-      anchor.web3.LAMPORTS_PER_SOL / 2,
+
+    const mintIx = createMintToInstruction(
+      token.mint,
+      token.fundTokenVault,
+      ctx.provider.wallet.publicKey,
+      1_000
+    );
+    await ctx.provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(mintIx),
+      []
     );
 
     const fundState = await ctx.program.account.fundState.fetch(ctx.fundPda);
@@ -404,57 +457,105 @@ describe("limit-orders", () => {
       orderId,
       ctx.program.programId
     );
+    const orderSolVault = deriveOrderSolVault(orderPda, ctx.program.programId);
     const orderVaultAuth = deriveOrderVaultAuth(
       orderPda,
       ctx.program.programId
     );
-    const orderSolVault = deriveOrderSolVault(orderPda, ctx.program.programId);
     const orderTokenVault = await anchor.utils.token.associatedAddress({
-      mint: WSOL_MINT,
+      mint: token.mint,
       owner: orderVaultAuth,
     });
 
-    await expectError(
-      ctx.program.methods
-        .createLimitOrder(
-          SIDE_BUY,
-          new anchor.BN(10_000),
-          new anchor.BN(1),
-          new anchor.BN(1),
-          0,
-          new anchor.BN(0)
-        )
-        .accounts({
-          manager: rogue.publicKey,
-          config: ctx.configPda,
-          fundState: ctx.fundPda,
-          fundVault: ctx.vaultPda,
-          mint: token.mint,
-          whitelist: token.fundWhitelistPda,
-          order: orderPda,
-          orderSolVault,
-          orderVaultAuth,
-          orderTokenVault,
-          fundTokenVault: token.fundTokenVault,
-          wsolMint: WSOL_MINT,
-          systemProgram: anchor.web3.SystemProgram.programId,
-          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
-          associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
-          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
-        })
-        .signers([rogue])
-        .rpc(),
-      "Unauthorized"
+    const fundTokenBefore =
+      await ctx.provider.connection.getTokenAccountBalance(
+        token.fundTokenVault
+      );
+
+    await ctx.program.methods
+      .createLimitOrder(
+        SIDE_SELL,
+        new anchor.BN(500),
+        new anchor.BN(1),
+        new anchor.BN(0),
+        0,
+        new anchor.BN(0),
+        ORDER_TYPE_TRAILING_STOP,
+        new anchor.BN(100),
+        500
+      )
+      .accounts({
+        manager: ctx.provider.wallet.publicKey,
+        config: ctx.configPda,
+        fundState: ctx.fundPda,
+        fundVault: ctx.vaultPda,
+        mint: token.mint,
+        whitelist: token.fundWhitelistPda,
+        order: orderPda,
+        orderSolVault,
+        orderVaultAuth,
+        orderTokenVault,
+        fundTokenVault: token.fundTokenVault,
+        wsolMint: WSOL_MINT,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .rpc();
+
+    const orderAccount = await ctx.program.account.limitOrder.fetch(orderPda);
+    expect(orderAccount.orderType).to.equal(ORDER_TYPE_TRAILING_STOP);
+    expect(orderAccount.trailBps).to.equal(500);
+    expect(orderAccount.peakPrice.toNumber()).to.equal(100);
+
+    const fundTokenAfter = await ctx.provider.connection.getTokenAccountBalance(
+      token.fundTokenVault
     );
+    expect(
+      Number(fundTokenBefore.value.amount) - Number(fundTokenAfter.value.amount)
+    ).to.equal(500);
+
+    await ctx.program.methods
+      .cancelLimitOrder(orderId)
+      .accounts({
+        manager: ctx.provider.wallet.publicKey,
+        config: ctx.configPda,
+        fundState: ctx.fundPda,
+        fundVault: ctx.vaultPda,
+        whitelist: token.fundWhitelistPda,
+        fundTokenVault: token.fundTokenVault,
+        order: orderPda,
+        orderSolVault,
+        orderVaultAuth,
+        orderTokenVault,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const orderInfo = await ctx.provider.connection.getAccountInfo(orderPda);
+    expect(orderInfo).to.equal(null);
   });
 
-  it("Rejects create limit order with zero amount", async () => {
+  it("Amends a sell limit order in place", async () => {
     const ctx = await getContext();
     await ensureGlobalConfig(ctx);
     await ensureFund(ctx);
 
     const token = await addFundToken(ctx);
 
+    const mintIx = createMintToInstruction(
+      token.mint,
+      token.fundTokenVault,
+      ctx.provider.wallet.publicKey,
+      1_000
+    );
+    await ctx.provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(mintIx),
+      []
+    );
+
     const fundState = await ctx.program.account.fundState.fetch(ctx.fundPda);
     const orderId = new anchor.BN(fundState.nextOrderId.toString());
     const orderPda = deriveOrderPda(
@@ -462,38 +563,878 @@ describe("limit-orders", () => {
       orderId,
       ctx.program.programId
     );
+    const orderSolVault = deriveOrderSolVault(orderPda, ctx.program.programId);
     const orderVaultAuth = deriveOrderVaultAuth(
       orderPda,
       ctx.program.programId
     );
-    const orderSolVault = deriveOrderSolVault(orderPda, ctx.program.programId);
     const orderTokenVault = await anchor.utils.token.associatedAddress({
-      mint: WSOL_MINT,
+      mint: token.mint,
       owner: orderVaultAuth,
     });
 
+    await ctx.program.methods
+      .createLimitOrder(
+        SIDE_SELL,
+        new anchor.BN(500),
+        new anchor.BN(1),
+        new anchor.BN(1),
+        0,
+        new anchor.BN(0),
+        ORDER_TYPE_LIMIT,
+        new anchor.BN(0),
+        0
+      )
+      .accounts({
+        manager: ctx.provider.wallet.publicKey,
+        config: ctx.configPda,
+        fundState: ctx.fundPda,
+        fundVault: ctx.vaultPda,
+        mint: token.mint,
+        whitelist: token.fundWhitelistPda,
+        order: orderPda,
+        orderSolVault,
+        orderVaultAuth,
+        orderTokenVault,
+        fundTokenVault: token.fundTokenVault,
+        wsolMint: WSOL_MINT,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .rpc();
+
+    const amendAccounts = {
+      manager: ctx.provider.wallet.publicKey,
+      config: ctx.configPda,
+      fundState: ctx.fundPda,
+      fundVault: ctx.vaultPda,
+      whitelist: token.fundWhitelistPda,
+      fundTokenVault: token.fundTokenVault,
+      order: orderPda,
+      orderSolVault,
+      orderVaultAuth,
+      orderTokenVault,
+      tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    };
+
     await expectError(
       ctx.program.methods
-        .createLimitOrder(
-          SIDE_BUY,
+        .amendLimitOrder(
+          orderId,
           new anchor.BN(0),
           new anchor.BN(1),
           new anchor.BN(1),
+          new anchor.BN(0),
           0,
           new anchor.BN(0)
         )
-        .accounts({
-          manager: ctx.provider.wallet.publicKey,
-          config: ctx.configPda,
-          fundState: ctx.fundPda,
-          fundVault: ctx.vaultPda,
-          mint: token.mint,
-          whitelist: token.fundWhitelistPda,
-          order: orderPda,
-          orderSolVault,
-          orderVaultAuth,
-          orderTokenVault,
-          fundTokenVault: token.fundTokenVault,
+        .accounts(amendAccounts)
+        .rpc(),
+      "InvalidOrderAmount"
+    );
+
+    const fundTokenBefore =
+      await ctx.provider.connection.getTokenAccountBalance(
+        token.fundTokenVault
+      );
+
+    await ctx.program.methods
+      .amendLimitOrder(
+        orderId,
+        new anchor.BN(300),
+        new anchor.BN(2),
+        new anchor.BN(2),
+        new anchor.BN(0),
+        0,
+        new anchor.BN(0)
+      )
+      .accounts(amendAccounts)
+      .rpc();
+
+    const fundTokenAfter = await ctx.provider.connection.getTokenAccountBalance(
+      token.fundTokenVault
+    );
+    expect(
+      Number(fundTokenAfter.value.amount) - Number(fundTokenBefore.value.amount)
+    ).to.equal(200);
+    const orderTokenBalance =
+      await ctx.provider.connection.getTokenAccountBalance(orderTokenVault);
+    expect(Number(orderTokenBalance.value.amount)).to.equal(300);
+    const whitelistAccount = await ctx.program.account.fundWhitelist.fetch(
+      token.fundWhitelistPda
+    );
+    expect(whitelistAccount.escrowedAmount.toNumber()).to.equal(300);
+
+    const order = await ctx.program.account.limitOrder.fetch(orderPda);
+    expect(order.amountIn.toNumber()).to.equal(300);
+    expect(order.minOut.toNumber()).to.equal(2);
+    expect(order.limitPrice.toNumber()).to.equal(2);
+
+    await ctx.program.methods
+      .cancelLimitOrder(orderId)
+      .accounts({
+        manager: ctx.provider.wallet.publicKey,
+        config: ctx.configPda,
+        fundState: ctx.fundPda,
+        fundVault: ctx.vaultPda,
+        whitelist: token.fundWhitelistPda,
+        fundTokenVault: token.fundTokenVault,
+        order: orderPda,
+        orderSolVault,
+        orderVaultAuth,
+        orderTokenVault,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+  });
+
+  it("Creates and cancels a token-to-token buy limit order", async () => {
+    const ctx = await getContext();
+    await ensureGlobalConfig(ctx);
+    await ensureFund(ctx);
+
+    const base = await addFundToken(ctx);
+    const quote = await addFundToken(ctx);
+
+    const mintIx = createMintToInstruction(
+      quote.mint,
+      quote.fundTokenVault,
+      ctx.provider.wallet.publicKey,
+      1_000
+    );
+    await ctx.provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(mintIx),
+      []
+    );
+
+    const fundState = await ctx.program.account.fundState.fetch(ctx.fundPda);
+    const orderId = new anchor.BN(fundState.nextOrderId.toString());
+    const orderPda = deriveOrderPda(
+      ctx.fundPda,
+      orderId,
+      ctx.program.programId
+    );
+    const orderSolVault = deriveOrderSolVault(orderPda, ctx.program.programId);
+    const orderVaultAuth = deriveOrderVaultAuth(
+      orderPda,
+      ctx.program.programId
+    );
+    const orderTokenVault = await anchor.utils.token.associatedAddress({
+      mint: quote.mint,
+      owner: orderVaultAuth,
+    });
+
+    await ctx.program.methods
+      .createLimitOrder(
+        SIDE_BUY,
+        new anchor.BN(400),
+        new anchor.BN(1),
+        new anchor.BN(2),
+        0,
+        new anchor.BN(0),
+        ORDER_TYPE_LIMIT,
+        new anchor.BN(0),
+        0
+      )
+      .accounts({
+        manager: ctx.provider.wallet.publicKey,
+        config: ctx.configPda,
+        fundState: ctx.fundPda,
+        fundVault: ctx.vaultPda,
+        mint: base.mint,
+        whitelist: base.fundWhitelistPda,
+        order: orderPda,
+        orderSolVault,
+        orderVaultAuth,
+        orderTokenVault,
+        fundTokenVault: base.fundTokenVault,
+        wsolMint: WSOL_MINT,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .remainingAccounts([
+        { pubkey: quote.fundWhitelistPda, isWritable: true, isSigner: false },
+        { pubkey: quote.mint, isWritable: false, isSigner: false },
+        { pubkey: quote.fundTokenVault, isWritable: true, isSigner: false },
+      ])
+      .rpc();
+
+    const orderAccount = await ctx.program.account.limitOrder.fetch(orderPda);
+    expect(orderAccount.quoteMint.toBase58()).to.equal(quote.mint.toBase58());
+    expect(orderAccount.quotePriceFeed.toBase58()).to.equal(
+      quote.tokenPythFeed.toBase58()
+    );
+    const escrow = await ctx.provider.connection.getTokenAccountBalance(
+      orderTokenVault
+    );
+    expect(Number(escrow.value.amount)).to.equal(400);
+    const quoteWhitelist = await ctx.program.account.fundWhitelist.fetch(
+      quote.fundWhitelistPda
+    );
+    expect(quoteWhitelist.escrowedAmount.toNumber()).to.equal(400);
+
+    await ctx.program.methods
+      .cancelLimitOrder(orderId)
+      .accounts({
+        manager: ctx.provider.wallet.publicKey,
+        config: ctx.configPda,
+        fundState: ctx.fundPda,
+        fundVault: ctx.vaultPda,
+        whitelist: quote.fundWhitelistPda,
+        fundTokenVault: quote.fundTokenVault,
+        order: orderPda,
+        orderSolVault,
+        orderVaultAuth,
+        orderTokenVault,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const quoteAfter = await ctx.provider.connection.getTokenAccountBalance(
+      quote.fundTokenVault
+    );
+    expect(Number(quoteAfter.value.amount)).to.equal(1_000);
+    const quoteWhitelistAfter = await ctx.program.account.fundWhitelist.fetch(
+      quote.fundWhitelistPda
+    );
+    expect(quoteWhitelistAfter.escrowedAmount.toNumber()).to.equal(0);
+  });
+
+  it("Rejects a token-to-token order quoted in its own mint", async () => {
+    const ctx = await getContext();
+    await ensureGlobalConfig(ctx);
+    await ensureFund(ctx);
+
+    const token = await addFundToken(ctx);
+    const fundState = await ctx.program.account.fundState.fetch(ctx.fundPda);
+    const orderId = new anchor.BN(fundState.nextOrderId.toString());
+    const orderPda = deriveOrderPda(
+      ctx.fundPda,
+      orderId,
+      ctx.program.programId
+    );
+    const orderSolVault = deriveOrderSolVault(orderPda, ctx.program.programId);
+    const orderVaultAuth = deriveOrderVaultAuth(
+      orderPda,
+      ctx.program.programId
+    );
+    const orderTokenVault = await anchor.utils.token.associatedAddress({
+      mint: token.mint,
+      owner: orderVaultAuth,
+    });
+
+    await expectError(
+      ctx.program.methods
+        .createLimitOrder(
+          SIDE_SELL,
+          new anchor.BN(100),
+          new anchor.BN(1),
+          new anchor.BN(1),
+          0,
+          new anchor.BN(0),
+          ORDER_TYPE_LIMIT,
+          new anchor.BN(0),
+          0
+        )
+        .accounts({
+          manager: ctx.provider.wallet.publicKey,
+          config: ctx.configPda,
+          fundState: ctx.fundPda,
+          fundVault: ctx.vaultPda,
+          mint: token.mint,
+          whitelist: token.fundWhitelistPda,
+          order: orderPda,
+          orderSolVault,
+          orderVaultAuth,
+          orderTokenVault,
+          fundTokenVault: token.fundTokenVault,
+          wsolMint: WSOL_MINT,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+          associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        })
+        .remainingAccounts([
+          { pubkey: token.fundWhitelistPda, isWritable: false, isSigner: false },
+          { pubkey: token.mint, isWritable: false, isSigner: false },
+          { pubkey: token.fundTokenVault, isWritable: true, isSigner: false },
+        ])
+        .rpc(),
+      "InvalidTokenVault"
+    );
+  });
+
+  it("Rejects execute when oracle account is invalid", async () => {
+    const ctx = await getContext();
+    await ensureGlobalConfig(ctx);
+    await ensureFund(ctx);
+
+    const token = await addFundToken(ctx);
+
+    await airdropIfNeeded(ctx.provider, token.tokenPythFeed, 1);
+
+    const fundState = await ctx.program.account.fundState.fetch(ctx.fundPda);
+    const orderId = new anchor.BN(fundState.nextOrderId.toString());
+    const orderPda = deriveOrderPda(
+      ctx.fundPda,
+      orderId,
+      ctx.program.programId
+    );
+    const orderVaultAuth = deriveOrderVaultAuth(
+      orderPda,
+      ctx.program.programId
+    );
+    const orderTokenVault = await anchor.utils.token.associatedAddress({
+      mint: WSOL_MINT,
+      owner: orderVaultAuth,
+    });
+    const orderSolVault = deriveOrderSolVault(orderPda, ctx.program.programId);
+
+    await ctx.program.methods
+      .createLimitOrder(
+        SIDE_BUY,
+        new anchor.BN(50_000),
+        new anchor.BN(1),
+        new anchor.BN(1),
+        0,
+        new anchor.BN(0),
+        ORDER_TYPE_LIMIT,
+        new anchor.BN(0),
+        0
+      )
+      .accounts({
+        manager: ctx.provider.wallet.publicKey,
+        config: ctx.configPda,
+        fundState: ctx.fundPda,
+        fundVault: ctx.vaultPda,
+        mint: token.mint,
+        whitelist: token.fundWhitelistPda,
+        order: orderPda,
+        orderSolVault,
+        orderVaultAuth,
+        orderTokenVault,
+        fundTokenVault: token.fundTokenVault,
+        wsolMint: WSOL_MINT,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .rpc();
+
+    await expectError(
+      ctx.program.methods
+        .executeLimitOrder(orderId, new anchor.BN(50_000), Buffer.from([1]))
+        .accounts({
+          executor: ctx.provider.wallet.publicKey,
+          config: ctx.configPda,
+          fundState: ctx.fundPda,
+          manager: ctx.provider.wallet.publicKey,
+          fundVault: ctx.vaultPda,
+          whitelist: token.fundWhitelistPda,
+          fundTokenVault: token.fundTokenVault,
+          order: orderPda,
+          orderSolVault,
+          orderVaultAuth,
+          orderTokenVault,
+          priceFeed: token.tokenPythFeed,
+          solPriceFeed: ctx.solPythFeed,
+          swapProgram: JUPITER_PROGRAM_ID,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc(),
+      "InvalidOracle"
+    );
+
+    await removeFundToken(ctx, token);
+  });
+
+  it("Rejects create limit order by non-manager", async () => {
+    const ctx = await getContext();
+    await ensureGlobalConfig(ctx);
+    await ensureFund(ctx);
+
+    const token = await addFundToken(ctx);
+    const rogue = anchor.web3.Keypair.generate();
+    await airdropIfNeeded(
+      ctx.provider,
+      rogue.publicKey,
+      anchor.web3.LAMPORTS_PER_SOL / 2,
+    );
+
+    const fundState = await ctx.program.account.fundState.fetch(ctx.fundPda);
+    const orderId = new anchor.BN(fundState.nextOrderId.toString());
+    const orderPda = deriveOrderPda(
+      ctx.fundPda,
+      orderId,
+      ctx.program.programId
+    );
+    const orderVaultAuth = deriveOrderVaultAuth(
+      orderPda,
+      ctx.program.programId
+    );
+    const orderSolVault = deriveOrderSolVault(orderPda, ctx.program.programId);
+    const orderTokenVault = await anchor.utils.token.associatedAddress({
+      mint: WSOL_MINT,
+      owner: orderVaultAuth,
+    });
+
+    await expectError(
+      ctx.program.methods
+        .createLimitOrder(
+          SIDE_BUY,
+          new anchor.BN(10_000),
+          new anchor.BN(1),
+          new anchor.BN(1),
+          0,
+          new anchor.BN(0),
+          ORDER_TYPE_LIMIT,
+          new anchor.BN(0),
+          0
+        )
+        .accounts({
+          manager: rogue.publicKey,
+          config: ctx.configPda,
+          fundState: ctx.fundPda,
+          fundVault: ctx.vaultPda,
+          mint: token.mint,
+          whitelist: token.fundWhitelistPda,
+          order: orderPda,
+          orderSolVault,
+          orderVaultAuth,
+          orderTokenVault,
+          fundTokenVault: token.fundTokenVault,
+          wsolMint: WSOL_MINT,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+          associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        })
+        .signers([rogue])
+        .rpc(),
+      "Unauthorized"
+    );
+  });
+
+  it("Rejects create limit order with zero amount", async () => {
+    const ctx = await getContext();
+    await ensureGlobalConfig(ctx);
+    await ensureFund(ctx);
+
+    const token = await addFundToken(ctx);
+
+    const fundState = await ctx.program.account.fundState.fetch(ctx.fundPda);
+    const orderId = new anchor.BN(fundState.nextOrderId.toString());
+    const orderPda = deriveOrderPda(
+      ctx.fundPda,
+      orderId,
+      ctx.program.programId
+    );
+    const orderVaultAuth = deriveOrderVaultAuth(
+      orderPda,
+      ctx.program.programId
+    );
+    const orderSolVault = deriveOrderSolVault(orderPda, ctx.program.programId);
+    const orderTokenVault = await anchor.utils.token.associatedAddress({
+      mint: WSOL_MINT,
+      owner: orderVaultAuth,
+    });
+
+    await expectError(
+      ctx.program.methods
+        .createLimitOrder(
+          SIDE_BUY,
+          new anchor.BN(0),
+          new anchor.BN(1),
+          new anchor.BN(1),
+          0,
+          new anchor.BN(0),
+          ORDER_TYPE_LIMIT,
+          new anchor.BN(0),
+          0
+        )
+        .accounts({
+          manager: ctx.provider.wallet.publicKey,
+          config: ctx.configPda,
+          fundState: ctx.fundPda,
+          fundVault: ctx.vaultPda,
+          mint: token.mint,
+          whitelist: token.fundWhitelistPda,
+          order: orderPda,
+          orderSolVault,
+          orderVaultAuth,
+          orderTokenVault,
+          fundTokenVault: token.fundTokenVault,
+          wsolMint: WSOL_MINT,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+          associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        })
+        .rpc(),
+      "MathOverflow"
+    );
+  });
+
+  it("Rejects create limit order with zero min_out", async () => {
+    const ctx = await getContext();
+    await ensureGlobalConfig(ctx);
+    await ensureFund(ctx);
+
+    const token = await addFundToken(ctx);
+
+    const fundState = await ctx.program.account.fundState.fetch(ctx.fundPda);
+    const orderId = new anchor.BN(fundState.nextOrderId.toString());
+    const orderPda = deriveOrderPda(
+      ctx.fundPda,
+      orderId,
+      ctx.program.programId
+    );
+    const orderVaultAuth = deriveOrderVaultAuth(
+      orderPda,
+      ctx.program.programId
+    );
+    const orderSolVault = deriveOrderSolVault(orderPda, ctx.program.programId);
+    const orderTokenVault = await anchor.utils.token.associatedAddress({
+      mint: WSOL_MINT,
+      owner: orderVaultAuth,
+    });
+
+    await expectError(
+      ctx.program.methods
+        .createLimitOrder(
+          SIDE_BUY,
+          new anchor.BN(10_000),
+          new anchor.BN(0),
+          new anchor.BN(1),
+          0,
+          new anchor.BN(0),
+          ORDER_TYPE_LIMIT,
+          new anchor.BN(0),
+          0
+        )
+        .accounts({
+          manager: ctx.provider.wallet.publicKey,
+          config: ctx.configPda,
+          fundState: ctx.fundPda,
+          fundVault: ctx.vaultPda,
+          mint: token.mint,
+          whitelist: token.fundWhitelistPda,
+          order: orderPda,
+          orderSolVault,
+          orderVaultAuth,
+          orderTokenVault,
+          fundTokenVault: token.fundTokenVault,
+          wsolMint: WSOL_MINT,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+          associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        })
+        .rpc(),
+      "InvalidMinOut"
+    );
+  });
+
+  it("Rejects bracket order with stop above take-profit", async () => {
+    const ctx = await getContext();
+    await ensureGlobalConfig(ctx);
+    await ensureFund(ctx);
+
+    const token = await addFundToken(ctx);
+
+    const fundState = await ctx.program.account.fundState.fetch(ctx.fundPda);
+    const orderId = new anchor.BN(fundState.nextOrderId.toString());
+    const orderPda = deriveOrderPda(
+      ctx.fundPda,
+      orderId,
+      ctx.program.programId
+    );
+    const orderVaultAuth = deriveOrderVaultAuth(
+      orderPda,
+      ctx.program.programId
+    );
+    const orderSolVault = deriveOrderSolVault(orderPda, ctx.program.programId);
+    const orderTokenVault = await anchor.utils.token.associatedAddress({
+      mint: token.mint,
+      owner: orderVaultAuth,
+    });
+
+    await expectError(
+      ctx.program.methods
+        .createLimitOrder(
+          SIDE_SELL,
+          new anchor.BN(10_000),
+          new anchor.BN(1),
+          new anchor.BN(100),
+          0,
+          new anchor.BN(0),
+          ORDER_TYPE_BRACKET,
+          new anchor.BN(150),
+          0
+        )
+        .accounts({
+          manager: ctx.provider.wallet.publicKey,
+          config: ctx.configPda,
+          fundState: ctx.fundPda,
+          fundVault: ctx.vaultPda,
+          mint: token.mint,
+          whitelist: token.fundWhitelistPda,
+          order: orderPda,
+          orderSolVault,
+          orderVaultAuth,
+          orderTokenVault,
+          fundTokenVault: token.fundTokenVault,
+          wsolMint: WSOL_MINT,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+          associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        })
+        .rpc(),
+      "InvalidOrderType"
+    );
+  });
+
+  it("Rejects stop order with a limit price", async () => {
+    const ctx = await getContext();
+    await ensureGlobalConfig(ctx);
+    await ensureFund(ctx);
+
+    const token = await addFundToken(ctx);
+
+    const fundState = await ctx.program.account.fundState.fetch(ctx.fundPda);
+    const orderId = new anchor.BN(fundState.nextOrderId.toString());
+    const orderPda = deriveOrderPda(
+      ctx.fundPda,
+      orderId,
+      ctx.program.programId
+    );
+    const orderVaultAuth = deriveOrderVaultAuth(
+      orderPda,
+      ctx.program.programId
+    );
+    const orderSolVault = deriveOrderSolVault(orderPda, ctx.program.programId);
+    const orderTokenVault = await anchor.utils.token.associatedAddress({
+      mint: WSOL_MINT,
+      owner: orderVaultAuth,
+    });
+
+    await expectError(
+      ctx.program.methods
+        .createLimitOrder(
+          SIDE_BUY,
+          new anchor.BN(10_000),
+          new anchor.BN(1),
+          new anchor.BN(1),
+          0,
+          new anchor.BN(0),
+          ORDER_TYPE_STOP,
+          new anchor.BN(100),
+          0
+        )
+        .accounts({
+          manager: ctx.provider.wallet.publicKey,
+          config: ctx.configPda,
+          fundState: ctx.fundPda,
+          fundVault: ctx.vaultPda,
+          mint: token.mint,
+          whitelist: token.fundWhitelistPda,
+          order: orderPda,
+          orderSolVault,
+          orderVaultAuth,
+          orderTokenVault,
+          fundTokenVault: token.fundTokenVault,
+          wsolMint: WSOL_MINT,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+          associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        })
+        .rpc(),
+      "InvalidOrderType"
+    );
+  });
+
+  it("Rejects trailing stop with zero trail", async () => {
+    const ctx = await getContext();
+    await ensureGlobalConfig(ctx);
+    await ensureFund(ctx);
+
+    const token = await addFundToken(ctx);
+
+    const fundState = await ctx.program.account.fundState.fetch(ctx.fundPda);
+    const orderId = new anchor.BN(fundState.nextOrderId.toString());
+    const orderPda = deriveOrderPda(
+      ctx.fundPda,
+      orderId,
+      ctx.program.programId
+    );
+    const orderVaultAuth = deriveOrderVaultAuth(
+      orderPda,
+      ctx.program.programId
+    );
+    const orderSolVault = deriveOrderSolVault(orderPda, ctx.program.programId);
+    const orderTokenVault = await anchor.utils.token.associatedAddress({
+      mint: WSOL_MINT,
+      owner: orderVaultAuth,
+    });
+
+    await expectError(
+      ctx.program.methods
+        .createLimitOrder(
+          SIDE_BUY,
+          new anchor.BN(10_000),
+          new anchor.BN(1),
+          new anchor.BN(0),
+          0,
+          new anchor.BN(0),
+          ORDER_TYPE_TRAILING_STOP,
+          new anchor.BN(100),
+          0
+        )
+        .accounts({
+          manager: ctx.provider.wallet.publicKey,
+          config: ctx.configPda,
+          fundState: ctx.fundPda,
+          fundVault: ctx.vaultPda,
+          mint: token.mint,
+          whitelist: token.fundWhitelistPda,
+          order: orderPda,
+          orderSolVault,
+          orderVaultAuth,
+          orderTokenVault,
+          fundTokenVault: token.fundTokenVault,
+          wsolMint: WSOL_MINT,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+          associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        })
+        .rpc(),
+      "InvalidOrderType"
+    );
+  });
+
+  it("Rejects create limit order with invalid side", async () => {
+    const ctx = await getContext();
+    await ensureGlobalConfig(ctx);
+    await ensureFund(ctx);
+
+    const token = await addFundToken(ctx);
+
+    const fundState = await ctx.program.account.fundState.fetch(ctx.fundPda);
+    const orderId = new anchor.BN(fundState.nextOrderId.toString());
+    const orderPda = deriveOrderPda(
+      ctx.fundPda,
+      orderId,
+      ctx.program.programId
+    );
+    const orderVaultAuth = deriveOrderVaultAuth(
+      orderPda,
+      ctx.program.programId
+    );
+    const orderSolVault = deriveOrderSolVault(orderPda, ctx.program.programId);
+    const orderTokenVault = await anchor.utils.token.associatedAddress({
+      mint: WSOL_MINT,
+      owner: orderVaultAuth,
+    });
+
+    await expectError(
+      ctx.program.methods
+        .createLimitOrder(
+          3,
+          new anchor.BN(10_000),
+          new anchor.BN(1),
+          new anchor.BN(1),
+          0,
+          new anchor.BN(0),
+          ORDER_TYPE_LIMIT,
+          new anchor.BN(0),
+          0
+        )
+        .accounts({
+          manager: ctx.provider.wallet.publicKey,
+          config: ctx.configPda,
+          fundState: ctx.fundPda,
+          fundVault: ctx.vaultPda,
+          mint: token.mint,
+          whitelist: token.fundWhitelistPda,
+          order: orderPda,
+          orderSolVault,
+          orderVaultAuth,
+          orderTokenVault,
+          fundTokenVault: token.fundTokenVault,
+          wsolMint: WSOL_MINT,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+          associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        })
+        .rpc(),
+      "InvalidOrderSide"
+    );
+  });
+
+  it("Rejects create limit order with insufficient liquidity", async () => {
+    const ctx = await getContext();
+    await ensureGlobalConfig(ctx);
+    await ensureFund(ctx);
+
+    const token = await addFundToken(ctx);
+
+    const fundState = await ctx.program.account.fundState.fetch(ctx.fundPda);
+    const orderId = new anchor.BN(fundState.nextOrderId.toString());
+    const orderPda = deriveOrderPda(
+      ctx.fundPda,
+      orderId,
+      ctx.program.programId
+    );
+    const orderVaultAuth = deriveOrderVaultAuth(
+      orderPda,
+      ctx.program.programId
+    );
+    const orderSolVault = deriveOrderSolVault(orderPda, ctx.program.programId);
+    const orderTokenVault = await anchor.utils.token.associatedAddress({
+      mint: WSOL_MINT,
+      owner: orderVaultAuth,
+    });
+
+    const tooLarge = new anchor.BN(anchor.web3.LAMPORTS_PER_SOL);
+
+    await expectError(
+      ctx.program.methods
+        .createLimitOrder(
+          SIDE_BUY,
+          tooLarge,
+          new anchor.BN(1),
+          new anchor.BN(1),
+          0,
+          new anchor.BN(0),
+          ORDER_TYPE_LIMIT,
+          new anchor.BN(0),
+          0
+        )
+        .accounts({
+          manager: ctx.provider.wallet.publicKey,
+          config: ctx.configPda,
+          fundState: ctx.fundPda,
+          fundVault: ctx.vaultPda,
+          mint: token.mint,
+          whitelist: token.fundWhitelistPda,
+          order: orderPda,
+          orderSolVault,
+          orderVaultAuth,
+          orderTokenVault,
+          fundTokenVault: token.fundTokenVault,
           wsolMint: WSOL_MINT,
           systemProgram: anchor.web3.SystemProgram.programId,
           tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
@@ -501,11 +1442,94 @@ describe("limit-orders", () => {
           rent: anchor.web3.SYSVAR_RENT_PUBKEY,
         })
         .rpc(),
-      "MathOverflow"
+      "InsufficientLiquidity"
+    );
+  });
+
+  it("Rejects execute limit order by non-keeper", async () => {
+    const ctx = await getContext();
+    await ensureGlobalConfig(ctx);
+    await ensureFund(ctx);
+
+    const token = await addFundToken(ctx);
+
+    const fundState = await ctx.program.account.fundState.fetch(ctx.fundPda);
+    const orderId = new anchor.BN(fundState.nextOrderId.toString());
+    const orderPda = deriveOrderPda(
+      ctx.fundPda,
+      orderId,
+      ctx.program.programId
+    );
+    const orderVaultAuth = deriveOrderVaultAuth(
+      orderPda,
+      ctx.program.programId
+    );
+    const orderTokenVault = await anchor.utils.token.associatedAddress({
+      mint: WSOL_MINT,
+      owner: orderVaultAuth,
+    });
+    const orderSolVault = deriveOrderSolVault(orderPda, ctx.program.programId);
+
+    await ctx.program.methods
+      .createLimitOrder(
+        SIDE_BUY,
+        new anchor.BN(10_000),
+        new anchor.BN(1),
+        new anchor.BN(1),
+        0,
+        new anchor.BN(0),
+        ORDER_TYPE_LIMIT,
+        new anchor.BN(0),
+        0
+      )
+      .accounts({
+        manager: ctx.provider.wallet.publicKey,
+        config: ctx.configPda,
+        fundState: ctx.fundPda,
+        fundVault: ctx.vaultPda,
+        mint: token.mint,
+        whitelist: token.fundWhitelistPda,
+        order: orderPda,
+        orderSolVault,
+        orderVaultAuth,
+        orderTokenVault,
+        fundTokenVault: token.fundTokenVault,
+        wsolMint: WSOL_MINT,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .rpc();
+
+    await expectError(
+      ctx.program.methods
+        .executeLimitOrder(orderId, new anchor.BN(10_000), Buffer.from([1]))
+        .accounts({
+          executor: ctx.investor.publicKey,
+          config: ctx.configPda,
+          fundState: ctx.fundPda,
+          manager: ctx.provider.wallet.publicKey,
+          fundVault: ctx.vaultPda,
+          whitelist: token.fundWhitelistPda,
+          fundTokenVault: token.fundTokenVault,
+          order: orderPda,
+          orderSolVault,
+          orderVaultAuth,
+          orderTokenVault,
+          priceFeed: token.tokenPythFeed,
+          solPriceFeed: ctx.solPythFeed,
+          swapProgram: JUPITER_PROGRAM_ID,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([ctx.investor])
+        .rpc(),
+      "Unauthorized"
     );
   });
 
-  it("Rejects create limit order with zero min_out", async () => {
+  it("Rejects execute limit order with fill above the remaining amount", async () => {
     const ctx = await getContext();
     await ensureGlobalConfig(ctx);
     await ensureFund(ctx);
@@ -523,46 +1547,71 @@ describe("limit-orders", () => {
       orderPda,
       ctx.program.programId
     );
-    const orderSolVault = deriveOrderSolVault(orderPda, ctx.program.programId);
     const orderTokenVault = await anchor.utils.token.associatedAddress({
       mint: WSOL_MINT,
       owner: orderVaultAuth,
     });
+    const orderSolVault = deriveOrderSolVault(orderPda, ctx.program.programId);
+
+    await ctx.program.methods
+      .createLimitOrder(
+        SIDE_BUY,
+        new anchor.BN(10_000),
+        new anchor.BN(1),
+        new anchor.BN(1),
+        0,
+        new anchor.BN(0),
+        ORDER_TYPE_LIMIT,
+        new anchor.BN(0),
+        0
+      )
+      .accounts({
+        manager: ctx.provider.wallet.publicKey,
+        config: ctx.configPda,
+        fundState: ctx.fundPda,
+        fundVault: ctx.vaultPda,
+        mint: token.mint,
+        whitelist: token.fundWhitelistPda,
+        order: orderPda,
+        orderSolVault,
+        orderVaultAuth,
+        orderTokenVault,
+        fundTokenVault: token.fundTokenVault,
+        wsolMint: WSOL_MINT,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .rpc();
 
     await expectError(
       ctx.program.methods
-        .createLimitOrder(
-          SIDE_BUY,
-          new anchor.BN(10_000),
-          new anchor.BN(0),
-          new anchor.BN(1),
-          0,
-          new anchor.BN(0)
-        )
+        .executeLimitOrder(orderId, new anchor.BN(10_001), Buffer.from([1]))
         .accounts({
-          manager: ctx.provider.wallet.publicKey,
+          executor: ctx.provider.wallet.publicKey,
           config: ctx.configPda,
           fundState: ctx.fundPda,
+          manager: ctx.provider.wallet.publicKey,
           fundVault: ctx.vaultPda,
-          mint: token.mint,
           whitelist: token.fundWhitelistPda,
+          fundTokenVault: token.fundTokenVault,
           order: orderPda,
           orderSolVault,
           orderVaultAuth,
           orderTokenVault,
-          fundTokenVault: token.fundTokenVault,
-          wsolMint: WSOL_MINT,
-          systemProgram: anchor.web3.SystemProgram.programId,
+          priceFeed: token.tokenPythFeed,
+          solPriceFeed: ctx.solPythFeed,
+          swapProgram: JUPITER_PROGRAM_ID,
           tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
-          associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
-          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+          systemProgram: anchor.web3.SystemProgram.programId,
         })
         .rpc(),
-      "InvalidMinOut"
+      "InvalidFillAmount"
     );
   });
 
-  it("Rejects create limit order with invalid side", async () => {
+  it("Rejects execute limit order with fill below the minimum size", async () => {
     const ctx = await getContext();
     await ensureGlobalConfig(ctx);
     await ensureFund(ctx);
@@ -580,46 +1629,71 @@ describe("limit-orders", () => {
       orderPda,
       ctx.program.programId
     );
-    const orderSolVault = deriveOrderSolVault(orderPda, ctx.program.programId);
     const orderTokenVault = await anchor.utils.token.associatedAddress({
       mint: WSOL_MINT,
       owner: orderVaultAuth,
     });
+    const orderSolVault = deriveOrderSolVault(orderPda, ctx.program.programId);
+
+    await ctx.program.methods
+      .createLimitOrder(
+        SIDE_BUY,
+        new anchor.BN(10_000),
+        new anchor.BN(1),
+        new anchor.BN(1),
+        0,
+        new anchor.BN(0),
+        ORDER_TYPE_LIMIT,
+        new anchor.BN(0),
+        0
+      )
+      .accounts({
+        manager: ctx.provider.wallet.publicKey,
+        config: ctx.configPda,
+        fundState: ctx.fundPda,
+        fundVault: ctx.vaultPda,
+        mint: token.mint,
+        whitelist: token.fundWhitelistPda,
+        order: orderPda,
+        orderSolVault,
+        orderVaultAuth,
+        orderTokenVault,
+        fundTokenVault: token.fundTokenVault,
+        wsolMint: WSOL_MINT,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .rpc();
 
     await expectError(
       ctx.program.methods
-        .createLimitOrder(
-          3,
-          new anchor.BN(10_000),
-          new anchor.BN(1),
-          new anchor.BN(1),
-          0,
-          new anchor.BN(0)
-        )
+        .executeLimitOrder(orderId, new anchor.BN(99), Buffer.from([1]))
         .accounts({
-          manager: ctx.provider.wallet.publicKey,
+          executor: ctx.provider.wallet.publicKey,
           config: ctx.configPda,
           fundState: ctx.fundPda,
+          manager: ctx.provider.wallet.publicKey,
           fundVault: ctx.vaultPda,
-          mint: token.mint,
           whitelist: token.fundWhitelistPda,
+          fundTokenVault: token.fundTokenVault,
           order: orderPda,
           orderSolVault,
           orderVaultAuth,
           orderTokenVault,
-          fundTokenVault: token.fundTokenVault,
-          wsolMint: WSOL_MINT,
-          systemProgram: anchor.web3.SystemProgram.programId,
+          priceFeed: token.tokenPythFeed,
+          solPriceFeed: ctx.solPythFeed,
+          swapProgram: JUPITER_PROGRAM_ID,
           tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
-          associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
-          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+          systemProgram: anchor.web3.SystemProgram.programId,
         })
         .rpc(),
-      "InvalidOrderSide"
+      "InvalidFillAmount"
     );
   });
 
-  it("Rejects create limit order with insufficient liquidity", async () => {
+  it("Rejects execute limit order when expired", async () => {
     const ctx = await getContext();
     await ensureGlobalConfig(ctx);
     await ensureFund(ctx);
@@ -637,53 +1711,81 @@ describe("limit-orders", () => {
       orderPda,
       ctx.program.programId
     );
-    const orderSolVault = deriveOrderSolVault(orderPda, ctx.program.programId);
     const orderTokenVault = await anchor.utils.token.associatedAddress({
       mint: WSOL_MINT,
       owner: orderVaultAuth,
     });
+    const orderSolVault = deriveOrderSolVault(orderPda, ctx.program.programId);
 
-    const tooLarge = new anchor.BN(anchor.web3.LAMPORTS_PER_SOL);
+    const expiry = new anchor.BN(
+      (await getClockUnixTimestamp(ctx.provider.connection)) - 5
+    );
+
+    await ctx.program.methods
+      .createLimitOrder(
+        SIDE_BUY,
+        new anchor.BN(10_000),
+        new anchor.BN(1),
+        new anchor.BN(1),
+        0,
+        expiry,
+        ORDER_TYPE_LIMIT,
+        new anchor.BN(0),
+        0
+      )
+      .accounts({
+        manager: ctx.provider.wallet.publicKey,
+        config: ctx.configPda,
+        fundState: ctx.fundPda,
+        fundVault: ctx.vaultPda,
+        mint: token.mint,
+        whitelist: token.fundWhitelistPda,
+        order: orderPda,
+        orderSolVault,
+        orderVaultAuth,
+        orderTokenVault,
+        fundTokenVault: token.fundTokenVault,
+        wsolMint: WSOL_MINT,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .rpc();
 
     await expectError(
       ctx.program.methods
-        .createLimitOrder(
-          SIDE_BUY,
-          tooLarge,
-          new anchor.BN(1),
-          new anchor.BN(1),
-          0,
-          new anchor.BN(0)
-        )
+        .executeLimitOrder(orderId, new anchor.BN(10_000), Buffer.from([1]))
         .accounts({
-          manager: ctx.provider.wallet.publicKey,
+          executor: ctx.provider.wallet.publicKey,
           config: ctx.configPda,
           fundState: ctx.fundPda,
+          manager: ctx.provider.wallet.publicKey,
           fundVault: ctx.vaultPda,
-          mint: token.mint,
           whitelist: token.fundWhitelistPda,
+          fundTokenVault: token.fundTokenVault,
           order: orderPda,
           orderSolVault,
           orderVaultAuth,
           orderTokenVault,
-          fundTokenVault: token.fundTokenVault,
-          wsolMint: WSOL_MINT,
-          systemProgram: anchor.web3.SystemProgram.programId,
+          priceFeed: token.tokenPythFeed,
+          solPriceFeed: ctx.solPythFeed,
+          swapProgram: JUPITER_PROGRAM_ID,
           tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
-          associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
-          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+          systemProgram: anchor.web3.SystemProgram.programId,
         })
         .rpc(),
-      "InsufficientLiquidity"
+      "OrderExpired"
     );
   });
 
-  it("Rejects execute limit order by non-keeper", async () => {
+  it("Expires a buy limit order from any caller", async () => {
     const ctx = await getContext();
     await ensureGlobalConfig(ctx);
     await ensureFund(ctx);
 
     const token = await addFundToken(ctx);
+    const cranker = anchor.web3.Keypair.generate();
 
     const fundState = await ctx.program.account.fundState.fetch(ctx.fundPda);
     const orderId = new anchor.BN(fundState.nextOrderId.toString());
@@ -702,6 +1804,14 @@ describe("limit-orders", () => {
     });
     const orderSolVault = deriveOrderSolVault(orderPda, ctx.program.programId);
 
+    const expiry = new anchor.BN(
+      (await getClockUnixTimestamp(ctx.provider.connection)) - 5
+    );
+
+    const fundVaultBefore = await ctx.provider.connection.getBalance(
+      ctx.vaultPda
+    );
+
     await ctx.program.methods
       .createLimitOrder(
         SIDE_BUY,
@@ -709,7 +1819,10 @@ describe("limit-orders", () => {
         new anchor.BN(1),
         new anchor.BN(1),
         0,
-        new anchor.BN(0)
+        expiry,
+        ORDER_TYPE_LIMIT,
+        new anchor.BN(0),
+        0
       )
       .accounts({
         manager: ctx.provider.wallet.publicKey,
@@ -731,33 +1844,44 @@ describe("limit-orders", () => {
       })
       .rpc();
 
-    await expectError(
-      ctx.program.methods
-        .executeLimitOrder(orderId, Buffer.from([1]))
-        .accounts({
-          executor: ctx.investor.publicKey,
-          config: ctx.configPda,
-          fundState: ctx.fundPda,
-          fundVault: ctx.vaultPda,
-          whitelist: token.fundWhitelistPda,
-          fundTokenVault: token.fundTokenVault,
-          order: orderPda,
-          orderSolVault,
-          orderVaultAuth,
-          orderTokenVault,
-          priceFeed: token.tokenPythFeed,
-          solPriceFeed: ctx.solPythFeed,
-          swapProgram: JUPITER_PROGRAM_ID,
-          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
-          systemProgram: anchor.web3.SystemProgram.programId,
-        })
-        .signers([ctx.investor])
-        .rpc(),
-      "Unauthorized"
+    await ctx.program.methods
+      .expireLimitOrder(orderId)
+      .accounts({
+        caller: cranker.publicKey,
+        fundState: ctx.fundPda,
+        manager: ctx.provider.wallet.publicKey,
+        fundVault: ctx.vaultPda,
+        whitelist: token.fundWhitelistPda,
+        fundTokenVault: token.fundTokenVault,
+        order: orderPda,
+        orderSolVault,
+        orderVaultAuth,
+        orderTokenVault,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([cranker])
+      .rpc();
+
+    const fundVaultAfter = await ctx.provider.connection.getBalance(
+      ctx.vaultPda
+    );
+    expect(fundVaultAfter).to.equal(fundVaultBefore);
+    expect(await ctx.provider.connection.getAccountInfo(orderPda)).to.equal(
+      null
+    );
+    expect(
+      await ctx.provider.connection.getAccountInfo(orderSolVault)
+    ).to.equal(null);
+    const fundStateAfter = await ctx.program.account.fundState.fetch(
+      ctx.fundPda
+    );
+    expect(fundStateAfter.activeLimitCount).to.equal(
+      fundState.activeLimitCount
     );
   });
 
-  it("Rejects execute limit order when expired", async () => {
+  it("Rejects expire of a limit order without an expiry", async () => {
     const ctx = await getContext();
     await ensureGlobalConfig(ctx);
     await ensureFund(ctx);
@@ -781,10 +1905,6 @@ describe("limit-orders", () => {
     });
     const orderSolVault = deriveOrderSolVault(orderPda, ctx.program.programId);
 
-    const expiry = new anchor.BN(
-      (await getClockUnixTimestamp(ctx.provider.connection)) - 5
-    );
-
     await ctx.program.methods
       .createLimitOrder(
         SIDE_BUY,
@@ -792,7 +1912,10 @@ describe("limit-orders", () => {
         new anchor.BN(1),
         new anchor.BN(1),
         0,
-        expiry
+        new anchor.BN(0),
+        ORDER_TYPE_LIMIT,
+        new anchor.BN(0),
+        0
       )
       .accounts({
         manager: ctx.provider.wallet.publicKey,
@@ -816,11 +1939,11 @@ describe("limit-orders", () => {
 
     await expectError(
       ctx.program.methods
-        .executeLimitOrder(orderId, Buffer.from([1]))
+        .expireLimitOrder(orderId)
         .accounts({
-          executor: ctx.provider.wallet.publicKey,
-          config: ctx.configPda,
+          caller: ctx.provider.wallet.publicKey,
           fundState: ctx.fundPda,
+          manager: ctx.provider.wallet.publicKey,
           fundVault: ctx.vaultPda,
           whitelist: token.fundWhitelistPda,
           fundTokenVault: token.fundTokenVault,
@@ -828,15 +1951,30 @@ describe("limit-orders", () => {
           orderSolVault,
           orderVaultAuth,
           orderTokenVault,
-          priceFeed: token.tokenPythFeed,
-          solPriceFeed: ctx.solPythFeed,
-          swapProgram: JUPITER_PROGRAM_ID,
           tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
           systemProgram: anchor.web3.SystemProgram.programId,
         })
         .rpc(),
-      "OrderExpired"
+      "OrderNotExpired"
     );
+
+    await ctx.program.methods
+      .cancelLimitOrder(orderId)
+      .accounts({
+        manager: ctx.provider.wallet.publicKey,
+        config: ctx.configPda,
+        fundState: ctx.fundPda,
+        fundVault: ctx.vaultPda,
+        whitelist: token.fundWhitelistPda,
+        fundTokenVault: token.fundTokenVault,
+        order: orderPda,
+        orderSolVault,
+        orderVaultAuth,
+        orderTokenVault,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
   });
 
   it("Rejects cancel limit order by non-manager", async () => {
@@ -871,7 +2009,10 @@ describe("limit-orders", () => {
         new anchor.BN(1),
         new anchor.BN(1),
         0,
-        new anchor.BN(0)
+        new anchor.BN(0),
+        ORDER_TYPE_LIMIT,
+        new anchor.BN(0),
+        0
       )
       .accounts({
         manager: ctx.provider.wallet.publicKey,
@@ -13,6 +13,8 @@ import { createMintToInstruction } from "@solana/spl-token";
 
 const DCA_SIDE_BUY = 0;
 const DCA_SIDE_SELL = 1;
+const DCA_MODE_FIXED = 0;
+const DCA_MODE_VALUE_AVERAGE = 1;
 const WSOL_MINT = new anchor.web3.PublicKey(
   "So11111111111111111111111111111111111111112",
 );
@@ -96,6 +98,11 @@ describe("dca-orders", () => {
         new anchor.BN(1),
         new anchor.BN(1),
         new anchor.BN(0),
+        0,
+        new anchor.BN(0),
+        new anchor.BN(0),
+        0,
+        DCA_MODE_FIXED,
       )
       .accounts({
         manager: ctx.provider.wallet.publicKey,
@@ -120,6 +127,9 @@ describe("dca-orders", () => {
     const orderAccount = await ctx.program.account.dcaOrder.fetch(orderPda);
     expect(orderAccount.status).to.equal(0);
     expect(orderAccount.remainingAmount.toString()).to.equal("100000");
+    const configAccount = await ctx.program.account.globalConfig.fetch(ctx.configPda);
+    expect(orderAccount.maxSlippageBps).to.equal(configAccount.maxSlippageBps);
+    expect(orderAccount.mode).to.equal(DCA_MODE_FIXED);
 
     const fundVaultAfter = await ctx.provider.connection.getBalance(ctx.vaultPda);
     expect(fundVaultBefore - fundVaultAfter).to.equal(100_000);
@@ -142,8 +152,114 @@ describe("dca-orders", () => {
       })
       .rpc();
 
-    const cancelled = await ctx.program.account.dcaOrder.fetch(orderPda);
-    expect(cancelled.status).to.equal(2);
+    const orderInfo = await ctx.provider.connection.getAccountInfo(orderPda);
+    expect(orderInfo).to.equal(null);
+  });
+
+  it("Amends a buy DCA order in place", async () => {
+    const ctx = await getContext();
+    await ensureGlobalConfig(ctx);
+    await ensureFund(ctx);
+
+    const token = await addFundToken(ctx);
+
+    const fundState = await ctx.program.account.fundState.fetch(ctx.fundPda);
+    const orderId = new anchor.BN(fundState.nextOrderId.toString());
+    const { orderPda, orderSolVault, orderVaultAuth, orderTokenVault } =
+      await deriveDcaAccounts(ctx, orderId, token.mint, DCA_SIDE_BUY);
+
+    await ctx.program.methods
+      .createDcaOrder(
+        DCA_SIDE_BUY,
+        new anchor.BN(100_000),
+        new anchor.BN(50_000),
+        new anchor.BN(60),
+        new anchor.BN(1),
+        new anchor.BN(0),
+        0,
+        new anchor.BN(0),
+        new anchor.BN(0),
+        0,
+        DCA_MODE_FIXED,
+      )
+      .accounts({
+        manager: ctx.provider.wallet.publicKey,
+        config: ctx.configPda,
+        fundState: ctx.fundPda,
+        fundVault: ctx.vaultPda,
+        mint: token.mint,
+        whitelist: token.fundWhitelistPda,
+        order: orderPda,
+        orderSolVault,
+        orderVaultAuth,
+        orderTokenVault,
+        fundTokenVault: token.fundTokenVault,
+        wsolMint: WSOL_MINT,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .rpc();
+
+    const created = await ctx.program.account.dcaOrder.fetch(orderPda);
+    const fundVaultBefore = await ctx.provider.connection.getBalance(ctx.vaultPda);
+
+    await ctx.program.methods
+      .amendDcaOrder(
+        orderId,
+        new anchor.BN(150_000),
+        new anchor.BN(30_000),
+        new anchor.BN(120),
+        new anchor.BN(1),
+        new anchor.BN(0),
+        new anchor.BN(0),
+        new anchor.BN(0),
+      )
+      .accounts({
+        manager: ctx.provider.wallet.publicKey,
+        config: ctx.configPda,
+        fundState: ctx.fundPda,
+        fundVault: ctx.vaultPda,
+        whitelist: token.fundWhitelistPda,
+        fundTokenVault: token.fundTokenVault,
+        order: orderPda,
+        orderSolVault,
+        orderVaultAuth,
+        orderTokenVault,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const fundVaultAfter = await ctx.provider.connection.getBalance(ctx.vaultPda);
+    expect(fundVaultBefore - fundVaultAfter).to.equal(50_000);
+
+    const amended = await ctx.program.account.dcaOrder.fetch(orderPda);
+    expect(amended.totalAmount.toString()).to.equal("150000");
+    expect(amended.remainingAmount.toString()).to.equal("150000");
+    expect(amended.sliceAmount.toString()).to.equal("30000");
+    expect(amended.nextExecTs.toNumber()).to.equal(
+      created.nextExecTs.toNumber() + 60
+    );
+
+    await ctx.program.methods
+      .cancelDcaOrder(orderId)
+      .accounts({
+        manager: ctx.provider.wallet.publicKey,
+        config: ctx.configPda,
+        fundState: ctx.fundPda,
+        fundVault: ctx.vaultPda,
+        whitelist: token.fundWhitelistPda,
+        fundTokenVault: token.fundTokenVault,
+        order: orderPda,
+        orderSolVault,
+        orderVaultAuth,
+        orderTokenVault,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
   });
 
   it("Creates and cancels a sell DCA order", async () => {
@@ -178,6 +294,11 @@ describe("dca-orders", () => {
         new anchor.BN(1),
         new anchor.BN(1),
         new anchor.BN(0),
+        0,
+        new anchor.BN(0),
+        new anchor.BN(0),
+        0,
+        DCA_MODE_FIXED,
       )
       .accounts({
         manager: ctx.provider.wallet.publicKey,
@@ -222,8 +343,102 @@ describe("dca-orders", () => {
       })
       .rpc();
 
-    const cancelled = await ctx.program.account.dcaOrder.fetch(orderPda);
-    expect(cancelled.status).to.equal(2);
+    const orderInfo = await ctx.provider.connection.getAccountInfo(orderPda);
+    expect(orderInfo).to.equal(null);
+  });
+
+  it("Creates and cancels a token-to-token sell DCA order", async () => {
+    const ctx = await getContext();
+    await ensureGlobalConfig(ctx);
+    await ensureFund(ctx);
+
+    const token = await addFundToken(ctx);
+    const quote = await addFundToken(ctx);
+
+    const mintIx = createMintToInstruction(
+      token.mint,
+      token.fundTokenVault,
+      ctx.provider.wallet.publicKey,
+      1_000,
+    );
+    await ctx.provider.sendAndConfirm(new anchor.web3.Transaction().add(mintIx), []);
+
+    const fundState = await ctx.program.account.fundState.fetch(ctx.fundPda);
+    const orderId = new anchor.BN(fundState.nextOrderId.toString());
+    const { orderPda, orderSolVault, orderVaultAuth, orderTokenVault } =
+      await deriveDcaAccounts(ctx, orderId, token.mint, DCA_SIDE_SELL);
+
+    const fundTokenBefore = await ctx.provider.connection.getTokenAccountBalance(
+      token.fundTokenVault,
+    );
+
+    await ctx.program.methods
+      .createDcaOrder(
+        DCA_SIDE_SELL,
+        new anchor.BN(500),
+        new anchor.BN(100),
+        new anchor.BN(1),
+        new anchor.BN(1),
+        new anchor.BN(0),
+        0,
+        new anchor.BN(0),
+        new anchor.BN(0),
+        0,
+        DCA_MODE_FIXED,
+      )
+      .accounts({
+        manager: ctx.provider.wallet.publicKey,
+        config: ctx.configPda,
+        fundState: ctx.fundPda,
+        fundVault: ctx.vaultPda,
+        mint: token.mint,
+        whitelist: token.fundWhitelistPda,
+        order: orderPda,
+        orderSolVault,
+        orderVaultAuth,
+        orderTokenVault,
+        fundTokenVault: token.fundTokenVault,
+        wsolMint: WSOL_MINT,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .remainingAccounts([
+        { pubkey: quote.fundWhitelistPda, isWritable: false, isSigner: false },
+        { pubkey: quote.mint, isWritable: false, isSigner: false },
+        { pubkey: quote.fundTokenVault, isWritable: true, isSigner: false },
+      ])
+      .rpc();
+
+    const orderAccount = await ctx.program.account.dcaOrder.fetch(orderPda);
+    expect(orderAccount.quoteMint.toBase58()).to.equal(quote.mint.toBase58());
+
+    const fundTokenAfter = await ctx.provider.connection.getTokenAccountBalance(
+      token.fundTokenVault,
+    );
+    expect(Number(fundTokenBefore.value.amount) - Number(fundTokenAfter.value.amount)).to.equal(500);
+
+    await ctx.program.methods
+      .cancelDcaOrder(orderId)
+      .accounts({
+        manager: ctx.provider.wallet.publicKey,
+        config: ctx.configPda,
+        fundState: ctx.fundPda,
+        fundVault: ctx.vaultPda,
+        whitelist: token.fundWhitelistPda,
+        fundTokenVault: token.fundTokenVault,
+        order: orderPda,
+        orderSolVault,
+        orderVaultAuth,
+        orderTokenVault,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const orderInfo = await ctx.provider.connection.getAccountInfo(orderPda);
+    expect(orderInfo).to.equal(null);
   });
 
   it("Rejects create DCA by non-manager", async () => {
@@ -253,6 +468,11 @@ describe("dca-orders", () => {
           new anchor.BN(10),
           new anchor.BN(1),
           new anchor.BN(0),
+          0,
+          new anchor.BN(0),
+          new anchor.BN(0),
+          0,
+          DCA_MODE_FIXED,
         )
         .accounts({
           manager: rogue.publicKey,
@@ -298,6 +518,11 @@ describe("dca-orders", () => {
           new anchor.BN(0),
           new anchor.BN(1),
           new anchor.BN(0),
+          0,
+          new anchor.BN(0),
+          new anchor.BN(0),
+          0,
+          DCA_MODE_FIXED,
         )
         .accounts({
           manager: ctx.provider.wallet.publicKey,
@@ -342,6 +567,11 @@ describe("dca-orders", () => {
           new anchor.BN(10),
           new anchor.BN(1),
           new anchor.BN(0),
+          0,
+          new anchor.BN(0),
+          new anchor.BN(0),
+          0,
+          DCA_MODE_FIXED,
         )
         .accounts({
           manager: ctx.provider.wallet.publicKey,
@@ -386,6 +616,11 @@ describe("dca-orders", () => {
           new anchor.BN(10),
           new anchor.BN(1),
           new anchor.BN(0),
+          0,
+          new anchor.BN(0),
+          new anchor.BN(0),
+          0,
+          DCA_MODE_FIXED,
         )
         .accounts({
           manager: ctx.provider.wallet.publicKey,
@@ -410,7 +645,7 @@ describe("dca-orders", () => {
     );
   });
 
-  it("Rejects create DCA with zero min_out", async () => {
+  it("Rejects create DCA with slippage above config", async () => {
     const ctx = await getContext();
     await ensureGlobalConfig(ctx);
     await ensureFund(ctx);
@@ -428,8 +663,111 @@ describe("dca-orders", () => {
           new anchor.BN(10_000),
           new anchor.BN(5_000),
           new anchor.BN(10),
+          new anchor.BN(1),
+          new anchor.BN(0),
+          5_000,
+          new anchor.BN(0),
+          new anchor.BN(0),
+          0,
+          DCA_MODE_FIXED,
+        )
+        .accounts({
+          manager: ctx.provider.wallet.publicKey,
+          config: ctx.configPda,
+          fundState: ctx.fundPda,
+          fundVault: ctx.vaultPda,
+          mint: token.mint,
+          whitelist: token.fundWhitelistPda,
+          order: orderPda,
+          orderSolVault,
+          orderVaultAuth,
+          orderTokenVault,
+          fundTokenVault: token.fundTokenVault,
+          wsolMint: WSOL_MINT,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+          associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        })
+        .rpc(),
+      "InvalidSlippage",
+    );
+  });
+
+  it("Rejects create DCA with inverted price band", async () => {
+    const ctx = await getContext();
+    await ensureGlobalConfig(ctx);
+    await ensureFund(ctx);
+
+    const token = await addFundToken(ctx);
+    const fundState = await ctx.program.account.fundState.fetch(ctx.fundPda);
+    const orderId = new anchor.BN(fundState.nextOrderId.toString());
+    const { orderPda, orderSolVault, orderVaultAuth, orderTokenVault } =
+      await deriveDcaAccounts(ctx, orderId, token.mint, DCA_SIDE_BUY);
+
+    await expectError(
+      ctx.program.methods
+        .createDcaOrder(
+          DCA_SIDE_BUY,
+          new anchor.BN(10_000),
+          new anchor.BN(5_000),
+          new anchor.BN(10),
+          new anchor.BN(1),
+          new anchor.BN(0),
+          0,
+          new anchor.BN(2_000_000),
+          new anchor.BN(1_000_000),
+          -6,
+          DCA_MODE_FIXED,
+        )
+        .accounts({
+          manager: ctx.provider.wallet.publicKey,
+          config: ctx.configPda,
+          fundState: ctx.fundPda,
+          fundVault: ctx.vaultPda,
+          mint: token.mint,
+          whitelist: token.fundWhitelistPda,
+          order: orderPda,
+          orderSolVault,
+          orderVaultAuth,
+          orderTokenVault,
+          fundTokenVault: token.fundTokenVault,
+          wsolMint: WSOL_MINT,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+          associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        })
+        .rpc(),
+      "InvalidDcaBand",
+    );
+  });
+
+  it("Rejects value-averaging DCA on the sell side", async () => {
+    const ctx = await getContext();
+    await ensureGlobalConfig(ctx);
+    await ensureFund(ctx);
+
+    const token = await addFundToken(ctx);
+    const fundState = await ctx.program.account.fundState.fetch(ctx.fundPda);
+    const orderId = new anchor.BN(fundState.nextOrderId.toString());
+    const { orderPda, orderSolVault, orderVaultAuth, orderTokenVault } =
+      await deriveDcaAccounts(ctx, orderId, token.mint, DCA_SIDE_SELL);
+
+    await expectError(
+      ctx.program.methods
+        .createDcaOrder(
+          DCA_SIDE_SELL,
+          new anchor.BN(10_000),
+          new anchor.BN(5_000),
+          new anchor.BN(10),
+          new anchor.BN(1),
+          new anchor.BN(0),
+          0,
           new anchor.BN(0),
           new anchor.BN(0),
+          0,
+          DCA_MODE_VALUE_AVERAGE,
         )
         .accounts({
           manager: ctx.provider.wallet.publicKey,
@@ -450,7 +788,7 @@ describe("dca-orders", () => {
           rent: anchor.web3.SYSVAR_RENT_PUBKEY,
         })
         .rpc(),
-      "InvalidMinOut",
+      "InvalidDcaMode",
     );
   });
 
@@ -474,6 +812,11 @@ describe("dca-orders", () => {
           new anchor.BN(10),
           new anchor.BN(1),
           new anchor.BN(0),
+          0,
+          new anchor.BN(0),
+          new anchor.BN(0),
+          0,
+          DCA_MODE_FIXED,
         )
         .accounts({
           manager: ctx.provider.wallet.publicKey,
@@ -519,6 +862,11 @@ describe("dca-orders", () => {
           new anchor.BN(10),
           new anchor.BN(1),
           new anchor.BN(0),
+          0,
+          new anchor.BN(0),
+          new anchor.BN(0),
+          0,
+          DCA_MODE_FIXED,
         )
         .accounts({
           manager: ctx.provider.wallet.publicKey,
@@ -562,6 +910,11 @@ describe("dca-orders", () => {
         new anchor.BN(10),
         new anchor.BN(1),
         new anchor.BN(0),
+        0,
+        new anchor.BN(0),
+        new anchor.BN(0),
+        0,
+        DCA_MODE_FIXED,
       )
       .accounts({
         manager: ctx.provider.wallet.publicKey,
@@ -591,6 +944,7 @@ describe("dca-orders", () => {
           executor: rogue.publicKey,
           config: ctx.configPda,
           fundState: ctx.fundPda,
+          manager: ctx.provider.wallet.publicKey,
           fundVault: ctx.vaultPda,
           whitelist: token.fundWhitelistPda,
           fundTokenVault: token.fundTokenVault,
@@ -647,6 +1001,11 @@ describe("dca-orders", () => {
         new anchor.BN(60),
         new anchor.BN(1),
         new anchor.BN(0),
+        0,
+        new anchor.BN(0),
+        new anchor.BN(0),
+        0,
+        DCA_MODE_FIXED,
       )
       .accounts({
         manager: ctx.provider.wallet.publicKey,
@@ -675,6 +1034,7 @@ describe("dca-orders", () => {
           executor: ctx.provider.wallet.publicKey,
           config: ctx.configPda,
           fundState: ctx.fundPda,
+          manager: ctx.provider.wallet.publicKey,
           fundVault: ctx.vaultPda,
           whitelist: token.fundWhitelistPda,
           fundTokenVault: token.fundTokenVault,
@@ -732,6 +1092,11 @@ describe("dca-orders", () => {
         new anchor.BN(1),
         new anchor.BN(1),
         new anchor.BN(expiredTs),
+        0,
+        new anchor.BN(0),
+        new anchor.BN(0),
+        0,
+        DCA_MODE_FIXED,
       )
       .accounts({
         manager: ctx.provider.wallet.publicKey,
@@ -760,6 +1125,7 @@ describe("dca-orders", () => {
           executor: ctx.provider.wallet.publicKey,
           config: ctx.configPda,
           fundState: ctx.fundPda,
+          manager: ctx.provider.wallet.publicKey,
           fundVault: ctx.vaultPda,
           whitelist: token.fundWhitelistPda,
           fundTokenVault: token.fundTokenVault,
@@ -796,6 +1162,81 @@ describe("dca-orders", () => {
       .rpc();
   });
 
+  it("Expires a buy DCA order from any caller", async () => {
+    const ctx = await getContext();
+    await ensureGlobalConfig(ctx);
+    await ensureFund(ctx);
+
+    const token = await addFundToken(ctx);
+    const cranker = anchor.web3.Keypair.generate();
+    const fundState = await ctx.program.account.fundState.fetch(ctx.fundPda);
+    const orderId = new anchor.BN(fundState.nextOrderId.toString());
+    const { orderPda, orderSolVault, orderVaultAuth, orderTokenVault } =
+      await deriveDcaAccounts(ctx, orderId, token.mint, DCA_SIDE_BUY);
+
+    const expiredTs = (await getClockUnixTimestamp(ctx.provider.connection)) - 1;
+    const fundVaultBefore = await ctx.provider.connection.getBalance(ctx.vaultPda);
+
+    await ctx.program.methods
+      .createDcaOrder(
+        DCA_SIDE_BUY,
+        new anchor.BN(10_000),
+        new anchor.BN(5_000),
+        new anchor.BN(1),
+        new anchor.BN(1),
+        new anchor.BN(expiredTs),
+        0,
+        new anchor.BN(0),
+        new anchor.BN(0),
+        0,
+        DCA_MODE_FIXED,
+      )
+      .accounts({
+        manager: ctx.provider.wallet.publicKey,
+        config: ctx.configPda,
+        fundState: ctx.fundPda,
+        fundVault: ctx.vaultPda,
+        mint: token.mint,
+        whitelist: token.fundWhitelistPda,
+        order: orderPda,
+        orderSolVault,
+        orderVaultAuth,
+        orderTokenVault,
+        fundTokenVault: token.fundTokenVault,
+        wsolMint: WSOL_MINT,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .rpc();
+
+    await ctx.program.methods
+      .expireDcaOrder(orderId)
+      .accounts({
+        caller: cranker.publicKey,
+        fundState: ctx.fundPda,
+        manager: ctx.provider.wallet.publicKey,
+        fundVault: ctx.vaultPda,
+        whitelist: token.fundWhitelistPda,
+        fundTokenVault: token.fundTokenVault,
+        order: orderPda,
+        orderSolVault,
+        orderVaultAuth,
+        orderTokenVault,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([cranker])
+      .rpc();
+
+    const fundVaultAfter = await ctx.provider.connection.getBalance(ctx.vaultPda);
+    expect(fundVaultAfter).to.equal(fundVaultBefore);
+    expect(await ctx.provider.connection.getAccountInfo(orderPda)).to.equal(null);
+    const fundStateAfter = await ctx.program.account.fundState.fetch(ctx.fundPda);
+    expect(fundStateAfter.activeDcaCount).to.equal(fundState.activeDcaCount);
+  });
+
   it("Rejects execute DCA with invalid oracle", async () => {
     const ctx = await getContext();
     await ensureGlobalConfig(ctx);
@@ -815,6 +1256,11 @@ describe("dca-orders", () => {
         new anchor.BN(1),
         new anchor.BN(1),
         new anchor.BN(0),
+        0,
+        new anchor.BN(0),
+        new anchor.BN(0),
+        0,
+        DCA_MODE_FIXED,
       )
       .accounts({
         manager: ctx.provider.wallet.publicKey,
@@ -845,6 +1291,7 @@ describe("dca-orders", () => {
           executor: ctx.provider.wallet.publicKey,
           config: ctx.configPda,
           fundState: ctx.fundPda,
+          manager: ctx.provider.wallet.publicKey,
           fundVault: ctx.vaultPda,
           whitelist: token.fundWhitelistPda,
           fundTokenVault: token.fundTokenVault,
@@ -900,6 +1347,11 @@ describe("dca-orders", () => {
         new anchor.BN(10),
         new anchor.BN(1),
         new anchor.BN(0),
+        0,
+        new anchor.BN(0),
+        new anchor.BN(0),
+        0,
+        DCA_MODE_FIXED,
       )
       .accounts({
         manager: ctx.provider.wallet.publicKey,
@@ -1001,6 +1453,11 @@ describe("dca-orders", () => {
             new anchor.BN(10),
             new anchor.BN(1),
             new anchor.BN(0),
+            0,
+            new anchor.BN(0),
+            new anchor.BN(0),
+            0,
+            DCA_MODE_FIXED,
           )
           .accounts({
             manager: ctx.provider.wallet.publicKey,
@@ -1048,6 +1505,11 @@ describe("dca-orders", () => {
           new anchor.BN(10),
           new anchor.BN(1),
           new anchor.BN(0),
+          0,
+          new anchor.BN(0),
+          new anchor.BN(0),
+          0,
+          DCA_MODE_FIXED,
         )
         .accounts({
           manager: ctx.provider.wallet.publicKey,
@@ -1083,6 +1545,11 @@ describe("dca-orders", () => {
           new anchor.BN(10),
           new anchor.BN(1),
           new anchor.BN(0),
+          0,
+          new anchor.BN(0),
+          new anchor.BN(0),
+          0,
+          DCA_MODE_FIXED,
         )
         .accounts({
           manager: ctx.provider.wallet.publicKey,
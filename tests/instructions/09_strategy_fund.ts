@@ -217,6 +217,10 @@ describe("strategy-fund", () => {
         ],
         200,
         new anchor.BN(60),
+        0,
+        0,
+        new anchor.BN(0),
+        new anchor.BN(0),
       )
       .accounts({
         manager: ctx.provider.wallet.publicKey,
@@ -231,7 +235,141 @@ describe("strategy-fund", () => {
       .rpc();
 
     const strategy = await ctx.program.account.strategy.fetch(strategyPda);
-    expect(strategy.allocationCount).to.equal(2);
+    expect(strategy.allocations.length).to.equal(2);
+  });
+
+  it("Sets strategy with a SOL allocation and liquidity reserve", async () => {
+    const ctx = await getContext();
+    await ensureGlobalConfig(ctx);
+
+    const fundId = new anchor.BN(28);
+    const { fundPda, strategyPda } = await createStrategyFund(ctx, fundId);
+    const token = await addTokenForFund(ctx, fundPda, fundId);
+
+    await ctx.program.methods
+      .setStrategy(
+        [{ mint: token.mint, weightBps: 7000 }],
+        200,
+        new anchor.BN(60),
+        3000,
+        1000,
+        new anchor.BN(0),
+        new anchor.BN(0),
+      )
+      .accounts({
+        manager: ctx.provider.wallet.publicKey,
+        fundState: fundPda,
+        strategy: strategyPda,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .remainingAccounts([
+        { pubkey: token.fundWhitelistPda, isWritable: false, isSigner: false },
+      ])
+      .rpc();
+
+    const strategy = await ctx.program.account.strategy.fetch(strategyPda);
+    expect(strategy.solWeightBps).to.equal(3000);
+    expect(strategy.liquidityReserveBps).to.equal(1000);
+  });
+
+  it("Accepts a reserve above the SOL weight up to 10,000 bps", async () => {
+    const ctx = await getContext();
+    await ensureGlobalConfig(ctx);
+
+    const fundId = new anchor.BN(29);
+    const { fundPda, strategyPda } = await createStrategyFund(ctx, fundId);
+    const token = await addTokenForFund(ctx, fundPda, fundId);
+
+    const setReserve = (reserveBps: number) =>
+      ctx.program.methods
+        .setStrategy(
+          [{ mint: token.mint, weightBps: 9000 }],
+          200,
+          new anchor.BN(60),
+          1000,
+          reserveBps,
+          new anchor.BN(0),
+          new anchor.BN(0),
+        )
+        .accounts({
+          manager: ctx.provider.wallet.publicKey,
+          fundState: fundPda,
+          strategy: strategyPda,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .remainingAccounts([
+          { pubkey: token.fundWhitelistPda, isWritable: false, isSigner: false },
+        ])
+        .rpc();
+
+    await expectError(setReserve(10_001), "InvalidFeeBps");
+    await setReserve(2000);
+
+    const strategy = await ctx.program.account.strategy.fetch(strategyPda);
+    expect(strategy.solWeightBps).to.equal(1000);
+    expect(strategy.liquidityReserveBps).to.equal(2000);
+  });
+
+  it("Stages strategy updates behind the withdraw timelock", async () => {
+    const ctx = await getContext();
+    await ensureGlobalConfig(ctx);
+
+    const fundId = new anchor.BN(30);
+    const { fundPda, strategyPda } = await createStrategyFund(ctx, fundId);
+    const tokenA = await addTokenForFund(ctx, fundPda, fundId);
+    const tokenB = await addTokenForFund(ctx, fundPda, fundId);
+
+    const setWeights = (
+      weightA: number,
+      weightB: number,
+      glideSecs: number,
+      effectiveTs: number,
+      cooldownSecs = 60,
+    ) =>
+      ctx.program.methods
+        .setStrategy(
+          [
+            { mint: tokenA.mint, weightBps: weightA },
+            { mint: tokenB.mint, weightBps: weightB },
+          ],
+          200,
+          new anchor.BN(cooldownSecs),
+          0,
+          0,
+          new anchor.BN(glideSecs),
+          new anchor.BN(effectiveTs),
+        )
+        .accounts({
+          manager: ctx.provider.wallet.publicKey,
+          fundState: fundPda,
+          strategy: strategyPda,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .remainingAccounts([
+          { pubkey: tokenA.fundWhitelistPda, isWritable: false, isSigner: false },
+          { pubkey: tokenB.fundWhitelistPda, isWritable: false, isSigner: false },
+        ])
+        .rpc();
+
+    await setWeights(8000, 2000, 0, 0);
+    const effectiveTs = Math.floor(Date.now() / 1000) + 3600;
+    await setWeights(2000, 8000, 7 * 24 * 60 * 60, effectiveTs, 120);
+
+    const strategy = await ctx.program.account.strategy.fetch(strategyPda);
+    expect(strategy.allocations[0].weightBps).to.equal(8000);
+    expect(strategy.hasPendingUpdate).to.equal(true);
+    expect(strategy.pendingAllocations[0].weightBps).to.equal(2000);
+    expect(strategy.pendingEffectiveTs.toNumber()).to.equal(effectiveTs);
+    expect(strategy.pendingGlideDurationSecs.toNumber()).to.equal(
+      7 * 24 * 60 * 60,
+    );
+    expect(strategy.rebalanceCooldownSecs.toNumber()).to.equal(60);
+    expect(strategy.pendingRebalanceCooldownSecs.toNumber()).to.equal(120);
+
+    await expectError(
+      setWeights(5000, 5000, 0, 1),
+      "StrategyTimelock",
+    );
   });
 
   it("Rejects set_strategy on trading fund", async () => {
@@ -250,6 +388,10 @@ describe("strategy-fund", () => {
           [{ mint: ctx.provider.wallet.publicKey, weightBps: 10_000 }],
           200,
           new anchor.BN(60),
+          0,
+          0,
+          new anchor.BN(0),
+          new anchor.BN(0),
         )
         .accounts({
           manager: ctx.provider.wallet.publicKey,
@@ -282,6 +424,10 @@ describe("strategy-fund", () => {
           [{ mint: token.mint, weightBps: 10_000 }],
           200,
           new anchor.BN(60),
+          0,
+          0,
+          new anchor.BN(0),
+          new anchor.BN(0),
         )
         .accounts({
           manager: rogue.publicKey,
@@ -314,6 +460,10 @@ describe("strategy-fund", () => {
           ],
           200,
           new anchor.BN(60),
+          0,
+          0,
+          new anchor.BN(0),
+          new anchor.BN(0),
         )
         .accounts({
           manager: ctx.provider.wallet.publicKey,
@@ -344,6 +494,10 @@ describe("strategy-fund", () => {
           ],
           200,
           new anchor.BN(60),
+          0,
+          0,
+          new anchor.BN(0),
+          new anchor.BN(0),
         )
         .accounts({
           manager: ctx.provider.wallet.publicKey,
@@ -374,6 +528,10 @@ describe("strategy-fund", () => {
           ],
           200,
           new anchor.BN(60),
+          0,
+          0,
+          new anchor.BN(0),
+          new anchor.BN(0),
         )
         .accounts({
           manager: ctx.provider.wallet.publicKey,
@@ -386,30 +544,83 @@ describe("strategy-fund", () => {
     );
   });
 
-  it("Rejects set_strategy with more than 8 allocations", async () => {
+  it("Builds a strategy from appended allocation chunks", async () => {
     const ctx = await getContext();
     await ensureGlobalConfig(ctx);
 
     const fundId = new anchor.BN(17);
     const { fundPda, strategyPda } = await createStrategyFund(ctx, fundId);
+    const tokenA = await addTokenForFund(ctx, fundPda, fundId);
+    const tokenB = await addTokenForFund(ctx, fundPda, fundId);
+
+    await ctx.program.methods
+      .appendStrategyAllocations([{ mint: tokenA.mint, weightBps: 5000 }])
+      .accounts({
+        manager: ctx.provider.wallet.publicKey,
+        fundState: fundPda,
+        strategy: strategyPda,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .remainingAccounts([
+        { pubkey: tokenA.fundWhitelistPda, isWritable: false, isSigner: false },
+      ])
+      .rpc();
 
-    const allocations = Array.from({ length: 9 }, () => ({
-      mint: anchor.web3.Keypair.generate().publicKey,
-      weightBps: 1,
-    }));
+    let strategy = await ctx.program.account.strategy.fetch(strategyPda);
+    expect(strategy.draftAllocations.length).to.equal(1);
+    expect(strategy.allocations.length).to.equal(0);
 
-    await expectError(
+    await ctx.program.methods
+      .setStrategy(
+        [{ mint: tokenB.mint, weightBps: 5000 }],
+        200,
+        new anchor.BN(60),
+        0,
+        0,
+        new anchor.BN(0),
+        new anchor.BN(0),
+      )
+      .accounts({
+        manager: ctx.provider.wallet.publicKey,
+        fundState: fundPda,
+        strategy: strategyPda,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .remainingAccounts([
+        { pubkey: tokenB.fundWhitelistPda, isWritable: false, isSigner: false },
+      ])
+      .rpc();
+
+    strategy = await ctx.program.account.strategy.fetch(strategyPda);
+    expect(strategy.allocations.length).to.equal(2);
+    expect(strategy.draftAllocations.length).to.equal(0);
+    expect(strategy.allocations[0].mint.toBase58()).to.equal(tokenA.mint.toBase58());
+  });
+
+  it("Rejects a duplicate mint across appended chunks", async () => {
+    const ctx = await getContext();
+    await ensureGlobalConfig(ctx);
+
+    const fundId = new anchor.BN(31);
+    const { fundPda, strategyPda } = await createStrategyFund(ctx, fundId);
+    const token = await addTokenForFund(ctx, fundPda, fundId);
+
+    const append = () =>
       ctx.program.methods
-        .setStrategy(allocations, 200, new anchor.BN(60))
+        .appendStrategyAllocations([{ mint: token.mint, weightBps: 10_000 }])
         .accounts({
           manager: ctx.provider.wallet.publicKey,
           fundState: fundPda,
           strategy: strategyPda,
           systemProgram: anchor.web3.SystemProgram.programId,
         })
-        .rpc(),
-      "InvalidStrategy",
-    );
+        .remainingAccounts([
+          { pubkey: token.fundWhitelistPda, isWritable: false, isSigner: false },
+        ])
+        .rpc();
+
+    await append();
+    await expectError(append(), "InvalidStrategy");
   });
 
   it("Rejects rebalance by non-keeper", async () => {
@@ -425,6 +636,10 @@ describe("strategy-fund", () => {
         [{ mint: token.mint, weightBps: 10_000 }],
         200,
         new anchor.BN(60),
+        0,
+        0,
+        new anchor.BN(0),
+        new anchor.BN(0),
       )
       .accounts({
         manager: ctx.provider.wallet.publicKey,
@@ -437,6 +652,10 @@ describe("strategy-fund", () => {
       ])
       .rpc();
 
+    const [sessionPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("rebalance_session"), fundPda.toBuffer()],
+      ctx.program.programId,
+    );
     const fundWsolVault = await anchor.utils.token.associatedAddress({
       mint: WSOL_MINT,
       owner: fundPda,
@@ -458,6 +677,7 @@ describe("strategy-fund", () => {
           fundState: fundPda,
           fundVault: vaultPda,
           strategy: strategyPda,
+          session: sessionPda,
           fundTokenVault: token.fundTokenVault,
           fundWsolVault,
           wsolMint: WSOL_MINT,
@@ -486,6 +706,10 @@ describe("strategy-fund", () => {
         [{ mint: token.mint, weightBps: 10_000 }],
         200,
         new anchor.BN(600),
+        0,
+        0,
+        new anchor.BN(0),
+        new anchor.BN(0),
       )
       .accounts({
         manager: ctx.provider.wallet.publicKey,
@@ -498,6 +722,10 @@ describe("strategy-fund", () => {
       ])
       .rpc();
 
+    const [sessionPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("rebalance_session"), fundPda.toBuffer()],
+      ctx.program.programId,
+    );
     const fundWsolVault = await anchor.utils.token.associatedAddress({
       mint: WSOL_MINT,
       owner: fundPda,
@@ -512,6 +740,7 @@ describe("strategy-fund", () => {
           fundState: fundPda,
           fundVault: vaultPda,
           strategy: strategyPda,
+          session: sessionPda,
           fundTokenVault: token.fundTokenVault,
           fundWsolVault,
           wsolMint: WSOL_MINT,
@@ -539,6 +768,10 @@ describe("strategy-fund", () => {
         [{ mint: token.mint, weightBps: 10_000 }],
         200,
         new anchor.BN(60),
+        0,
+        0,
+        new anchor.BN(0),
+        new anchor.BN(0),
       )
       .accounts({
         manager: ctx.provider.wallet.publicKey,
@@ -551,6 +784,10 @@ describe("strategy-fund", () => {
       ])
       .rpc();
 
+    const [sessionPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("rebalance_session"), fundPda.toBuffer()],
+      ctx.program.programId,
+    );
     const fundWsolVault = await getOrCreateAssociatedTokenAccount(
       ctx.provider.connection,
       ctx.provider.wallet.payer,
@@ -578,6 +815,7 @@ describe("strategy-fund", () => {
           fundState: fundPda,
           fundVault: vaultPda,
           strategy: strategyPda,
+          session: sessionPda,
           fundTokenVault: token.fundTokenVault,
           fundWsolVault: fundWsolVault.address,
           wsolMint: WSOL_MINT,
@@ -628,6 +866,9 @@ describe("strategy-fund", () => {
           new anchor.BN(1),
           0,
           new anchor.BN(0),
+          0,
+          new anchor.BN(0),
+          0,
         )
         .accounts({
           manager: ctx.provider.wallet.publicKey,
@@ -783,4 +1024,545 @@ describe("strategy-fund", () => {
       "InvalidOrderVault",
     );
   });
+
+  it("Rejects open rebalance session by non-keeper", async () => {
+    const ctx = await getContext();
+    await ensureGlobalConfig(ctx);
+
+    const fundId = new anchor.BN(25);
+    const { fundPda, strategyPda } = await createStrategyFund(ctx, fundId);
+    const token = await addTokenForFund(ctx, fundPda, fundId);
+
+    await ctx.program.methods
+      .setStrategy(
+        [{ mint: token.mint, weightBps: 10_000 }],
+        200,
+        new anchor.BN(60),
+        0,
+        0,
+        new anchor.BN(0),
+        new anchor.BN(0),
+      )
+      .accounts({
+        manager: ctx.provider.wallet.publicKey,
+        fundState: fundPda,
+        strategy: strategyPda,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .remainingAccounts([
+        { pubkey: token.fundWhitelistPda, isWritable: false, isSigner: false },
+      ])
+      .rpc();
+
+    const [sessionPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("rebalance_session"), fundPda.toBuffer()],
+      ctx.program.programId,
+    );
+    const fundWsolVault = await anchor.utils.token.associatedAddress({
+      mint: WSOL_MINT,
+      owner: fundPda,
+    });
+
+    const rogue = anchor.web3.Keypair.generate();
+    await airdropIfNeeded(
+      ctx.provider,
+      rogue.publicKey,
+      anchor.web3.LAMPORTS_PER_SOL / 2,
+    );
+
+    await expectError(
+      ctx.program.methods
+        .openRebalanceSession()
+        .accounts({
+          executor: rogue.publicKey,
+          config: ctx.configPda,
+          fundState: fundPda,
+          strategy: strategyPda,
+          session: sessionPda,
+          fundWsolVault,
+          wsolMint: WSOL_MINT,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+          associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([rogue])
+        .rpc(),
+      "Unauthorized",
+    );
+  });
+
+  it("Rejects open rebalance session when cooldown not met", async () => {
+    const ctx = await getContext();
+    await ensureGlobalConfig(ctx);
+
+    const fundId = new anchor.BN(26);
+    const { fundPda, strategyPda } = await createStrategyFund(ctx, fundId);
+    const token = await addTokenForFund(ctx, fundPda, fundId);
+
+    await ctx.program.methods
+      .setStrategy(
+        [{ mint: token.mint, weightBps: 10_000 }],
+        200,
+        new anchor.BN(600),
+        0,
+        0,
+        new anchor.BN(0),
+        new anchor.BN(0),
+      )
+      .accounts({
+        manager: ctx.provider.wallet.publicKey,
+        fundState: fundPda,
+        strategy: strategyPda,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .remainingAccounts([
+        { pubkey: token.fundWhitelistPda, isWritable: false, isSigner: false },
+      ])
+      .rpc();
+
+    const [sessionPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("rebalance_session"), fundPda.toBuffer()],
+      ctx.program.programId,
+    );
+    const fundWsolVault = await anchor.utils.token.associatedAddress({
+      mint: WSOL_MINT,
+      owner: fundPda,
+    });
+
+    await expectError(
+      ctx.program.methods
+        .openRebalanceSession()
+        .accounts({
+          executor: ctx.provider.wallet.publicKey,
+          config: ctx.configPda,
+          fundState: fundPda,
+          strategy: strategyPda,
+          session: sessionPda,
+          fundWsolVault,
+          wsolMint: WSOL_MINT,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+          associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc(),
+      "RebalanceNotNeeded",
+    );
+  });
+
+  it("Rejects rebalance leg without an open session", async () => {
+    const ctx = await getContext();
+    await ensureGlobalConfig(ctx);
+
+    const fundId = new anchor.BN(27);
+    const { fundPda, strategyPda, vaultPda } = await createStrategyFund(ctx, fundId);
+    const token = await addTokenForFund(ctx, fundPda, fundId);
+
+    await ctx.program.methods
+      .setStrategy(
+        [{ mint: token.mint, weightBps: 10_000 }],
+        200,
+        new anchor.BN(60),
+        0,
+        0,
+        new anchor.BN(0),
+        new anchor.BN(0),
+      )
+      .accounts({
+        manager: ctx.provider.wallet.publicKey,
+        fundState: fundPda,
+        strategy: strategyPda,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .remainingAccounts([
+        { pubkey: token.fundWhitelistPda, isWritable: false, isSigner: false },
+      ])
+      .rpc();
+
+    const [sessionPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("rebalance_session"), fundPda.toBuffer()],
+      ctx.program.programId,
+    );
+    const fundWsolVault = await anchor.utils.token.associatedAddress({
+      mint: WSOL_MINT,
+      owner: fundPda,
+    });
+
+    await expectError(
+      ctx.program.methods
+        .executeRebalanceLeg(
+          WSOL_MINT,
+          token.mint,
+          new anchor.BN(1_000),
+          new anchor.BN(1),
+          Buffer.from([1]),
+        )
+        .accounts({
+          executor: ctx.provider.wallet.publicKey,
+          config: ctx.configPda,
+          fundState: fundPda,
+          fundVault: vaultPda,
+          strategy: strategyPda,
+          session: sessionPda,
+          inputTokenVault: fundWsolVault,
+          outputTokenVault: token.fundTokenVault,
+          solPriceFeed: ctx.solPythFeed,
+          swapProgram: JUPITER_PROGRAM_ID,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        })
+        .rpc(),
+      "AccountNotInitialized",
+    );
+  });
+
+  it("Rejects a valuation batch without an open session", async () => {
+    const ctx = await getContext();
+    await ensureGlobalConfig(ctx);
+
+    const fundId = new anchor.BN(32);
+    const { fundPda, strategyPda, vaultPda } = await createStrategyFund(ctx, fundId);
+    const token = await addTokenForFund(ctx, fundPda, fundId);
+
+    await ctx.program.methods
+      .setStrategy(
+        [{ mint: token.mint, weightBps: 10_000 }],
+        200,
+        new anchor.BN(60),
+        0,
+        0,
+        new anchor.BN(0),
+        new anchor.BN(0),
+      )
+      .accounts({
+        manager: ctx.provider.wallet.publicKey,
+        fundState: fundPda,
+        strategy: strategyPda,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .remainingAccounts([
+        { pubkey: token.fundWhitelistPda, isWritable: false, isSigner: false },
+      ])
+      .rpc();
+
+    const [sessionPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("rebalance_session"), fundPda.toBuffer()],
+      ctx.program.programId,
+    );
+    const fundWsolVault = await anchor.utils.token.associatedAddress({
+      mint: WSOL_MINT,
+      owner: fundPda,
+    });
+
+    await expectError(
+      ctx.program.methods
+        .valueRebalanceBatch()
+        .accounts({
+          executor: ctx.provider.wallet.publicKey,
+          config: ctx.configPda,
+          fundState: fundPda,
+          fundVault: vaultPda,
+          strategy: strategyPda,
+          session: sessionPda,
+          fundWsolVault,
+          solPriceFeed: ctx.solPythFeed,
+        })
+        .remainingAccounts([
+          { pubkey: token.fundWhitelistPda, isWritable: false, isSigner: false },
+          { pubkey: token.fundTokenVault, isWritable: false, isSigner: false },
+          { pubkey: token.tokenPythFeed, isWritable: false, isSigner: false },
+        ])
+        .rpc(),
+      "AccountNotInitialized",
+    );
+  });
+
+  it("Blocks single-call rebalances while a session is open", async () => {
+    const ctx = await getContext();
+    await ensureGlobalConfig(ctx);
+
+    const fundId = new anchor.BN(33);
+    const { fundPda, strategyPda, vaultPda } = await createStrategyFund(ctx, fundId);
+    const token = await addTokenForFund(ctx, fundPda, fundId);
+
+    await ctx.program.methods
+      .setStrategy(
+        [{ mint: token.mint, weightBps: 10_000 }],
+        200,
+        new anchor.BN(1),
+        0,
+        0,
+        new anchor.BN(0),
+        new anchor.BN(0),
+      )
+      .accounts({
+        manager: ctx.provider.wallet.publicKey,
+        fundState: fundPda,
+        strategy: strategyPda,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .remainingAccounts([
+        { pubkey: token.fundWhitelistPda, isWritable: false, isSigner: false },
+      ])
+      .rpc();
+    await new Promise((resolve) => setTimeout(resolve, 2_000));
+
+    const [sessionPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("rebalance_session"), fundPda.toBuffer()],
+      ctx.program.programId,
+    );
+    const fundWsolVault = await anchor.utils.token.associatedAddress({
+      mint: WSOL_MINT,
+      owner: fundPda,
+    });
+
+    await ctx.program.methods
+      .openRebalanceSession()
+      .accounts({
+        executor: ctx.provider.wallet.publicKey,
+        config: ctx.configPda,
+        fundState: fundPda,
+        strategy: strategyPda,
+        session: sessionPda,
+        fundWsolVault,
+        wsolMint: WSOL_MINT,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+    const session = await ctx.program.account.rebalanceSession.fetch(sessionPda);
+    expect(session.opener.equals(ctx.provider.wallet.publicKey)).to.equal(true);
+    expect(session.wsolCreated).to.equal(true);
+    expect(session.notionalLamports.toNumber()).to.equal(0);
+    expect(
+      await ctx.provider.connection.getAccountInfo(fundWsolVault),
+    ).to.not.equal(null);
+    const strategy = await ctx.program.account.strategy.fetch(strategyPda);
+    expect(strategy.lastRebalanceTs.toNumber()).to.equal(
+      session.openedTs.toNumber(),
+    );
+
+    await expectError(
+      ctx.program.methods
+        .rebalanceStrategy(token.mint, new anchor.BN(1), Buffer.from([1]))
+        .accounts({
+          executor: ctx.provider.wallet.publicKey,
+          config: ctx.configPda,
+          fundState: fundPda,
+          fundVault: vaultPda,
+          strategy: strategyPda,
+          session: sessionPda,
+          fundTokenVault: token.fundTokenVault,
+          fundWsolVault,
+          wsolMint: WSOL_MINT,
+          solPriceFeed: ctx.solPythFeed,
+          swapProgram: JUPITER_PROGRAM_ID,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+          associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc(),
+      "RebalanceSessionActive",
+    );
+
+    const closeSession = (opener: anchor.web3.PublicKey) =>
+      ctx.program.methods
+        .closeRebalanceSession()
+        .accounts({
+          executor: ctx.provider.wallet.publicKey,
+          config: ctx.configPda,
+          fundState: fundPda,
+          fundVault: vaultPda,
+          session: sessionPda,
+          opener,
+          fundWsolVault,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+
+    await expectError(
+      closeSession(anchor.web3.Keypair.generate().publicKey),
+      "ConstraintHasOne",
+    );
+    await closeSession(ctx.provider.wallet.publicKey);
+    expect(await ctx.provider.connection.getAccountInfo(sessionPda)).to.equal(
+      null,
+    );
+    expect(await ctx.provider.connection.getAccountInfo(fundWsolVault)).to.equal(
+      null,
+    );
+  });
+
+  it("Takes deposits and withdrawals at the maximum strategy size", async () => {
+    const ctx = await getContext();
+    await ensureGlobalConfig(ctx);
+    await airdropIfNeeded(
+      ctx.provider,
+      ctx.investor.publicKey,
+      2 * anchor.web3.LAMPORTS_PER_SOL,
+    );
+    await airdropIfNeeded(ctx.provider, ctx.solPythFeed, 1);
+
+    const maxTokens = 50;
+    const fundId = new anchor.BN(34);
+    const { fundPda, shareMintPda, vaultPda, strategyPda } =
+      await createStrategyFund(ctx, fundId);
+    const tokens = [];
+    for (let i = 0; i < maxTokens; i++) {
+      const token = await addTokenForFund(ctx, fundPda, fundId);
+      await airdropIfNeeded(ctx.provider, token.tokenPythFeed, 1);
+      tokens.push(token);
+    }
+    await expectError(
+      addTokenForFund(ctx, fundPda, fundId),
+      "TooManyFundTokens",
+    );
+
+    const strategyAccounts = {
+      manager: ctx.provider.wallet.publicKey,
+      fundState: fundPda,
+      strategy: strategyPda,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    };
+    const chunk = (batch: typeof tokens) => ({
+      allocations: batch.map((token) => ({ mint: token.mint, weightBps: 190 })),
+      whitelists: batch.map((token) => ({
+        pubkey: token.fundWhitelistPda,
+        isWritable: false,
+        isSigner: false,
+      })),
+    });
+    for (let i = 0; i < maxTokens - 10; i += 10) {
+      const { allocations, whitelists } = chunk(tokens.slice(i, i + 10));
+      await ctx.program.methods
+        .appendStrategyAllocations(allocations)
+        .accounts(strategyAccounts)
+        .remainingAccounts(whitelists)
+        .rpc();
+    }
+    const last = chunk(tokens.slice(maxTokens - 10));
+    await ctx.program.methods
+      .setStrategy(
+        last.allocations,
+        200,
+        new anchor.BN(60),
+        500,
+        1000,
+        new anchor.BN(0),
+        new anchor.BN(0),
+      )
+      .accounts(strategyAccounts)
+      .remainingAccounts(last.whitelists)
+      .rpc();
+    const strategy = await ctx.program.account.strategy.fetch(strategyPda);
+    expect(strategy.allocations.length).to.equal(maxTokens);
+
+    await ctx.program.methods
+      .setNavSnapshotPolicy(true, new anchor.BN(150))
+      .accounts({
+        manager: ctx.provider.wallet.publicKey,
+        fundState: fundPda,
+      })
+      .rpc();
+
+    const navSnapshotPda = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("nav_snapshot"), fundPda.toBuffer()],
+      ctx.program.programId,
+    )[0];
+    tokens.sort((a, b) => Buffer.compare(a.mint.toBuffer(), b.mint.toBuffer()));
+    for (let i = 0; i < maxTokens; i += 6) {
+      const batch = tokens.slice(i, i + 6);
+      await ctx.program.methods
+        .refreshNavBatch(batch.length)
+        .accounts({
+          caller: ctx.provider.wallet.publicKey,
+          config: ctx.configPda,
+          fundState: fundPda,
+          fundVault: vaultPda,
+          navSnapshot: navSnapshotPda,
+          solPriceFeed: ctx.solPythFeed,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .remainingAccounts(
+          batch.flatMap((token) => [
+            { pubkey: token.fundWhitelistPda, isWritable: false, isSigner: false },
+            { pubkey: token.fundTokenVault, isWritable: false, isSigner: false },
+            { pubkey: token.tokenPythFeed, isWritable: false, isSigner: false },
+          ]),
+        )
+        .rpc();
+    }
+    const snapshot = await ctx.program.account.navSnapshot.fetch(navSnapshotPda);
+    expect(snapshot.assets.length).to.equal(maxTokens);
+    expect(snapshot.batchCursor).to.equal(0);
+
+    const investorShareAccount = await anchor.utils.token.associatedAddress({
+      mint: shareMintPda,
+      owner: ctx.investor.publicKey,
+    });
+    const snapshotOnly = [
+      { pubkey: navSnapshotPda, isWritable: false, isSigner: false },
+    ];
+    await ctx.program.methods
+      .deposit(new anchor.BN(anchor.web3.LAMPORTS_PER_SOL / 4))
+      .accounts({
+        investor: ctx.investor.publicKey,
+        config: ctx.configPda,
+        fundState: fundPda,
+        fundVault: vaultPda,
+        shareMint: shareMintPda,
+        investorShareAccount,
+        feeTreasury: ctx.feeTreasury.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .remainingAccounts(snapshotOnly)
+      .signers([ctx.investor])
+      .rpc();
+
+    const shares = await ctx.provider.connection.getTokenAccountBalance(
+      investorShareAccount,
+    );
+    expect(Number(shares.value.amount)).to.be.greaterThan(0);
+    const withdrawRequestPda = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("withdraw"), fundPda.toBuffer(), ctx.investor.publicKey.toBuffer()],
+      ctx.program.programId,
+    )[0];
+    await ctx.program.methods
+      .requestWithdraw(new anchor.BN(shares.value.amount))
+      .accounts({
+        investor: ctx.investor.publicKey,
+        config: ctx.configPda,
+        fundState: fundPda,
+        shareMint: shareMintPda,
+        investorShareAccount,
+        withdrawRequest: withdrawRequestPda,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .signers([ctx.investor])
+      .rpc();
+    await ctx.program.methods
+      .executeWithdraw()
+      .accounts({
+        investor: ctx.investor.publicKey,
+        config: ctx.configPda,
+        fundState: fundPda,
+        fundVault: vaultPda,
+        shareMint: shareMintPda,
+        investorShareAccount,
+        withdrawRequest: withdrawRequestPda,
+        feeTreasury: ctx.feeTreasury.publicKey,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      })
+      .remainingAccounts(snapshotOnly)
+      .signers([ctx.investor])
+      .rpc();
+
+    const sharesAfter = await ctx.provider.connection.getTokenAccountBalance(
+      investorShareAccount,
+    );
+    expect(sharesAfter.value.amount).to.equal("0");
+  });
 });
@@ -0,0 +1,406 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  addFundToken,
+  airdropIfNeeded,
+  ensureFund,
+  ensureGlobalConfig,
+  expectError,
+  getContext,
+  removeFundToken,
+} from "../helpers";
+
+const deriveFundPdas = (
+  ctx: Awaited<ReturnType<typeof getContext>>,
+  fundId: anchor.BN,
+) => {
+  const fundIdSeed = fundId.toArrayLike(Buffer, "le", 8);
+  const [fundPda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("fund"),
+      ctx.configPda.toBuffer(),
+      ctx.provider.wallet.publicKey.toBuffer(),
+      fundIdSeed,
+    ],
+    ctx.program.programId,
+  );
+  const [shareMintPda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("shares"), fundPda.toBuffer()],
+    ctx.program.programId,
+  );
+  const [vaultPda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("vault"), fundPda.toBuffer()],
+    ctx.program.programId,
+  );
+  const [tradingPda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("trading"), fundPda.toBuffer()],
+    ctx.program.programId,
+  );
+  return { fundPda, shareMintPda, vaultPda, tradingPda };
+};
+
+const createTradingFund = async (
+  ctx: Awaited<ReturnType<typeof getContext>>,
+  fundId: anchor.BN,
+) => {
+  const pdas = deriveFundPdas(ctx, fundId);
+  const managerShareAccount = await anchor.utils.token.associatedAddress({
+    mint: pdas.shareMintPda,
+    owner: ctx.provider.wallet.publicKey,
+  });
+
+  await ctx.program.methods
+    .initializeFund(
+      fundId,
+      new anchor.BN(anchor.web3.LAMPORTS_PER_SOL / 10),
+      0,
+      new anchor.BN(anchor.web3.LAMPORTS_PER_SOL / 20),
+      new anchor.BN(0),
+    )
+    .accounts({
+      manager: ctx.provider.wallet.publicKey,
+      config: ctx.configPda,
+      feeTreasury: ctx.feeTreasury.publicKey,
+      fundState: pdas.fundPda,
+      trading: pdas.tradingPda,
+      shareMint: pdas.shareMintPda,
+      managerShareAccount,
+      fundVault: pdas.vaultPda,
+      systemProgram: anchor.web3.SystemProgram.programId,
+      tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+      rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+    })
+    .rpc();
+
+  return pdas;
+};
+
+const addFundShares = async (
+  ctx: Awaited<ReturnType<typeof getContext>>,
+  parent: ReturnType<typeof deriveFundPdas>,
+  underlying: ReturnType<typeof deriveFundPdas>,
+) => {
+  const fundWhitelistPda = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("whitelist"),
+      parent.fundPda.toBuffer(),
+      underlying.shareMintPda.toBuffer(),
+    ],
+    ctx.program.programId,
+  )[0];
+  const fundTokenVault = await anchor.utils.token.associatedAddress({
+    mint: underlying.shareMintPda,
+    owner: parent.fundPda,
+  });
+
+  const signature = await ctx.program.methods
+    .addFundShares()
+    .accounts({
+      manager: ctx.provider.wallet.publicKey,
+      config: ctx.configPda,
+      fundState: parent.fundPda,
+      underlyingFund: underlying.fundPda,
+      shareMint: underlying.shareMintPda,
+      fundWhitelist: fundWhitelistPda,
+      fundTokenVault,
+      systemProgram: anchor.web3.SystemProgram.programId,
+      tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+    })
+    .rpc();
+
+  return { signature, fundWhitelistPda, fundTokenVault };
+};
+
+describe("fund-of-funds", () => {
+  it("Invests in and redeems from another fund through its proxy", async () => {
+    const ctx = await getContext();
+    await ensureGlobalConfig(ctx);
+    await ensureFund(ctx);
+
+    const underlying = await createTradingFund(ctx, new anchor.BN(4001));
+    const parent = await createTradingFund(ctx, new anchor.BN(4002));
+    await airdropIfNeeded(
+      ctx.provider,
+      parent.vaultPda,
+      2 * anchor.web3.LAMPORTS_PER_SOL,
+    );
+
+    const { fundWhitelistPda, fundTokenVault } = await addFundShares(
+      ctx,
+      parent,
+      underlying,
+    );
+    const whitelist = await ctx.program.account.fundWhitelist.fetch(
+      fundWhitelistPda,
+    );
+    expect(whitelist.kind).to.equal(1);
+    expect(whitelist.underlyingFund.toBase58()).to.equal(
+      underlying.fundPda.toBase58(),
+    );
+    const parentState = await ctx.program.account.fundState.fetch(
+      parent.fundPda,
+    );
+    const underlyingState = await ctx.program.account.fundState.fetch(
+      underlying.fundPda,
+    );
+    expect(parentState.heldFundCount).to.equal(1);
+    expect(underlyingState.holderCount).to.equal(1);
+
+    await expectError(
+      addFundShares(ctx, underlying, parent).then((r) => r.signature),
+      "FundNestingNotAllowed",
+    );
+
+    const [proxyPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("fund_proxy"),
+        parent.fundPda.toBuffer(),
+        underlying.fundPda.toBuffer(),
+      ],
+      ctx.program.programId,
+    );
+    const proxyShareAccount = await anchor.utils.token.associatedAddress({
+      mint: underlying.shareMintPda,
+      owner: proxyPda,
+    });
+    const sharesBefore = underlyingState.totalShares;
+
+    await ctx.program.methods
+      .investInFund(
+        new anchor.BN(anchor.web3.LAMPORTS_PER_SOL / 2),
+        new anchor.BN(1),
+      )
+      .accounts({
+        manager: ctx.provider.wallet.publicKey,
+        config: ctx.configPda,
+        fundState: parent.fundPda,
+        fundVault: parent.vaultPda,
+        fundWhitelist: fundWhitelistPda,
+        fundTokenVault,
+        underlyingFund: underlying.fundPda,
+        underlyingVault: underlying.vaultPda,
+        shareMint: underlying.shareMintPda,
+        proxy: proxyPda,
+        proxyShareAccount,
+        feeTreasury: ctx.feeTreasury.publicKey,
+        fundProgram: ctx.program.programId,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .rpc();
+
+    const heldShares = (
+      await ctx.provider.connection.getTokenAccountBalance(fundTokenVault)
+    ).value.amount;
+    const underlyingAfter = await ctx.program.account.fundState.fetch(
+      underlying.fundPda,
+    );
+    expect(
+      underlyingAfter.totalShares.sub(sharesBefore).toString(),
+    ).to.equal(heldShares);
+    expect(await ctx.provider.connection.getBalance(proxyPda)).to.equal(0);
+
+    const snapshotPda = (fundPda: anchor.web3.PublicKey) =>
+      anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("nav_snapshot"), fundPda.toBuffer()],
+        ctx.program.programId,
+      )[0];
+    const refreshNav = (
+      fund: ReturnType<typeof deriveFundPdas>,
+      remaining: anchor.web3.PublicKey[],
+    ) =>
+      ctx.program.methods
+        .refreshNav()
+        .accounts({
+          caller: ctx.provider.wallet.publicKey,
+          config: ctx.configPda,
+          fundState: fund.fundPda,
+          fundVault: fund.vaultPda,
+          navSnapshot: snapshotPda(fund.fundPda),
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .remainingAccounts(
+          remaining.map((pubkey) => ({
+            pubkey,
+            isWritable: false,
+            isSigner: false,
+          })),
+        )
+        .rpc();
+    const parentLayout = [
+      ctx.solPythFeed,
+      fundWhitelistPda,
+      fundTokenVault,
+      snapshotPda(underlying.fundPda),
+    ];
+
+    await refreshNav(underlying, []);
+    await expectError(
+      refreshNav(parent, parentLayout),
+      "InvalidRemainingAccounts",
+    );
+    await refreshNav(parent, [...parentLayout, underlying.fundPda]);
+
+    const investorShareAccount = await anchor.utils.token.associatedAddress({
+      mint: underlying.shareMintPda,
+      owner: ctx.provider.wallet.publicKey,
+    });
+    await ctx.program.methods
+      .deposit(new anchor.BN(anchor.web3.LAMPORTS_PER_SOL / 10))
+      .accounts({
+        investor: ctx.provider.wallet.publicKey,
+        config: ctx.configPda,
+        fundState: underlying.fundPda,
+        fundVault: underlying.vaultPda,
+        shareMint: underlying.shareMintPda,
+        investorShareAccount,
+        feeTreasury: ctx.feeTreasury.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .rpc();
+    await expectError(
+      refreshNav(parent, [...parentLayout, underlying.fundPda]),
+      "StaleNavSnapshot",
+    );
+
+    const withdrawRequestPda = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("withdraw"),
+        underlying.fundPda.toBuffer(),
+        proxyPda.toBuffer(),
+      ],
+      ctx.program.programId,
+    )[0];
+    const redemptionAccounts = {
+      manager: ctx.provider.wallet.publicKey,
+      config: ctx.configPda,
+      fundState: parent.fundPda,
+      fundVault: parent.vaultPda,
+      fundWhitelist: fundWhitelistPda,
+      fundTokenVault,
+      underlyingFund: underlying.fundPda,
+      shareMint: underlying.shareMintPda,
+      proxy: proxyPda,
+      proxyShareAccount,
+      withdrawRequest: withdrawRequestPda,
+      fundProgram: ctx.program.programId,
+      systemProgram: anchor.web3.SystemProgram.programId,
+      tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+    };
+    const requestRedemption = () =>
+      ctx.program.methods
+        .requestFundRedemption(new anchor.BN(heldShares))
+        .accounts({
+          ...redemptionAccounts,
+          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        })
+        .rpc();
+
+    await expectError(
+      ctx.program.methods
+        .cancelFundRedemption()
+        .accounts(redemptionAccounts)
+        .rpc(),
+      "NoFundRedemptionPending",
+    );
+    const escrowBefore = (
+      await ctx.program.account.fundState.fetch(parent.fundPda)
+    ).escrowedLamports;
+    await requestRedemption();
+    expect(await ctx.provider.connection.getBalance(proxyPda)).to.equal(0);
+    await ctx.program.methods
+      .cancelFundRedemption()
+      .accounts(redemptionAccounts)
+      .rpc();
+
+    const cancelled = await ctx.program.account.fundWhitelist.fetch(
+      fundWhitelistPda,
+    );
+    expect(cancelled.escrowedAmount.toNumber()).to.equal(0);
+    expect(
+      (await ctx.provider.connection.getTokenAccountBalance(fundTokenVault))
+        .value.amount,
+    ).to.equal(heldShares);
+    expect(
+      (await ctx.program.account.fundState.fetch(parent.fundPda))
+        .escrowedLamports.toString(),
+    ).to.equal(escrowBefore.toString());
+    expect(
+      await ctx.provider.connection.getAccountInfo(withdrawRequestPda),
+    ).to.equal(null);
+    expect(await ctx.provider.connection.getBalance(proxyPda)).to.equal(0);
+
+    await requestRedemption();
+
+    const pending = await ctx.program.account.fundWhitelist.fetch(
+      fundWhitelistPda,
+    );
+    expect(pending.escrowedAmount.toString()).to.equal(heldShares);
+
+    const parentVaultBefore = await ctx.provider.connection.getBalance(
+      parent.vaultPda,
+    );
+    await ctx.program.methods
+      .completeFundRedemption()
+      .accounts({
+        manager: ctx.provider.wallet.publicKey,
+        config: ctx.configPda,
+        fundState: parent.fundPda,
+        fundVault: parent.vaultPda,
+        fundWhitelist: fundWhitelistPda,
+        underlyingFund: underlying.fundPda,
+        underlyingVault: underlying.vaultPda,
+        shareMint: underlying.shareMintPda,
+        proxy: proxyPda,
+        proxyShareAccount,
+        withdrawRequest: withdrawRequestPda,
+        feeTreasury: ctx.feeTreasury.publicKey,
+        fundProgram: ctx.program.programId,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    const settled = await ctx.program.account.fundWhitelist.fetch(
+      fundWhitelistPda,
+    );
+    expect(settled.escrowedAmount.toNumber()).to.equal(0);
+    const parentVaultAfter = await ctx.provider.connection.getBalance(
+      parent.vaultPda,
+    );
+    expect(parentVaultAfter).to.be.greaterThan(parentVaultBefore);
+    expect(await ctx.provider.connection.getBalance(proxyPda)).to.equal(0);
+
+    const token = await addFundToken(ctx);
+    await ctx.program.methods
+      .removeToken(1, new anchor.BN(4002))
+      .accounts({
+        authority: ctx.provider.wallet.publicKey,
+        config: ctx.configPda,
+        mint: underlying.shareMintPda,
+        globalWhitelist: token.globalWhitelistPda,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .remainingAccounts([
+        { pubkey: parent.fundPda, isWritable: true, isSigner: false },
+        { pubkey: fundWhitelistPda, isWritable: true, isSigner: false },
+        { pubkey: fundTokenVault, isWritable: true, isSigner: false },
+        { pubkey: underlying.fundPda, isWritable: true, isSigner: false },
+      ])
+      .rpc();
+
+    const underlyingFinal = await ctx.program.account.fundState.fetch(
+      underlying.fundPda,
+    );
+    expect(underlyingFinal.holderCount).to.equal(0);
+
+    await removeFundToken(ctx, token);
+  });
+});
@@ -0,0 +1,106 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  airdropIfNeeded,
+  ensureFund,
+  ensureGlobalConfig,
+  expectError,
+  getContext,
+} from "../helpers";
+
+const ROLE_TRADER = 1;
+const ROLE_RISK_OFFICER = 2;
+const ROLE_OPS = 4;
+
+describe("fund-roles", () => {
+  it("Scopes trader, risk officer and ops permissions", async () => {
+    const ctx = await getContext();
+    await ensureGlobalConfig(ctx);
+    await ensureFund(ctx);
+
+    const trader = anchor.web3.Keypair.generate();
+    const riskOfficer = anchor.web3.Keypair.generate();
+    const ops = anchor.web3.Keypair.generate();
+    for (const member of [trader, riskOfficer, ops]) {
+      await airdropIfNeeded(
+        ctx.provider,
+        member.publicKey,
+        anchor.web3.LAMPORTS_PER_SOL,
+      );
+    }
+
+    const fundRolesPda = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("fund_roles"), ctx.fundPda.toBuffer()],
+      ctx.program.programId,
+    )[0];
+    const setRole = (member: anchor.web3.PublicKey, roles: number) =>
+      ctx.program.methods
+        .setFundRole(member, roles)
+        .accounts({
+          manager: ctx.provider.wallet.publicKey,
+          fundState: ctx.fundPda,
+          fundRoles: fundRolesPda,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+
+    await setRole(trader.publicKey, ROLE_TRADER);
+    await setRole(riskOfficer.publicKey, ROLE_RISK_OFFICER);
+    await setRole(ops.publicKey, ROLE_OPS);
+    await expectError(setRole(ops.publicKey, 8), "InvalidRole");
+
+    const roles = await ctx.program.account.fundRoles.fetch(fundRolesPda);
+    expect(roles.members.length).to.equal(3);
+    const traderEntry = roles.members.find((m) =>
+      m.key.equals(trader.publicKey),
+    );
+    expect(traderEntry!.roles).to.equal(ROLE_TRADER);
+
+    const setPaused = (
+      authority: anchor.web3.Keypair,
+      paused: boolean,
+    ) =>
+      ctx.program.methods
+        .setFundPaused(paused)
+        .accounts({
+          authority: authority.publicKey,
+          fundState: ctx.fundPda,
+          fundRoles: fundRolesPda,
+        })
+        .signers([authority])
+        .rpc();
+
+    await expectError(setPaused(trader, true), "Unauthorized");
+    await setPaused(riskOfficer, true);
+    expect((await ctx.program.account.fundState.fetch(ctx.fundPda)).paused).to
+      .equal(true);
+    await setPaused(riskOfficer, false);
+    expect((await ctx.program.account.fundState.fetch(ctx.fundPda)).paused).to
+      .equal(false);
+
+    const navHistoryPda = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("nav_history"), ctx.fundPda.toBuffer()],
+      ctx.program.programId,
+    )[0];
+    const configureHistory = (authority: anchor.web3.Keypair) =>
+      ctx.program.methods
+        .configureNavHistory(new anchor.BN(3600))
+        .accounts({
+          manager: authority.publicKey,
+          fundState: ctx.fundPda,
+          navHistory: navHistoryPda,
+          fundRoles: fundRolesPda,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([authority])
+        .rpc();
+
+    await expectError(configureHistory(trader), "Unauthorized");
+    await configureHistory(ops);
+
+    await setRole(riskOfficer.publicKey, 0);
+    const pruned = await ctx.program.account.fundRoles.fetch(fundRolesPda);
+    expect(pruned.members.length).to.equal(2);
+    await expectError(setPaused(riskOfficer, true), "Unauthorized");
+  });
+});
@@ -0,0 +1,112 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  airdropIfNeeded,
+  ensureFund,
+  ensureGlobalConfig,
+  expectError,
+  getClockUnixTimestamp,
+  getContext,
+} from "../helpers";
+
+describe("manager-transfer", () => {
+  it("Hands management to a new key after the notice period", async () => {
+    const ctx = await getContext();
+    await ensureGlobalConfig(ctx);
+    await ensureFund(ctx);
+
+    const successor = anchor.web3.Keypair.generate();
+    await airdropIfNeeded(
+      ctx.provider,
+      successor.publicKey,
+      anchor.web3.LAMPORTS_PER_SOL,
+    );
+    const originalManager = ctx.provider.wallet.publicKey;
+
+    const propose = (
+      manager: anchor.web3.PublicKey,
+      newManager: anchor.web3.PublicKey,
+      effectiveTs: number,
+      signers: anchor.web3.Keypair[] = [],
+    ) =>
+      ctx.program.methods
+        .proposeManager(newManager, new anchor.BN(effectiveTs))
+        .accounts({ manager, fundState: ctx.fundPda })
+        .signers(signers)
+        .rpc();
+    const accept = (
+      newManager: anchor.web3.PublicKey,
+      signers: anchor.web3.Keypair[] = [],
+    ) =>
+      ctx.program.methods
+        .acceptManager()
+        .accounts({
+          newManager,
+          fundState: ctx.fundPda,
+          fundRoles: null,
+        })
+        .signers(signers)
+        .rpc();
+
+    await expectError(
+      propose(successor.publicKey, successor.publicKey, 0, [successor]),
+      "Unauthorized",
+    );
+    await expectError(
+      propose(originalManager, originalManager, 0),
+      "InvalidManager",
+    );
+    await expectError(accept(successor.publicKey, [successor]), "NoPendingManager");
+
+    const now = await getClockUnixTimestamp(ctx.provider.connection);
+    await propose(originalManager, successor.publicKey, now + 3600);
+    await propose(originalManager, anchor.web3.PublicKey.default, 0);
+    const cancelled = await ctx.program.account.fundState.fetch(ctx.fundPda);
+    expect(cancelled.pendingManager.toBase58()).to.equal(
+      anchor.web3.PublicKey.default.toBase58(),
+    );
+
+    const effectiveTs =
+      (await getClockUnixTimestamp(ctx.provider.connection)) + 2;
+    await propose(originalManager, successor.publicKey, effectiveTs);
+    const pending = await ctx.program.account.fundState.fetch(ctx.fundPda);
+    expect(pending.pendingManager.toBase58()).to.equal(
+      successor.publicKey.toBase58(),
+    );
+    expect(pending.manager.toBase58()).to.equal(originalManager.toBase58());
+
+    await expectError(accept(originalManager), "Unauthorized");
+    await new Promise((resolve) => setTimeout(resolve, 3000));
+    await accept(successor.publicKey, [successor]);
+
+    const transferred = await ctx.program.account.fundState.fetch(ctx.fundPda);
+    expect(transferred.manager.toBase58()).to.equal(
+      successor.publicKey.toBase58(),
+    );
+    expect(transferred.creator.toBase58()).to.equal(originalManager.toBase58());
+    expect(transferred.pendingManager.toBase58()).to.equal(
+      anchor.web3.PublicKey.default.toBase58(),
+    );
+
+    await expectError(
+      ctx.program.methods
+        .setFundPaused(true)
+        .accounts({
+          authority: originalManager,
+          fundState: ctx.fundPda,
+          fundRoles: null,
+        })
+        .rpc(),
+      "Unauthorized",
+    );
+
+    const handBack = await getClockUnixTimestamp(ctx.provider.connection);
+    await propose(successor.publicKey, originalManager, handBack + 1, [
+      successor,
+    ]);
+    await new Promise((resolve) => setTimeout(resolve, 2000));
+    await accept(originalManager);
+    const restored = await ctx.program.account.fundState.fetch(ctx.fundPda);
+    expect(restored.manager.toBase58()).to.equal(originalManager.toBase58());
+  });
+});
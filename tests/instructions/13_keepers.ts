@@ -0,0 +1,111 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import { NATIVE_MINT } from "@solana/spl-token";
+import {
+  airdropIfNeeded,
+  ensureFund,
+  ensureGlobalConfig,
+  expectError,
+  getContext,
+} from "../helpers";
+
+const KEEPER_SCOPE_ORDERS = 1;
+const KEEPER_SCOPE_SWEEP = 4;
+
+describe("keepers", () => {
+  it("Scopes registered keepers by task and fund", async () => {
+    const ctx = await getContext();
+    await ensureGlobalConfig(ctx);
+    await ensureFund(ctx);
+
+    const keeper = anchor.web3.Keypair.generate();
+    await airdropIfNeeded(
+      ctx.provider,
+      keeper.publicKey,
+      anchor.web3.LAMPORTS_PER_SOL,
+    );
+    const keeperRecordPda = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("keeper"),
+        ctx.configPda.toBuffer(),
+        keeper.publicKey.toBuffer(),
+      ],
+      ctx.program.programId,
+    )[0];
+    const fundWsolVault = await anchor.utils.token.associatedAddress({
+      mint: NATIVE_MINT,
+      owner: ctx.fundPda,
+    });
+
+    const register = (scopes: number, fund: anchor.web3.PublicKey) =>
+      ctx.program.methods
+        .registerKeeper(ctx.configId, keeper.publicKey, scopes, fund)
+        .accounts({
+          config: ctx.configPda,
+          admin: ctx.provider.wallet.publicKey,
+          keeperRecord: keeperRecordPda,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+    const sweep = () =>
+      ctx.program.methods
+        .sweepWsol()
+        .accounts({
+          executor: keeper.publicKey,
+          config: ctx.configPda,
+          fundState: ctx.fundPda,
+          fundVault: ctx.vaultPda,
+          fundWsolVault,
+          keeperRecord: keeperRecordPda,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        })
+        .signers([keeper])
+        .rpc();
+
+    await expectError(register(0, ctx.fundPda), "InvalidKeeper");
+    await expectError(register(16, ctx.fundPda), "InvalidKeeper");
+
+    const otherFund = anchor.web3.Keypair.generate().publicKey;
+    await register(KEEPER_SCOPE_SWEEP, otherFund);
+    await expectError(sweep(), "Unauthorized");
+
+    await register(KEEPER_SCOPE_ORDERS, anchor.web3.PublicKey.default);
+    await expectError(sweep(), "Unauthorized");
+
+    await register(KEEPER_SCOPE_SWEEP, ctx.fundPda);
+    await sweep();
+    const record = await ctx.program.account.keeperRecord.fetch(
+      keeperRecordPda,
+    );
+    expect(record.scopes).to.equal(KEEPER_SCOPE_SWEEP);
+    expect(record.fund.toBase58()).to.equal(ctx.fundPda.toBase58());
+
+    await ctx.program.methods
+      .removeKeeper(ctx.configId)
+      .accounts({
+        config: ctx.configPda,
+        admin: ctx.provider.wallet.publicKey,
+        keeperRecord: keeperRecordPda,
+      })
+      .rpc();
+    expect(
+      await ctx.provider.connection.getAccountInfo(keeperRecordPda),
+    ).to.equal(null);
+    await expectError(
+      ctx.program.methods
+        .sweepWsol()
+        .accounts({
+          executor: keeper.publicKey,
+          config: ctx.configPda,
+          fundState: ctx.fundPda,
+          fundVault: ctx.vaultPda,
+          fundWsolVault,
+          keeperRecord: null,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        })
+        .signers([keeper])
+        .rpc(),
+      "Unauthorized",
+    );
+  });
+});
@@ -0,0 +1,72 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  ensureFund,
+  ensureGlobalConfig,
+  expectError,
+  getContext,
+} from "../helpers";
+
+describe("keeper-tips", () => {
+  it("Caps per-fund keeper tips by the config maximum", async () => {
+    const ctx = await getContext();
+    await ensureGlobalConfig(ctx);
+    await ensureFund(ctx);
+
+    const setMax = (maxTip: number) =>
+      ctx.program.methods
+        .setMaxKeeperTip(ctx.configId, new anchor.BN(maxTip))
+        .accounts({
+          config: ctx.configPda,
+          admin: ctx.provider.wallet.publicKey,
+        })
+        .rpc();
+    const setTip = (
+      tipLamports: number,
+      tipBps: number,
+      manager?: anchor.web3.Keypair,
+    ) =>
+      ctx.program.methods
+        .setKeeperTip(new anchor.BN(tipLamports), tipBps)
+        .accounts({
+          manager: manager
+            ? manager.publicKey
+            : ctx.provider.wallet.publicKey,
+          config: ctx.configPda,
+          fundState: ctx.fundPda,
+        })
+        .signers(manager ? [manager] : [])
+        .rpc();
+
+    await setMax(0);
+    await expectError(setTip(5_000, 0), "InvalidKeeperTip");
+
+    await setMax(10_000);
+    await expectError(setTip(5_000, 10_001), "InvalidFeeBps");
+    await expectError(
+      setTip(5_000, 10, anchor.web3.Keypair.generate()),
+      "Unauthorized",
+    );
+    await setTip(5_000, 10);
+
+    const config = await ctx.program.account.globalConfig.fetch(ctx.configPda);
+    expect(config.maxKeeperTipLamports.toNumber()).to.equal(10_000);
+    const fund = await ctx.program.account.fundState.fetch(ctx.fundPda);
+    expect(fund.keeperTipLamports.toNumber()).to.equal(5_000);
+    expect(fund.keeperTipBps).to.equal(10);
+
+    await expectError(
+      ctx.program.methods
+        .setMaxKeeperTip(ctx.configId, new anchor.BN(1))
+        .accounts({
+          config: ctx.configPda,
+          admin: anchor.web3.Keypair.generate().publicKey,
+        })
+        .rpc(),
+      "Signature verification failed",
+    );
+
+    await setTip(0, 0);
+    await setMax(0);
+  });
+});
@@ -0,0 +1,48 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  ensureFund,
+  ensureGlobalConfig,
+  expectError,
+  getContext,
+} from "../helpers";
+
+describe("permissionless-execution", () => {
+  it("Lets the manager open order execution to any executor", async () => {
+    const ctx = await getContext();
+    await ensureGlobalConfig(ctx);
+    await ensureFund(ctx);
+
+    const setPermissionless = (
+      enabled: boolean,
+      executorShareBps: number,
+      manager?: anchor.web3.Keypair,
+    ) =>
+      ctx.program.methods
+        .setPermissionlessExecution(enabled, executorShareBps)
+        .accounts({
+          manager: manager
+            ? manager.publicKey
+            : ctx.provider.wallet.publicKey,
+          fundState: ctx.fundPda,
+        })
+        .signers(manager ? [manager] : [])
+        .rpc();
+
+    await expectError(setPermissionless(true, 10_001), "InvalidFeeBps");
+    await expectError(
+      setPermissionless(true, 2_500, anchor.web3.Keypair.generate()),
+      "Unauthorized",
+    );
+
+    await setPermissionless(true, 2_500);
+    const enabled = await ctx.program.account.fundState.fetch(ctx.fundPda);
+    expect(enabled.permissionlessExecution).to.equal(true);
+    expect(enabled.executorShareBps).to.equal(2_500);
+
+    await setPermissionless(false, 0);
+    const disabled = await ctx.program.account.fundState.fetch(ctx.fundPda);
+    expect(disabled.permissionlessExecution).to.equal(false);
+    expect(disabled.executorShareBps).to.equal(0);
+  });
+});